@@ -13,18 +13,51 @@ cli-unable-to-request-confirmation = Unable to request confirmation.
     .winpty-workaround = If you are using a Bash emulator (like Git Bash), try running winpty.
 cli-backup-id-with-multiple-games = Cannot specify backup ID when restoring multiple games.
 cli-invalid-backup-id = Invalid backup ID.
+cli-file-not-in-backup = This file is not in the chosen backup: {$path}
+cli-ambiguous-game = More than one game matched, so none were chosen:
+cli-unrecognized-steam-user = No Steam user found for: {$user}
+cli-invalid-pattern = Invalid pattern: {$pattern}
 
 badge-failed = FAILED
 badge-duplicates = DUPLICATES
 badge-duplicated = DUPLICATED
 badge-ignored = IGNORED
+badge-invalid = INVALID
 badge-redirected-from = FROM: {$path}
 badge-redirecting-to = TO: {$path}
+# This is shown on a file that a game-specific rule leaves out of the backup.
+badge-excluded-by = EXCLUDED: {$rule}
+# This is shown when a game's backup is larger than the limit set for that game.
+badge-over-size-limit = OVER SIZE LIMIT
 
 some-entries-failed = Some entries failed to process; look for {badge-failed} in the output for details. Double check whether you can access those files or whether their paths are very long.
 
 cli-game-line-item-redirected = Redirected from: {$path}
 cli-game-line-item-redirecting = Redirecting to: {$path}
+cli-game-line-item-excluded = Excluded by rule: {$rule}
+cli-game-size-limit-exceeded = Warning: larger than the size limit of {$size-limit}
+cli-diff-no-changes = No changes
+cli-history-no-versions = No stored versions
+cli-manifest-info-entry = Manifest entry
+cli-manifest-info-candidates = Candidate paths
+cli-manifest-info-no-candidates = No candidate paths
+cli-manifest-info-not-found = not found
+cli-manifest-info-files = { $files ->
+    [one] 1 file
+   *[other] { $files } files
+}
+cli-manifest-info-from = From
+cli-manifest-info-from-steam-cloud = Steam cloud saves
+cli-manifest-info-from-steam-screenshots = Steam screenshots
+cli-manifest-info-from-wine-registry = Wine registry files
+cli-manifest-info-root = Root
+cli-manifest-info-excluded-ignored-path = Excluded by ignored path
+cli-manifest-info-excluded-steam-user = Excluded by Steam user filter
+cli-manifest-info-excluded-toggled = Deselected for this game
+cli-manifest-info-excluded-game-rule = Excluded by game rule
+cli-watch-started = Watching {$total-games} games for changes. Press Ctrl+C to stop.
+cli-serve-started = Listening on {$url}. Press Ctrl+C to stop.
+cli-serve-without-token = WARNING: No token is required, so other computers may be able to connect. Use --token to require one.
 
 button-backup = Back up
 button-preview = Preview
@@ -45,6 +78,11 @@ button-disable-all = Disable all
 button-customize = Customize
 button-exit = Exit
 button-comment = Comment
+button-compare = Compare
+# This opens the backup rules for one game.
+button-filter = Filter
+# This opens the backup format and retention for one game.
+button-game-settings = Settings
 
 no-roots-are-configured = Add some roots to back up even more data.
 
@@ -57,6 +95,12 @@ registry-issue = Error: Some registry entries were skipped.
 unable-to-browse-file-system = Error: Unable to browse on your system.
 unable-to-open-directory = Error: Unable to open directory:
 unable-to-open-url = Error: Unable to open URL:
+unable-to-migrate-renamed-game = Error: Unable to move the backups for a renamed game:
+game-did-not-launch = Error: Unable to launch the game:
+server-did-not-start = Error: Unable to start the server:
+# A hook is a command that the user configured to run before or after a backup or restore.
+hook-failed = Error: A hook failed:
+hook-failures = Hook failures
 
 processed-games = {$total-games} {$total-games ->
     [one] game
@@ -71,17 +115,38 @@ processed-size-subset = {$processed-size} of {$total-size}
 field-backup-target = Back up to:
 toggle-backup-merge = Merge
 field-restore-source = Restore from:
+# This lets you restore each game to how it was at some point in the past.
+field-restore-as-of = As of:
+    .placeholder = YYYY-MM-DD HH:MM (optional)
 field-custom-files = Paths:
 field-custom-registry = Registry:
+# These are folder names (not full paths) that a game may be installed under.
+field-custom-install-dir = Install folders:
+field-custom-steam-id = Steam ID:
+field-custom-gog-id = GOG ID:
 field-search = Search:
 field-sort = Sort:
 field-redirect-source =
     .placeholder = Source (original location)
 field-redirect-target =
     .placeholder = Target (new location)
+# These are limits that only apply to one game.
+field-game-max-file-size = Max file size:
+field-game-max-total-size = Size limit:
+    .placeholder = Example: 100 MB (optional)
+# When checked, the game uses its own setting instead of the one from the backup screen.
+field-game-override-retention = Custom retention
+field-game-override-format = Custom format
 field-roots = Roots:
 field-backup-excluded-items = Backup exclusions:
+# Glob patterns like `*.log` and regular expressions starting with `regex:` are also allowed.
+field-backup-excluded-path =
+    .placeholder = Path, glob, or regex:pattern
+field-backup-excluded-registry =
+    .placeholder = Key, glob, or regex:pattern
 field-redirects = Redirects:
+# Additional manifests that are layered on top of the main one.
+field-secondary-manifests = Secondary manifests:
 # This appears next to the number of full backups that you'd like to keep.
 # A full backup includes all save files for a game.
 field-retention-full = Full:
@@ -100,11 +165,42 @@ label-checked = Checked
 label-updated = Updated
 label-new = New
 label-comment = Comment
+# This is shown when comparing a backup with the files currently on the system.
+label-live-files = Live files
+# This is shown when comparing against an earlier backup, but there isn't one.
+label-no-backup = No backup
+
+# This secondary manifest is a file on this computer.
+secondary-manifest-local = Local file
+# This secondary manifest is downloaded from a URL.
+secondary-manifest-remote = URL
+
+# This custom game replaces the manifest entry with the same name.
+integration-override = Override
+# This custom game adds to the manifest entry with the same name.
+integration-extend = Extend
+
+# This is an option for only backing up a path when using a certain operating system.
+constraint-any-os = Any OS
+# This is an option for only backing up a path when using a certain store.
+constraint-any-store = Any store
+
+os-windows = Windows
+os-linux = Linux
+os-mac = Mac
+os-other = Other
+
+tag-save = Save
+tag-config = Config
+tag-other = Other
 
+store-bottles = Bottles
 store-epic = Epic
 store-gog = GOG
 store-gog-galaxy = GOG Galaxy
 store-heroic = Heroic
+store-itch = itch
+store-lutris = Lutris
 store-microsoft = Microsoft
 store-origin = Origin
 store-prime = Prime Gaming
@@ -154,11 +250,27 @@ confirm-restore =
 
 confirm-add-missing-roots = Add these roots?
 no-missing-roots = No additional roots found.
+confirm-migrate-renamed-games =
+    These games were renamed in the manifest.
+    Move their backups so that they're found under the new names?
+no-renamed-games = No backups need to be moved for renamed games.
 preparing-backup-target = Preparing backup directory...
 updating-manifest = Updating manifest...
+manifest-updated = Updated manifest: {$url}
+manifest-up-to-date = Manifest is already up to date: {$url}
+manifest-diff-no-changes = No changes to any games.
+manifest-diff-added = Added games:
+manifest-diff-removed = Removed games:
+manifest-diff-renamed = Renamed games:
+manifest-diff-changed = Games with changed paths:
+# This is shown next to games in a manifest update that you have already backed up.
+manifest-diff-backed-up = has backups
+manifest-diff-summary = Manifest updated: {$added} added, {$removed} removed, {$renamed} renamed, {$changed} changed
 
 saves-found = Save data found.
 no-saves-found = No save data found.
+file-version-restored = File restored.
+games-without-backup = No backup existed yet at that time for these games
 
 # This is tacked on to form something like "Back up (no confirmation)",
 # meaning we would perform an action without asking the user if they're sure.
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use crate::{
+    config::RootsConfig,
+    manifest::Store,
+    prelude::{StrictPath, TitleFinder},
+};
+
+//
+/// Deserialization of Bottles bottles/*/bottle.yml
+//
+#[derive(serde::Deserialize, Debug)]
+struct Bottle {
+    #[serde(rename = "Name")]
+    name: Option<String>,
+    #[serde(rename = "External_Programs", default)]
+    external_programs: HashMap<String, BottleProgram>,
+}
+#[derive(serde::Deserialize, Debug)]
+struct BottleProgram {
+    name: String,
+    folder: Option<String>,
+    path: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct MemorizedGame {
+    install_dir: StrictPath,
+    prefix: StrictPath,
+}
+
+//
+/// Main structure where games installed with Bottles are collected
+//
+#[derive(Clone, Default, Debug)]
+pub struct BottlesGames {
+    games: HashMap<(RootsConfig, String), MemorizedGame>,
+}
+
+impl BottlesGames {
+    pub fn get_prefix(&self, root: &RootsConfig, game: &str) -> Option<&StrictPath> {
+        self.games.get(&(root.clone(), game.to_string())).map(|x| &x.prefix)
+    }
+
+    pub fn get_install_dir(&self, root: &RootsConfig, game: &str) -> Option<&StrictPath> {
+        self.games
+            .get(&(root.clone(), game.to_string()))
+            .map(|x| &x.install_dir)
+    }
+
+    pub fn scan(roots: &[RootsConfig], title_finder: &TitleFinder) -> Self {
+        let mut instance = BottlesGames::default();
+
+        for root in roots {
+            if root.store == Store::Bottles {
+                instance.detect_games(root, title_finder);
+                log::trace!("scan found: {:#?}", instance.games);
+            }
+        }
+
+        instance
+    }
+
+    fn detect_games(&mut self, root: &RootsConfig, title_finder: &TitleFinder) {
+        let bottles_path = root.path.joined("bottles");
+        log::trace!("detect_games searching for bottles in {}", bottles_path.interpret());
+
+        let Ok(entries) = std::fs::read_dir(bottles_path.interpret()) else {
+            log::trace!(
+                "detect_games could not read {}, Bottles probably not used yet... skipping",
+                bottles_path.interpret()
+            );
+            return;
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let prefix = StrictPath::from_std_path_buf(&entry.path());
+            let config_path = prefix.joined("bottle.yml");
+            if !config_path.is_file() {
+                continue;
+            }
+
+            let bottle = match serde_yaml::from_str::<Bottle>(&config_path.read().unwrap_or_default()) {
+                Ok(bottle) => bottle,
+                Err(e) => {
+                    log::warn!(
+                        "detect_games ignoring bottle since it could not read {}: {}",
+                        config_path.interpret(),
+                        e
+                    );
+                    continue;
+                }
+            };
+            log::trace!(
+                "detect_games found bottle {:?} with {} programs",
+                bottle.name,
+                bottle.external_programs.len()
+            );
+
+            for program in bottle.external_programs.values() {
+                let Some(official) = title_finder.find_one(&[program.name.to_owned()], &None, &None, true, true, false)
+                else {
+                    log::info!("Ignoring unrecognized Bottles program: '{}'", program.name);
+                    continue;
+                };
+
+                let install_dir = match (&program.folder, &program.path) {
+                    (Some(folder), _) => StrictPath::new(folder.clone()),
+                    (None, Some(path)) => match std::path::Path::new(path).parent() {
+                        Some(parent) => StrictPath::from_std_path_buf(parent),
+                        None => continue,
+                    },
+                    (None, None) => continue,
+                };
+
+                log::trace!(
+                    "detect_games memorizing info for '{}' (from: '{}'): install_dir={:?}, prefix={:?}",
+                    official,
+                    program.name,
+                    &install_dir,
+                    &prefix
+                );
+                self.games.insert(
+                    (root.clone(), official),
+                    MemorizedGame {
+                        install_dir,
+                        prefix: prefix.clone(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manifest::Manifest, serialization::ResourceFile, testing::repo};
+    use maplit::hashmap;
+    use pretty_assertions::assert_eq;
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            windows-game:
+              files:
+                <base>/file1.txt: {}
+            proton-game:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn title_finder() -> TitleFinder {
+        TitleFinder::new(&manifest(), &Default::default())
+    }
+
+    #[test]
+    fn scan_finds_nothing_when_folder_does_not_exist() {
+        let roots = vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/nonexistent", repo())),
+            store: Store::Bottles,
+        }];
+        let games = BottlesGames::scan(&roots, &title_finder());
+        assert_eq!(HashMap::new(), games.games);
+    }
+
+    #[test]
+    fn scan_finds_only_recognized_programs() {
+        let roots = vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/bottles", repo())),
+            store: Store::Bottles,
+        }];
+        let games = BottlesGames::scan(&roots, &title_finder());
+        assert_eq!(
+            hashmap! {
+                (roots[0].clone(), "windows-game".to_string()) => MemorizedGame {
+                    install_dir: StrictPath::new("/home/root/.local/share/bottles/bottles/windows-game/drive_c/Games/Windows Game".to_string()),
+                    prefix: StrictPath::new(format!("{}/tests/launchers/bottles/bottles/windows-game", repo())),
+                },
+                (roots[0].clone(), "proton-game".to_string()) => MemorizedGame {
+                    install_dir: StrictPath::new("/home/root/.local/share/bottles/bottles/Other Apps/drive_c/Games/Proton Game".to_string()),
+                    prefix: StrictPath::new(format!("{}/tests/launchers/bottles/bottles/Other Apps", repo())),
+                },
+            },
+            games.games,
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use crate::{
     config::{Config, RootsConfig},
@@ -18,6 +18,10 @@ pub struct Cache {
     pub backup: Backup,
     #[serde(default)]
     pub restore: Restore,
+    /// Games renamed in the manifest (old name -> new name),
+    /// as detected by matching Steam/GOG IDs during manifest updates.
+    #[serde(default)]
+    pub renamed_games: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -104,6 +108,22 @@ impl Cache {
         if update.modified {
             cached.updated = Some(update.timestamp);
         }
+
+        if let Some(diff) = &update.diff {
+            for (old, new) in &diff.renamed {
+                // If a game is renamed back to an earlier name, forget the outdated rename.
+                self.renamed_games.remove(new);
+                self.renamed_games.insert(old.clone(), new.clone());
+            }
+        }
+    }
+
+    /// Combine the detected renames with the explicit aliases from the config,
+    /// with the config taking precedence.
+    pub fn game_aliases(&self, config: &Config) -> BTreeMap<String, String> {
+        let mut aliases = self.renamed_games.clone();
+        aliases.extend(config.aliases.clone());
+        aliases
     }
 
     pub fn add_roots(&mut self, roots: &Vec<RootsConfig>) {
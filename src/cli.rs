@@ -1,21 +1,27 @@
 mod parse;
 mod report;
+mod serve;
+mod watch;
+mod wrap;
 
 use parse::{Cli, CompletionShell, Subcommand};
 use report::Reporter;
 
 use crate::{
     cache::Cache,
-    config::{Config, SortKey},
-    heroic::HeroicGames,
+    config::{Config, SortKey, ToggledRegistry},
+    hooks::{HookOperation, HookRunner},
     lang::Translator,
+    launchers::Launchers,
     layout::BackupLayout,
-    manifest::Manifest,
+    manifest::{Game, Manifest, ManifestSource},
     prelude::{
-        app_dir, back_up_game, prepare_backup_target, scan_game_for_backup, scan_game_for_restoration, BackupId,
-        DuplicateDetector, Error, InstallDirRanking, OperationStepDecision, SteamShortcuts, StrictPath, TitleFinder,
+        app_dir, back_up_game, find_path_candidates, prepare_backup_target, scan_game_for_backup,
+        scan_game_for_restoration, BackupId, DuplicateDetector, Error, GameDiff, InstallDirRanking,
+        OperationStepDecision, RestoreFilter, SteamShortcuts, StrictPath, TitleFinder,
     },
-    serialization::ResourceFile,
+    serialization::{ResourceFile, SaveableResourceFile},
+    steam::SteamUsers,
 };
 use clap::CommandFactory;
 use indicatif::ParallelProgressIterator;
@@ -23,8 +29,18 @@ use rayon::{
     iter::{IntoParallelRefIterator, ParallelIterator},
     prelude::IndexedParallelIterator,
 };
+use std::collections::BTreeMap;
 
-use self::parse::ManifestSubcommand;
+use self::parse::{BackupsSubcommand, ManifestSubcommand};
+
+/// A game's entry for `manifest show`, along with where it came from.
+#[derive(serde::Serialize)]
+struct ShownGame<'a> {
+    #[serde(flatten)]
+    game: &'a Game,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<&'a ManifestSource>,
+}
 
 #[derive(Clone, Debug, Default)]
 struct GameSubjects {
@@ -76,6 +92,24 @@ fn warn_deprecations(by_steam_id: bool) {
     }
 }
 
+/// In error mode, a failed hook stops the whole operation.
+fn run_hooks_before_all(hooks: &HookRunner, reporter: &mut Reporter) -> Result<(), Error> {
+    let failures = hooks.before_all();
+    let blocked = hooks.blocks(&failures).then(|| Error::HookFailed {
+        command: failures[0].command.clone(),
+        why: failures[0].reason.clone(),
+    });
+    reporter.add_hook_failures(failures, hooks.failure_mode());
+
+    match blocked {
+        Some(error) => {
+            reporter.print_failure();
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
 pub fn parse() -> Cli {
     use clap::Parser;
     Cli::from_args()
@@ -101,6 +135,7 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             by_steam_id,
             wine_prefix,
             api,
+            api_stream,
             sort,
             format,
             compression,
@@ -111,7 +146,9 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
         } => {
             warn_deprecations(by_steam_id);
 
-            let mut reporter = if api {
+            let mut reporter = if api_stream {
+                Reporter::json_stream()
+            } else if api {
                 Reporter::json()
             } else {
                 Reporter::standard(translator)
@@ -121,10 +158,10 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 if let Err(e) = Manifest::update_mut(&config, &mut cache, true) {
                     eprintln!("{}", translator.handle_error(&e));
                 }
-                Manifest::load().unwrap_or_default()
+                Manifest::load_with_secondary(&config).unwrap_or_default()
             } else {
                 Manifest::update_mut(&config, &mut cache, update)?;
-                Manifest::load()?
+                Manifest::load_with_secondary(&config)?
             };
 
             let backup_dir = match path {
@@ -152,17 +189,8 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 }
             }
 
-            if !preview {
-                prepare_backup_target(&backup_dir, merge)?;
-            }
-
             let mut all_games = manifest;
-            for custom_game in &config.custom_games {
-                if custom_game.ignore {
-                    continue;
-                }
-                all_games.add_custom_game(custom_game.clone());
-            }
+            all_games.load_custom_games(&config);
 
             let games_specified = !games.is_empty();
             let subjects = GameSubjects::new(all_games.0.keys().cloned().collect(), games, by_steam_id, &all_games);
@@ -174,24 +202,40 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 });
             }
 
+            let hooks = HookRunner::new(&config.hooks, HookOperation::Backup, &backup_dir);
+            if !preview {
+                run_hooks_before_all(&hooks, &mut reporter)?;
+                if let Err(e) = prepare_backup_target(&backup_dir, merge) {
+                    hooks.after_all(false);
+                    return Err(e);
+                }
+            }
+
             log::info!("beginning backup with {} steps", subjects.valid.len());
 
             let mut retention = config.backup.retention.clone();
-            if let Some(full_limit) = full_limit {
-                retention.full = full_limit;
-            }
-            if let Some(differential_limit) = differential_limit {
-                retention.differential = differential_limit;
+            let mut game_retention = config.backup.retention_overrides();
+            for retention in std::iter::once(&mut retention).chain(game_retention.values_mut()) {
+                if let Some(full_limit) = full_limit {
+                    retention.full = full_limit;
+                }
+                if let Some(differential_limit) = differential_limit {
+                    retention.differential = differential_limit;
+                }
             }
 
-            let layout = BackupLayout::new(backup_dir.clone(), retention);
-            let title_finder = TitleFinder::new(&all_games, &layout);
-            let heroic_games = HeroicGames::scan(&roots, &title_finder, None);
-            let filter = config.backup.filter.clone();
+            let layout = BackupLayout::new(backup_dir.clone(), retention).with_game_retention(game_retention);
+            let title_finder = TitleFinder::new(&all_games, &layout).with_aliases(&cache.game_aliases(&config));
+            let launchers = Launchers::scan(&roots, &title_finder, None);
+            let filter = config.backup.filter.compile();
             let ranking = InstallDirRanking::scan(&roots, &all_games, &subjects.valid);
             let toggled_paths = config.backup.toggled_paths.clone();
             let toggled_registry = config.backup.toggled_registry.clone();
             let steam_shortcuts = SteamShortcuts::scan();
+            let stream = reporter.stream();
+            if let Some(stream) = stream {
+                stream.started(HookOperation::Backup, subjects.valid.len());
+            }
 
             let mut info: Vec<_> = subjects
                 .valid
@@ -203,34 +247,38 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                     let game = &all_games.0[name];
                     let steam_id = game.steam.as_ref().and_then(|x| x.id);
 
-                    let previous = layout.latest_backup(name, false, &config.redirects);
-
-                    let scan_info = scan_game_for_backup(
-                        game,
-                        name,
-                        &roots,
-                        &StrictPath::from_std_path_buf(&app_dir()),
-                        &heroic_games,
-                        &steam_id,
-                        &filter,
-                        &wine_prefix,
-                        &ranking,
-                        &toggled_paths,
-                        &toggled_registry,
-                        previous,
-                        &config.redirects,
-                        &steam_shortcuts,
-                    );
+                    let scan = || {
+                        scan_game_for_backup(
+                            game,
+                            name,
+                            &roots,
+                            &StrictPath::from_std_path_buf(&app_dir()),
+                            &launchers,
+                            &steam_id,
+                            &filter,
+                            &wine_prefix,
+                            &ranking,
+                            &toggled_paths,
+                            &toggled_registry,
+                            layout.latest_backup(name, false, &config.redirects),
+                            &config.redirects,
+                            &steam_shortcuts,
+                        )
+                    };
+                    let scan_info = scan();
+                    if let Some(stream) = stream {
+                        stream.game_scanned(&scan_info);
+                    }
                     let ignored = !&config.is_game_enabled_for_backup(name) && !games_specified;
                     let decision = if ignored {
                         OperationStepDecision::Ignored
                     } else {
                         OperationStepDecision::Processed
                     };
-                    let backup_info = if preview || ignored {
-                        crate::prelude::BackupInfo::default()
+                    let (scan_info, backup_info, hook_failures) = if preview || ignored {
+                        (scan_info, crate::prelude::BackupInfo::default(), vec![])
                     } else {
-                        let mut backup_format = config.backup.format.clone();
+                        let mut backup_format = config.backup.format_for(name);
                         if let Some(format) = format {
                             backup_format.chosen = format;
                         }
@@ -243,21 +291,37 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                                 .set_level(&backup_format.zip.compression, level);
                         }
 
-                        back_up_game(
-                            &scan_info,
-                            layout.game_layout(name),
-                            merge,
-                            &chrono::Utc::now(),
-                            &backup_format,
+                        hooks.around_game(
+                            scan_info,
+                            None,
+                            |_| scan(),
+                            |scan_info| {
+                                back_up_game(
+                                    scan_info,
+                                    layout.game_layout(name),
+                                    merge,
+                                    &chrono::Utc::now(),
+                                    &backup_format,
+                                    &|file, failed| {
+                                        if let Some(stream) = stream {
+                                            stream.file_processed(name, file, false, failed);
+                                        }
+                                    },
+                                )
+                            },
+                            || layout.latest_backup_name(name),
                         )
                     };
+                    if let Some(stream) = stream {
+                        stream.game_completed(&scan_info, &backup_info, &decision);
+                    }
                     log::trace!("step {i} completed");
-                    (name, scan_info, backup_info, decision)
+                    (name, scan_info, backup_info, decision, hook_failures)
                 })
                 .collect();
             log::info!("completed backup");
 
-            for (_, scan_info, _, _) in info.iter() {
+            for (_, scan_info, ..) in info.iter() {
                 if !scan_info.found_anything() {
                     continue;
                 }
@@ -284,11 +348,20 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 info.reverse();
             }
 
-            for (name, scan_info, backup_info, decision) in info {
+            let mut hook_failures = vec![];
+            for (name, scan_info, backup_info, decision, game_hook_failures) in info {
                 if !reporter.add_game(name, &scan_info, &backup_info, &decision, &duplicate_detector) {
                     failed = true;
                 }
+                hook_failures.extend(game_hook_failures);
+            }
+            if !preview {
+                hook_failures.extend(hooks.after_all(!failed));
+            }
+            if hooks.blocks(&hook_failures) {
+                failed = true;
             }
+            reporter.add_hook_failures(hook_failures, hooks.failure_mode());
             reporter.print(&backup_dir);
         }
         Subcommand::Restore {
@@ -297,13 +370,21 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             force,
             by_steam_id,
             api,
+            api_stream,
             sort,
             backup,
+            as_of,
+            include,
+            exclude,
+            steam_user,
+            to_steam_user,
             games,
         } => {
             warn_deprecations(by_steam_id);
 
-            let mut reporter = if api {
+            let mut reporter = if api_stream {
+                Reporter::json_stream()
+            } else if api {
                 Reporter::json()
             } else {
                 Reporter::standard(translator)
@@ -312,7 +393,7 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             if !Manifest::path().exists() {
                 Manifest::update_mut(&config, &mut cache, true)?;
             }
-            let manifest = Manifest::load()?;
+            let manifest = Manifest::load_with_secondary(&config)?;
 
             let restore_dir = match path {
                 None => config.restore.path.clone(),
@@ -337,7 +418,21 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             if backup.is_some() && games.len() != 1 {
                 return Err(Error::CliBackupIdWithMultipleGames);
             }
-            let backup_id = backup.as_ref().map(|x| BackupId::Named(x.clone()));
+            let backup_id = match (&backup, as_of) {
+                (Some(backup), _) => Some(BackupId::Named(backup.clone())),
+                (None, Some(as_of)) => Some(BackupId::AsOf(as_of)),
+                (None, None) => None,
+            };
+            let filter = RestoreFilter::new(&include, &exclude)?;
+
+            let steam_users = SteamUsers::scan(&config.expanded_roots());
+            let resolve_steam_user = |user: String| {
+                steam_users
+                    .resolve(&user)
+                    .ok_or(Error::CliUnrecognizedSteamUser { user })
+            };
+            let steam_user = steam_user.map(resolve_steam_user).transpose()?;
+            let to_steam_user = to_steam_user.map(resolve_steam_user).transpose()?;
 
             let games_specified = !games.is_empty();
             let subjects = GameSubjects::new(restorable_names, games, by_steam_id, &manifest);
@@ -349,7 +444,16 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 });
             }
 
+            let hooks = HookRunner::new(&config.hooks, HookOperation::Restore, &restore_dir);
+            if !preview {
+                run_hooks_before_all(&hooks, &mut reporter)?;
+            }
+
             log::info!("beginning restore with {} steps", subjects.valid.len());
+            let stream = reporter.stream();
+            if let Some(stream) = stream {
+                stream.started(HookOperation::Restore, subjects.valid.len());
+            }
 
             let mut info: Vec<_> = subjects
                 .valid
@@ -359,12 +463,20 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 .map(|(i, name)| {
                     log::trace!("step {i} / {}: {name}", subjects.valid.len());
                     let mut layout = layout.game_layout(name);
-                    let scan_info = scan_game_for_restoration(
+                    let mut scan_info = scan_game_for_restoration(
                         name,
                         backup_id.as_ref().unwrap_or(&BackupId::Latest),
                         &mut layout,
                         &config.redirects,
                     );
+                    scan_info.update_ignored(&config.restore.toggled_paths, &ToggledRegistry::default());
+                    filter.apply(&mut scan_info);
+                    if let Some(steam_user) = &steam_user {
+                        layout.retarget_steam_user(&mut scan_info, steam_user, to_steam_user.as_deref());
+                    }
+                    if let Some(stream) = stream {
+                        stream.game_scanned(&scan_info);
+                    }
                     let ignored = !&config.is_game_enabled_for_restore(name) && !games_specified;
                     let decision = if ignored {
                         OperationStepDecision::Ignored
@@ -375,6 +487,9 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                     if let Some(backup) = &backup {
                         if let Some(BackupId::Named(scanned_backup)) = scan_info.backup.as_ref().map(|x| x.id()) {
                             if backup != &scanned_backup {
+                                if let Some(stream) = stream {
+                                    stream.game_completed(&scan_info, &Default::default(), &decision);
+                                }
                                 log::trace!("step {i} completed (backup mismatch)");
                                 return (
                                     name,
@@ -382,27 +497,47 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                                     Default::default(),
                                     decision,
                                     Some(Err(Error::CliInvalidBackupId)),
+                                    vec![],
                                 );
                             }
                         }
                     }
 
-                    let restore_info = if scan_info.backup.is_none() || preview || ignored {
-                        crate::prelude::BackupInfo::default()
-                    } else {
-                        layout.restore(&scan_info)
+                    let processed = scan_info.backup.is_some() && !preview && !ignored;
+                    let backup_name = scan_info.backup.as_ref().map(|x| x.name().to_string());
+                    let (scan_info, restore_info, hook_failures) = match backup_name {
+                        Some(backup_name) if processed => hooks.around_game(
+                            scan_info,
+                            Some(&backup_name),
+                            |scan_info| scan_info,
+                            |scan_info| {
+                                layout.restore(scan_info, &|file, failed| {
+                                    if let Some(stream) = stream {
+                                        stream.file_processed(name, file, true, failed);
+                                    }
+                                })
+                            },
+                            || Some(backup_name.clone()),
+                        ),
+                        _ => (scan_info, crate::prelude::BackupInfo::default(), vec![]),
                     };
+                    if let Some(stream) = stream {
+                        stream.game_completed(&scan_info, &restore_info, &decision);
+                    }
                     log::trace!("step {i} completed");
-                    (name, scan_info, restore_info, decision, None)
+                    (name, scan_info, restore_info, decision, None, hook_failures)
                 })
                 .collect();
             log::info!("completed restore");
 
-            for (_, scan_info, _, _, failure) in info.iter() {
+            for (_, scan_info, _, _, failure, _) in info.iter() {
                 if !scan_info.found_anything() {
                     continue;
                 }
                 if let Some(failure) = failure {
+                    if !preview {
+                        hooks.after_all(false);
+                    }
                     return failure.clone();
                 }
                 duplicate_detector.add_game(scan_info);
@@ -428,11 +563,27 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 info.reverse();
             }
 
-            for (name, scan_info, backup_info, decision, _) in info {
+            let mut games_without_backup = vec![];
+            let mut hook_failures = vec![];
+            for (name, scan_info, backup_info, decision, _, game_hook_failures) in info {
+                if as_of.is_some() && scan_info.backup.is_none() && decision == OperationStepDecision::Processed {
+                    games_without_backup.push(name.clone());
+                }
                 if !reporter.add_game(name, &scan_info, &backup_info, &decision, &duplicate_detector) {
                     failed = true;
                 }
+                hook_failures.extend(game_hook_failures);
+            }
+            if !games_without_backup.is_empty() {
+                reporter.trip_games_without_backup(games_without_backup);
             }
+            if !preview {
+                hook_failures.extend(hooks.after_all(!failed));
+            }
+            if hooks.blocks(&hook_failures) {
+                failed = true;
+            }
+            reporter.add_hook_failures(hook_failures, hooks.failure_mode());
             reporter.print(&restore_dir);
         }
         Subcommand::Complete { shell } => {
@@ -451,6 +602,119 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             )
         }
         Subcommand::Backups {
+            sub:
+                Some(BackupsSubcommand::Extract {
+                    path,
+                    backup,
+                    to,
+                    api,
+                    game,
+                }),
+            ..
+        } => {
+            let mut reporter = if api {
+                Reporter::json()
+            } else {
+                Reporter::standard(translator)
+            };
+
+            if !Manifest::path().exists() {
+                Manifest::update_mut(&config, &mut cache, true)?;
+            }
+            let manifest = Manifest::load_with_secondary(&config)?;
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+            let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+
+            let subjects = GameSubjects::new(layout.restorable_games(), vec![game], false, &manifest);
+            if !subjects.invalid.is_empty() {
+                reporter.trip_unknown_games(subjects.invalid.clone());
+                reporter.print_failure();
+                return Err(crate::prelude::Error::CliUnrecognizedGames {
+                    games: subjects.invalid,
+                });
+            }
+            let name = &subjects.valid[0];
+            let mut game_layout = layout.game_layout(name);
+
+            let backup_id = match backup {
+                None => BackupId::Latest,
+                Some(backup) => {
+                    let id = BackupId::Named(backup);
+                    if game_layout.verify_id(&id) != id {
+                        return Err(Error::CliInvalidBackupId);
+                    }
+                    id
+                }
+            };
+
+            // Redirects are deliberately not applied here,
+            // since the files are going to the extraction folder instead.
+            let mut scan_info = scan_game_for_restoration(name, &backup_id, &mut game_layout, &[]);
+            game_layout.redirect_for_extraction(&mut scan_info, &to);
+            let extract_info = if scan_info.backup.is_none() {
+                crate::prelude::BackupInfo::default()
+            } else {
+                game_layout.extract(&scan_info, &to)
+            };
+
+            if !reporter.add_game(
+                name,
+                &scan_info,
+                &extract_info,
+                &OperationStepDecision::Processed,
+                &duplicate_detector,
+            ) {
+                failed = true;
+            }
+            reporter.print(&to);
+        }
+        Subcommand::Backups {
+            sub: Some(BackupsSubcommand::Migrate { path, force }),
+            ..
+        } => {
+            if !Manifest::path().exists() {
+                Manifest::update_mut(&config, &mut cache, true)?;
+            }
+            let mut manifest = Manifest::load_with_secondary(&config)?;
+            manifest.load_custom_games(&config);
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+            let mut layout = BackupLayout::new(restore_dir, config.backup.retention.clone())
+                .with_game_retention(config.backup.retention_overrides());
+
+            let renamed = layout.find_renamed_games(&manifest, &cache.game_aliases(&config));
+            if renamed.is_empty() {
+                println!("{}", translator.no_renamed_games());
+                return Ok(());
+            }
+
+            if !force {
+                match dialoguer::Confirm::new()
+                    .with_prompt(translator.confirm_migrate_renamed_games(&renamed))
+                    .interact()
+                {
+                    Ok(true) => (),
+                    Ok(false) => return Ok(()),
+                    Err(_) => return Err(Error::CliUnableToRequestConfirmation),
+                }
+            }
+
+            for (old, new) in &renamed {
+                if let Err(e) = layout.migrate_renamed_game(old, new) {
+                    eprintln!("{}", translator.handle_error(&e));
+                    failed = true;
+                }
+            }
+        }
+        Subcommand::Backups {
+            sub: None,
             path,
             by_steam_id,
             api,
@@ -468,7 +732,7 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             if !Manifest::path().exists() {
                 Manifest::update_mut(&config, &mut cache, true)?;
             }
-            let manifest = Manifest::load()?;
+            let manifest = Manifest::load_with_secondary(&config)?;
 
             let restore_dir = match path {
                 None => config.restore.path.clone(),
@@ -500,10 +764,222 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
                 .collect();
 
             for (name, scan_info) in info {
-                reporter.add_backup(name, &scan_info);
+                reporter.add_backup(name, &scan_info, config.backup.overrides.get(name));
             }
             reporter.print(&restore_dir);
         }
+        Subcommand::Diff {
+            path,
+            from,
+            to,
+            live,
+            wine_prefix,
+            api,
+            game,
+        } => {
+            let mut reporter = if api {
+                Reporter::json()
+            } else {
+                Reporter::standard(translator)
+            };
+            reporter.suppress_overall();
+
+            if !Manifest::path().exists() {
+                Manifest::update_mut(&config, &mut cache, true)?;
+            }
+            let mut manifest = Manifest::load_with_secondary(&config)?;
+            manifest.load_custom_games(&config);
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+            let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+
+            let subjects = GameSubjects::new(layout.restorable_games(), vec![game], false, &manifest);
+            if !subjects.invalid.is_empty() {
+                reporter.trip_unknown_games(subjects.invalid.clone());
+                reporter.print_failure();
+                return Err(crate::prelude::Error::CliUnrecognizedGames {
+                    games: subjects.invalid,
+                });
+            }
+            let name = &subjects.valid[0];
+            if live && !manifest.0.contains_key(name) {
+                // We need the game's manifest entry to scan the live files.
+                reporter.trip_unknown_games(subjects.valid.clone());
+                reporter.print_failure();
+                return Err(crate::prelude::Error::CliUnrecognizedGames {
+                    games: subjects.valid.clone(),
+                });
+            }
+            let game_layout = layout.game_layout(name);
+
+            let verify_id = |id: Option<String>| match id {
+                None => Ok(None),
+                Some(id) => {
+                    let id = BackupId::Named(id);
+                    if game_layout.verify_id(&id) == id {
+                        Ok(Some(id))
+                    } else {
+                        Err(Error::CliInvalidBackupId)
+                    }
+                }
+            };
+            let from = verify_id(from)?;
+            let to = verify_id(to)?;
+
+            if live {
+                let from = from.unwrap_or(BackupId::Latest);
+                let from_backup = game_layout.find_by_id_flattened(&from);
+
+                let roots = config.expanded_roots();
+                let title_finder = TitleFinder::new(&manifest, &layout).with_aliases(&cache.game_aliases(&config));
+                let launchers = Launchers::scan(&roots, &title_finder, None);
+                let ranking = InstallDirRanking::scan(&roots, &manifest, &subjects.valid);
+                let steam_shortcuts = SteamShortcuts::scan();
+                let game = &manifest.0[name];
+                let steam_id = game.steam.as_ref().and_then(|x| x.id);
+
+                let scan_info = scan_game_for_backup(
+                    game,
+                    name,
+                    &roots,
+                    &StrictPath::from_std_path_buf(&app_dir()),
+                    &launchers,
+                    &steam_id,
+                    &config.backup.filter.compile(),
+                    &wine_prefix,
+                    &ranking,
+                    &config.backup.toggled_paths,
+                    &config.backup.toggled_registry,
+                    None,
+                    &config.redirects,
+                    &steam_shortcuts,
+                );
+                let live_files = scan_info.found_files.into_iter().filter(|x| !x.ignored).collect();
+
+                let diff = GameDiff::new(&game_layout.restorable_files(&from, false, &[]), &live_files);
+                reporter.add_diff(name, from_backup.as_ref().map(|x| x.name()), None, &diff);
+            } else {
+                let to = to.unwrap_or(BackupId::Latest);
+                let to_backup = game_layout.find_by_id_flattened(&to);
+                let from_backup = match from {
+                    Some(from) => game_layout.find_by_id_flattened(&from),
+                    None => game_layout.previous_backup(&to),
+                };
+
+                let diff = game_layout.diff(from_backup.as_ref().map(|x| x.id()).as_ref(), &to);
+                reporter.add_diff(
+                    name,
+                    from_backup.as_ref().map(|x| x.name()),
+                    to_backup.as_ref().map(|x| x.name()),
+                    &diff,
+                );
+            }
+
+            reporter.print(&restore_dir);
+        }
+        Subcommand::History {
+            path,
+            restore,
+            api,
+            game,
+            file,
+        } => {
+            let mut reporter = if api {
+                Reporter::json()
+            } else {
+                Reporter::standard(translator)
+            };
+
+            if !Manifest::path().exists() {
+                Manifest::update_mut(&config, &mut cache, true)?;
+            }
+            let manifest = Manifest::load_with_secondary(&config)?;
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+            let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+
+            let subjects = GameSubjects::new(layout.restorable_games(), vec![game], false, &manifest);
+            if !subjects.invalid.is_empty() {
+                reporter.trip_unknown_games(subjects.invalid.clone());
+                reporter.print_failure();
+                return Err(crate::prelude::Error::CliUnrecognizedGames {
+                    games: subjects.invalid,
+                });
+            }
+            let name = &subjects.valid[0];
+            let mut game_layout = layout.game_layout(name);
+
+            match restore {
+                None => {
+                    reporter.suppress_overall();
+                    reporter.add_file_history(name, &file, &game_layout.file_history(&file));
+                }
+                Some(backup) => {
+                    let id = BackupId::Named(backup);
+                    if game_layout.verify_id(&id) != id {
+                        return Err(Error::CliInvalidBackupId);
+                    }
+
+                    let mut scan_info = scan_game_for_restoration(name, &id, &mut game_layout, &config.redirects);
+                    let restore_info = game_layout.restore_file_version(&mut scan_info, &file)?;
+
+                    if !reporter.add_game(
+                        name,
+                        &scan_info,
+                        &restore_info,
+                        &OperationStepDecision::Processed,
+                        &duplicate_detector,
+                    ) {
+                        failed = true;
+                    }
+                }
+            }
+
+            reporter.print(&restore_dir);
+        }
+        Subcommand::Watch {
+            path,
+            debounce,
+            poll,
+            wine_prefix,
+            games,
+        } => {
+            watch::run(
+                &config,
+                translator,
+                &mut cache,
+                path,
+                debounce,
+                poll,
+                wine_prefix,
+                games,
+            )?;
+        }
+        Subcommand::Serve { port, host, token } => {
+            serve::run(&config, translator, &mut cache, port, host, token)?;
+        }
+        Subcommand::Wrap {
+            path,
+            game,
+            steam_id,
+            gog_id,
+            normalized,
+            no_restore,
+            no_backup,
+            commands,
+        } => {
+            let code = wrap::run(
+                &config, translator, &mut cache, path, game, steam_id, gog_id, normalized, no_restore, no_backup,
+                commands,
+            )?;
+            std::process::exit(code);
+        }
         Subcommand::Find {
             api,
             path,
@@ -524,14 +1000,9 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             if let Err(e) = Manifest::update_mut(&config, &mut cache, false) {
                 eprintln!("{}", translator.handle_error(&e));
             }
-            let mut manifest = Manifest::load().unwrap_or_default();
+            let mut manifest = Manifest::load_with_secondary(&config).unwrap_or_default();
 
-            for custom_game in &config.custom_games {
-                if custom_game.ignore {
-                    continue;
-                }
-                manifest.add_custom_game(custom_game.clone());
-            }
+            manifest.load_custom_games(&config);
 
             let restore_dir = match path {
                 None => config.restore.path.clone(),
@@ -539,7 +1010,7 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
             };
             let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
 
-            let title_finder = TitleFinder::new(&manifest, &layout);
+            let title_finder = TitleFinder::new(&manifest, &layout).with_aliases(&cache.game_aliases(&config));
             let found = title_finder.find(&names, &steam_id, &gog_id, normalized, backup, restore);
             reporter.add_found_titles(&found);
 
@@ -558,18 +1029,111 @@ pub fn run(sub: Subcommand) -> Result<(), Error> {
 
             reporter.print(&restore_dir);
         }
-        Subcommand::Manifest { sub: manifest_sub } => {
-            if let Some(ManifestSubcommand::Show { api }) = manifest_sub {
-                let mut manifest = Manifest::load().unwrap_or_default();
+        Subcommand::Manifest { sub: manifest_sub } => match manifest_sub {
+            None => {}
+            Some(ManifestSubcommand::Show { api }) => {
+                let (mut manifest, mut sources) = Manifest::load_with_sources(&config).unwrap_or_default();
                 manifest.load_custom_games(&config);
+                for custom_game in config.custom_games.iter().filter(|x| !x.ignore) {
+                    sources.insert(custom_game.name.clone(), ManifestSource::Custom);
+                }
+
+                let shown: BTreeMap<_, _> = manifest
+                    .0
+                    .iter()
+                    .map(|(name, game)| {
+                        (
+                            name,
+                            ShownGame {
+                                game,
+                                source: sources.get(name),
+                            },
+                        )
+                    })
+                    .collect();
 
                 if api {
-                    println!("{}", serde_json::to_string(&manifest).unwrap());
+                    println!("{}", serde_json::to_string(&shown).unwrap());
                 } else {
-                    println!("{}", serde_yaml::to_string(&manifest).unwrap());
+                    println!("{}", serde_yaml::to_string(&shown).unwrap());
                 }
             }
-        }
+            Some(ManifestSubcommand::Update { force, api }) => {
+                let mut reporter = if api {
+                    Reporter::json()
+                } else {
+                    Reporter::standard(translator)
+                };
+                reporter.suppress_overall();
+
+                let updates = Manifest::update(config.manifest.clone(), cache.manifests.clone(), force)?;
+                let layout = BackupLayout::new(config.restore.path.clone(), config.backup.retention.clone());
+                let backed_up = layout.restorable_games().into_iter().collect();
+                for update in updates {
+                    reporter.add_manifest_update(&update, &backed_up);
+                    cache.update_manifest(update);
+                }
+                cache.save();
+
+                reporter.print(&config.restore.path);
+            }
+            Some(ManifestSubcommand::Info { wine_prefix, api, game }) => {
+                let mut reporter = if api {
+                    Reporter::json()
+                } else {
+                    Reporter::standard(translator)
+                };
+                reporter.suppress_overall();
+
+                if !Manifest::path().exists() {
+                    Manifest::update_mut(&config, &mut cache, true)?;
+                }
+                let mut manifest = Manifest::load_with_secondary(&config)?;
+                manifest.load_custom_games(&config);
+
+                let subjects = GameSubjects::new(manifest.0.keys().cloned().collect(), vec![game], false, &manifest);
+                if !subjects.invalid.is_empty() {
+                    reporter.trip_unknown_games(subjects.invalid.clone());
+                    reporter.print_failure();
+                    return Err(crate::prelude::Error::CliUnrecognizedGames {
+                        games: subjects.invalid,
+                    });
+                }
+                let name = &subjects.valid[0];
+                let game = &manifest.0[name];
+
+                let roots = config.expanded_roots();
+                let layout = BackupLayout::new(config.backup.path.clone(), config.backup.retention.clone());
+                let title_finder = TitleFinder::new(&manifest, &layout).with_aliases(&cache.game_aliases(&config));
+                let launchers = Launchers::scan(&roots, &title_finder, None);
+                let ranking = InstallDirRanking::scan(&roots, &manifest, &subjects.valid);
+                let steam_shortcuts = SteamShortcuts::scan();
+                let steam_id = game.steam.as_ref().and_then(|x| x.id);
+                let filter = config.backup.filter.compile();
+
+                let candidates: Vec<_> = find_path_candidates(
+                    game,
+                    name,
+                    &roots,
+                    &StrictPath::from_std_path_buf(&app_dir()),
+                    &launchers,
+                    &steam_id,
+                    &config.backup.filter,
+                    &wine_prefix,
+                    &ranking,
+                    &steam_shortcuts,
+                )
+                .into_iter()
+                .map(|candidate| {
+                    let check = candidate.check(name, &filter, &config.backup.toggled_paths);
+                    (candidate, check)
+                })
+                .collect();
+
+                reporter.add_manifest_info(name, game, &candidates);
+                reporter.print(&config.backup.path);
+            }
+        },
     }
 
     if failed {
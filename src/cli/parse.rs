@@ -9,6 +9,15 @@ fn parse_strict_path(path: &str) -> StrictPath {
     StrictPath::new(path.to_owned())
 }
 
+fn parse_glob(pattern: &str) -> Result<String, globetter::PatternError> {
+    globetter::Pattern::new(pattern)?;
+    Ok(pattern.to_owned())
+}
+
+fn parse_as_of(raw: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    crate::prelude::parse_as_of(raw).ok_or_else(|| format!("Unrecognized date/time: {raw}"))
+}
+
 fn parse_existing_strict_path(path: &str) -> Result<StrictPath, std::io::Error> {
     let sp = StrictPath::new(path.to_owned());
     std::fs::canonicalize(sp.interpret())?;
@@ -139,6 +148,11 @@ pub enum Subcommand {
         #[clap(long)]
         api: bool,
 
+        /// Print events to stdout as they happen, with one JSON object per line.
+        /// This replaces the default, human-readable output.
+        #[clap(long, conflicts_with("api"))]
+        api_stream: bool,
+
         /// Sort the game list by different criteria.
         /// When not specified, this defers to the config file.
         #[clap(long, possible_values = CliSort::ALL)]
@@ -205,6 +219,11 @@ pub enum Subcommand {
         #[clap(long)]
         api: bool,
 
+        /// Print events to stdout as they happen, with one JSON object per line.
+        /// This replaces the default, human-readable output.
+        #[clap(long, conflicts_with("api"))]
+        api_stream: bool,
+
         /// Sort the game list by different criteria.
         /// When not specified, this defers to Ludusavi's config file.
         #[clap(long, possible_values = CliSort::ALL)]
@@ -215,6 +234,38 @@ pub enum Subcommand {
         #[clap(long)]
         backup: Option<String>,
 
+        /// Restore each game's newest backup from at or before this point in time,
+        /// instead of its latest backup.
+        /// This can be RFC 3339 (e.g., `2023-03-01T12:00:00Z`)
+        /// or a local date and time (e.g., `2023-03-01T12:00:00`).
+        /// A date without a time (e.g., `2023-03-01`) means the end of that day.
+        #[clap(long, conflicts_with("backup"), parse(try_from_str = parse_as_of))]
+        as_of: Option<chrono::DateTime<chrono::Utc>>,
+
+        /// Only restore files whose original path (or a parent folder of it)
+        /// matches one of these glob patterns.
+        /// This can be specified multiple times.
+        #[clap(long, parse(try_from_str = parse_glob), number_of_values = 1)]
+        include: Vec<String>,
+
+        /// Don't restore files whose original path (or a parent folder of it)
+        /// matches one of these glob patterns.
+        /// This takes precedence over `--include`.
+        /// This can be specified multiple times.
+        #[clap(long, parse(try_from_str = parse_glob), number_of_values = 1)]
+        exclude: Vec<String>,
+
+        /// Only restore Steam `userdata` files (like cloud saves and screenshots)
+        /// that were backed up from this Steam user.
+        /// This can be an account ID, a 64-bit Steam ID, an account name, or a persona name.
+        #[clap(long)]
+        steam_user: Option<String>,
+
+        /// Restore the files from `--steam-user` into this Steam user's folder instead.
+        /// This accepts the same formats as `--steam-user`.
+        #[clap(long, requires("steam-user"))]
+        to_steam_user: Option<String>,
+
         /// Only restore these specific games.
         #[clap()]
         games: Vec<String>,
@@ -226,6 +277,9 @@ pub enum Subcommand {
     },
     /// Show backups
     Backups {
+        #[clap(subcommand)]
+        sub: Option<BackupsSubcommand>,
+
         /// Directory in which to find backups.
         /// When unset, this defaults to the restore path from the config file.
         #[clap(long, parse(from_str = parse_strict_path))]
@@ -249,6 +303,159 @@ pub enum Subcommand {
         #[clap()]
         games: Vec<String>,
     },
+    /// Compare a game's backups with each other or with the current files
+    Diff {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, parse(from_str = parse_strict_path))]
+        path: Option<StrictPath>,
+
+        /// Backup to compare from, using an ID returned by the `backups` command.
+        /// When not specified, this is the latest backup if `--live` is set,
+        /// or else the backup made just before the `--to` backup.
+        #[clap(long)]
+        from: Option<String>,
+
+        /// Backup to compare to, using an ID returned by the `backups` command.
+        /// When not specified, this is the latest backup.
+        #[clap(long)]
+        to: Option<String>,
+
+        /// Compare to the files currently on the system instead of another backup.
+        #[clap(long, conflicts_with("to"))]
+        live: bool,
+
+        /// Extra Wine/Proton prefix to check for saves when using `--live`.
+        /// This should be a folder with an immediate child folder named "drive_c" (or another letter).
+        #[clap(long, parse(from_str = parse_strict_path))]
+        wine_prefix: Option<StrictPath>,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Game to compare.
+        #[clap()]
+        game: String,
+    },
+    /// Show every stored version of a file across a game's backups
+    History {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, parse(from_str = parse_strict_path))]
+        path: Option<StrictPath>,
+
+        /// Restore the file as it was in this backup, using an ID listed by this command.
+        /// Only this one file is restored, and redirects from the config file still apply.
+        #[clap(long)]
+        restore: Option<String>,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Game whose backups to check.
+        #[clap()]
+        game: String,
+
+        /// Original location of the file, as shown when restoring.
+        #[clap(parse(from_str = parse_strict_path))]
+        file: StrictPath,
+    },
+    /// Keep running and back up games automatically when their saves change
+    ///
+    /// Save locations are resolved once at startup and then watched for changes.
+    /// They are resolved again whenever the config file or manifest changes.
+    /// Each backup is merged into the backup folder.
+    Watch {
+        /// Directory in which to store backups.
+        /// When unset, this defaults to the value from the config file.
+        #[clap(long, parse(from_str = parse_strict_path))]
+        path: Option<StrictPath>,
+
+        /// How many seconds to wait after the last change before backing up a game.
+        /// This avoids making several backups while a game is still writing its saves.
+        #[clap(long, default_value = "5")]
+        debounce: u64,
+
+        /// Check for changes periodically instead of relying on system notifications.
+        /// This may help with network drives or other file systems that don't report changes.
+        #[clap(long)]
+        poll: bool,
+
+        /// Extra Wine/Proton prefix to check for saves. This should be a folder
+        /// with an immediate child folder named "drive_c" (or another letter).
+        #[clap(long, parse(from_str = parse_strict_path))]
+        wine_prefix: Option<StrictPath>,
+
+        /// Only watch these specific games.
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Keep running and answer requests from other programs over a local HTTP/JSON API
+    ///
+    /// The manifest, config, and backups are loaded once at startup and then reused between requests.
+    /// They are loaded again whenever the config file or manifest changes.
+    /// Responses use the same JSON format as the `--api` option of the matching commands.
+    Serve {
+        /// Port on which to listen.
+        #[clap(long, default_value = "61234")]
+        port: u16,
+
+        /// Address on which to listen.
+        /// By default, only programs on this computer can connect.
+        #[clap(long, default_value = "127.0.0.1")]
+        host: std::net::IpAddr,
+
+        /// Only accept requests with this value in an `Authorization: Bearer <token>` header.
+        #[clap(long)]
+        token: Option<String>,
+    },
+    /// Wrap restore/backup around a game launch
+    ///
+    /// Before launching the game, the latest backup is restored if it is newer than the current files.
+    /// Once the game (and any processes it started) exit, the game is backed up.
+    /// Problems with the restore or backup are reported, but don't prevent the game from launching,
+    /// and the game's exit code is passed through.
+    ///
+    /// Example for Steam launch options: `ludusavi wrap --steam-id 123 -- %command%`
+    Wrap {
+        /// Directory in which to find and store backups.
+        /// When unset, this defaults to the restore and backup paths from the config file.
+        #[clap(long, parse(from_str = parse_strict_path))]
+        path: Option<StrictPath>,
+
+        /// Look up game by an exact title.
+        #[clap(long, required_unless_present_any(&["steam-id", "gog-id"]))]
+        game: Option<String>,
+
+        /// Look up game by a Steam ID.
+        #[clap(long)]
+        steam_id: Option<u32>,
+
+        /// Look up game by a GOG ID.
+        #[clap(long)]
+        gog_id: Option<u64>,
+
+        /// Look up game by an approximation of the title.
+        /// Ignores capitalization, "edition" suffixes, year suffixes, and some special symbols.
+        #[clap(long)]
+        normalized: bool,
+
+        /// Don't restore before launching the game.
+        #[clap(long)]
+        no_restore: bool,
+
+        /// Don't back up after the game exits.
+        #[clap(long)]
+        no_backup: bool,
+
+        /// Command to launch the game, along with its arguments.
+        #[clap(required = true, last = true)]
+        commands: Vec<String>,
+    },
     /// Find game titles
     ///
     /// Precedence: Steam ID -> exact names -> normalized names.
@@ -305,19 +512,82 @@ pub enum Subcommand {
 impl Subcommand {
     pub fn api(&self) -> bool {
         match self {
-            Self::Backup { api, .. } => *api,
-            Self::Restore { api, .. } => *api,
+            Self::Backup { api, api_stream, .. } => *api || *api_stream,
+            Self::Restore { api, api_stream, .. } => *api || *api_stream,
+            Self::Backups {
+                sub: Some(BackupsSubcommand::Extract { api, .. }),
+                ..
+            } => *api,
             Self::Backups { api, .. } => *api,
+            Self::Diff { api, .. } => *api,
+            Self::History { api, .. } => *api,
+            Self::Watch { .. } => false,
+            Self::Serve { .. } => false,
+            Self::Wrap { .. } => false,
             Self::Find { api, .. } => *api,
             Self::Manifest {
                 sub: Some(ManifestSubcommand::Show { api }),
             } => *api,
+            Self::Manifest {
+                sub: Some(ManifestSubcommand::Update { api, .. }),
+            } => *api,
+            Self::Manifest {
+                sub: Some(ManifestSubcommand::Info { api, .. }),
+            } => *api,
             Self::Manifest { .. } => false,
             Self::Complete { .. } => false,
         }
     }
 }
 
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum BackupsSubcommand {
+    /// Copy the files of a backup into a folder, without restoring them.
+    ///
+    /// Files are placed under the target folder according to their original location
+    /// (e.g., `drive-C/Users/...`), ignoring any redirects from the config file.
+    /// Registry data, if any, is written to `registry.yaml` in the target folder.
+    Extract {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, parse(from_str = parse_strict_path))]
+        path: Option<StrictPath>,
+
+        /// Extract a specific backup, using an ID returned by the `backups` command.
+        /// When not specified, this is the latest backup.
+        #[clap(long)]
+        backup: Option<String>,
+
+        /// Directory in which to place the extracted files.
+        #[clap(long, parse(from_str = parse_strict_path))]
+        to: StrictPath,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Game to extract.
+        #[clap()]
+        game: String,
+    },
+    /// Move the backups of games that were renamed in the manifest,
+    /// so that they're found under the games' current names.
+    ///
+    /// Renames are detected by matching Steam/GOG IDs during manifest updates,
+    /// or by the `aliases` in the config file.
+    Migrate {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, parse(from_str = parse_strict_path))]
+        path: Option<StrictPath>,
+
+        /// Don't ask for confirmation.
+        #[clap(long)]
+        force: bool,
+    },
+}
+
 #[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
 pub enum ManifestSubcommand {
     /// Print the content of the manifest, including any custom entries.
@@ -326,6 +596,37 @@ pub enum ManifestSubcommand {
         #[clap(long)]
         api: bool,
     },
+    /// Check for manifest updates and report what changed.
+    ///
+    /// Games that have existing backups are marked if their entry was removed,
+    /// renamed, or had its paths changed.
+    Update {
+        /// Download the manifests even if they were checked recently.
+        #[clap(long)]
+        force: bool,
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+    },
+    /// Show a game's manifest entry and how its paths resolve on this machine.
+    ///
+    /// For each configured root, this lists every concrete path that a backup would check,
+    /// whether it exists, how many files it matched,
+    /// and how many of those files would be excluded (and why).
+    Info {
+        /// Extra Wine/Proton prefix to check for saves. This should be a folder
+        /// with an immediate child folder named "drive_c" (or another letter).
+        #[clap(long, parse(from_str = parse_strict_path))]
+        wine_prefix: Option<StrictPath>,
+
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+
+        /// Game to inspect.
+        #[clap()]
+        game: String,
+    },
 }
 
 #[derive(clap::Parser, Clone, Debug, PartialEq, Eq)]
@@ -388,6 +689,7 @@ mod tests {
                     by_steam_id: false,
                     wine_prefix: None,
                     api: false,
+                    api_stream: false,
                     sort: None,
                     format: None,
                     compression: None,
@@ -444,6 +746,7 @@ mod tests {
                     by_steam_id: true,
                     wine_prefix: Some(StrictPath::new(s("tests/wine-prefix"))),
                     api: true,
+                    api_stream: false,
                     sort: Some(CliSort::Name),
                     format: Some(BackupFormat::Zip),
                     compression: Some(ZipCompression::Bzip2),
@@ -473,6 +776,7 @@ mod tests {
                     by_steam_id: false,
                     wine_prefix: None,
                     api: false,
+                    api_stream: false,
                     sort: None,
                     format: None,
                     compression: None,
@@ -502,6 +806,7 @@ mod tests {
                     by_steam_id: false,
                     wine_prefix: None,
                     api: false,
+                    api_stream: false,
                     sort: None,
                     format: None,
                     compression: None,
@@ -531,6 +836,37 @@ mod tests {
                     by_steam_id: false,
                     wine_prefix: None,
                     api: false,
+                    api_stream: false,
+                    sort: None,
+                    format: None,
+                    compression: None,
+                    compression_level: None,
+                    full_limit: None,
+                    differential_limit: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backup_with_api_stream() {
+        check_args(
+            &["ludusavi", "backup", "--api-stream"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Backup {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    merge: false,
+                    no_merge: false,
+                    update: false,
+                    try_update: false,
+                    by_steam_id: false,
+                    wine_prefix: None,
+                    api: false,
+                    api_stream: true,
                     sort: None,
                     format: None,
                     compression: None,
@@ -543,6 +879,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_cli_backup_with_api_and_api_stream() {
+        check_args_err(
+            &["ludusavi", "backup", "--api", "--api-stream"],
+            clap::ErrorKind::ArgumentConflict,
+        );
+    }
+
     #[test]
     fn rejects_cli_backup_with_update_and_try_update() {
         check_args_err(
@@ -576,6 +920,7 @@ mod tests {
                         by_steam_id: false,
                         wine_prefix: None,
                         api: false,
+                        api_stream: false,
                         sort: Some(sort),
                         format: None,
                         compression: None,
@@ -606,6 +951,7 @@ mod tests {
                     by_steam_id: false,
                     wine_prefix: None,
                     api: false,
+                    api_stream: false,
                     sort: None,
                     format: None,
                     compression: None,
@@ -630,8 +976,14 @@ mod tests {
                     force: false,
                     by_steam_id: false,
                     api: false,
+                    api_stream: false,
                     sort: None,
                     backup: None,
+                    as_of: None,
+                    include: vec![],
+                    exclude: vec![],
+                    steam_user: None,
+                    to_steam_user: None,
                     games: vec![],
                 }),
             },
@@ -654,6 +1006,16 @@ mod tests {
                 "name",
                 "--backup",
                 ".",
+                "--include",
+                "/saves/slot1/*",
+                "--include",
+                "/saves/slot2",
+                "--exclude",
+                "*.ini",
+                "--steam-user",
+                "first",
+                "--to-steam-user",
+                "24680",
                 "game1",
                 "game2",
             ],
@@ -665,8 +1027,14 @@ mod tests {
                     force: true,
                     by_steam_id: true,
                     api: true,
+                    api_stream: false,
                     sort: Some(CliSort::Name),
                     backup: Some(s(".")),
+                    as_of: None,
+                    include: vec![s("/saves/slot1/*"), s("/saves/slot2")],
+                    exclude: vec![s("*.ini")],
+                    steam_user: Some(s("first")),
+                    to_steam_user: Some(s("24680")),
                     games: vec![s("game1"), s("game2")],
                 }),
             },
@@ -681,6 +1049,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accepts_cli_restore_with_as_of() {
+        check_args(
+            &["ludusavi", "restore", "--as-of", "2000-01-02T03:04:05Z"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Restore {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    by_steam_id: false,
+                    api: false,
+                    api_stream: false,
+                    sort: None,
+                    backup: None,
+                    as_of: Some(
+                        chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+                            .unwrap()
+                            .and_hms_opt(3, 4, 5)
+                            .unwrap()
+                            .and_local_timezone(chrono::Utc)
+                            .unwrap(),
+                    ),
+                    include: vec![],
+                    exclude: vec![],
+                    steam_user: None,
+                    to_steam_user: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_invalid_as_of() {
+        check_args_err(
+            &["ludusavi", "restore", "--as-of", "yesterday"],
+            clap::ErrorKind::ValueValidation,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_backup_and_as_of() {
+        check_args_err(
+            &["ludusavi", "restore", "--backup", ".", "--as-of", "2000-01-02", "game1"],
+            clap::ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_invalid_include_glob() {
+        check_args_err(
+            &["ludusavi", "restore", "--include", "[a"],
+            clap::ErrorKind::ValueValidation,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_to_steam_user_but_no_steam_user() {
+        check_args_err(
+            &["ludusavi", "restore", "--to-steam-user", "24680"],
+            clap::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
     #[test]
     fn accepts_cli_restore_with_sort_variants() {
         let cases = [
@@ -701,8 +1134,14 @@ mod tests {
                         force: false,
                         by_steam_id: false,
                         api: false,
+                        api_stream: false,
                         sort: Some(sort),
                         backup: None,
+                        as_of: None,
+                        include: vec![],
+                        exclude: vec![],
+                        steam_user: None,
+                        to_steam_user: None,
                         games: vec![],
                     }),
                 },
@@ -710,6 +1149,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn accepts_cli_manifest_update() {
+        check_args(
+            &["ludusavi", "manifest", "update", "--force", "--api"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Manifest {
+                    sub: Some(ManifestSubcommand::Update { force: true, api: true }),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_manifest_info_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "manifest",
+                "info",
+                "--wine-prefix",
+                "tests/wine-prefix",
+                "--api",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Manifest {
+                    sub: Some(ManifestSubcommand::Info {
+                        wine_prefix: Some(StrictPath::new(s("tests/wine-prefix"))),
+                        api: true,
+                        game: s("game1"),
+                    }),
+                }),
+            },
+        );
+    }
+
     #[test]
     fn accepts_cli_complete_for_bash() {
         check_args(
@@ -782,6 +1259,7 @@ mod tests {
             Cli {
                 config: None,
                 sub: Some(Subcommand::Backups {
+                    sub: None,
                     path: None,
                     by_steam_id: false,
                     api: false,
@@ -807,6 +1285,7 @@ mod tests {
             Cli {
                 config: None,
                 sub: Some(Subcommand::Backups {
+                    sub: None,
                     path: Some(StrictPath::new(s("tests/backup"))),
                     by_steam_id: true,
                     api: true,
@@ -816,6 +1295,400 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accepts_cli_backups_extract_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "backups", "extract", "--to", "tests/extract", "game1"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Backups {
+                    sub: Some(BackupsSubcommand::Extract {
+                        path: None,
+                        backup: None,
+                        to: StrictPath::new(s("tests/extract")),
+                        api: false,
+                        game: s("game1"),
+                    }),
+                    path: None,
+                    by_steam_id: false,
+                    api: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backups_extract_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "backups",
+                "extract",
+                "--path",
+                "tests/backup",
+                "--backup",
+                ".",
+                "--to",
+                "tests/extract",
+                "--api",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Backups {
+                    sub: Some(BackupsSubcommand::Extract {
+                        path: Some(StrictPath::new(s("tests/backup"))),
+                        backup: Some(s(".")),
+                        to: StrictPath::new(s("tests/extract")),
+                        api: true,
+                        game: s("game1"),
+                    }),
+                    path: None,
+                    by_steam_id: false,
+                    api: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_backups_extract_without_target() {
+        check_args_err(
+            &["ludusavi", "backups", "extract", "game1"],
+            clap::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backups_migrate_with_all_arguments() {
+        check_args(
+            &["ludusavi", "backups", "migrate", "--path", "tests/backup", "--force"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Backups {
+                    sub: Some(BackupsSubcommand::Migrate {
+                        path: Some(StrictPath::new(s("tests/backup"))),
+                        force: true,
+                    }),
+                    path: None,
+                    by_steam_id: false,
+                    api: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_history_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "history", "game1", "/save.dat"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::History {
+                    path: None,
+                    restore: None,
+                    api: false,
+                    game: s("game1"),
+                    file: StrictPath::new(s("/save.dat")),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_history_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "history",
+                "--path",
+                "tests/backup",
+                "--restore",
+                "backup-1",
+                "--api",
+                "game1",
+                "/save.dat",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::History {
+                    path: Some(StrictPath::new(s("tests/backup"))),
+                    restore: Some(s("backup-1")),
+                    api: true,
+                    game: s("game1"),
+                    file: StrictPath::new(s("/save.dat")),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_watch_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "watch"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Watch {
+                    path: None,
+                    debounce: 5,
+                    poll: false,
+                    wine_prefix: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_watch_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "watch",
+                "--path",
+                "tests/backup",
+                "--debounce",
+                "30",
+                "--poll",
+                "--wine-prefix",
+                "tests/wine-prefix",
+                "game1",
+                "game2",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Watch {
+                    path: Some(StrictPath::new(s("tests/backup"))),
+                    debounce: 30,
+                    poll: true,
+                    wine_prefix: Some(StrictPath::new(s("tests/wine-prefix"))),
+                    games: vec![s("game1"), s("game2")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_serve_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "serve"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Serve {
+                    port: 61234,
+                    host: std::net::IpAddr::from([127, 0, 0, 1]),
+                    token: None,
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_serve_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi", "serve", "--port", "8000", "--host", "0.0.0.0", "--token", "secret",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Serve {
+                    port: 8000,
+                    host: std::net::IpAddr::from([0, 0, 0, 0]),
+                    token: Some(s("secret")),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_serve_with_invalid_host() {
+        check_args_err(
+            &["ludusavi", "serve", "--host", "localhost"],
+            clap::ErrorKind::ValueValidation,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_wrap_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "wrap", "--game", "game1", "--", "game.exe"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Wrap {
+                    path: None,
+                    game: Some(s("game1")),
+                    steam_id: None,
+                    gog_id: None,
+                    normalized: false,
+                    no_restore: false,
+                    no_backup: false,
+                    commands: vec![s("game.exe")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_wrap_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "wrap",
+                "--path",
+                "tests/backup",
+                "--game",
+                "game1",
+                "--steam-id",
+                "101",
+                "--gog-id",
+                "102",
+                "--normalized",
+                "--no-restore",
+                "--no-backup",
+                "--",
+                "game.exe",
+                "--fullscreen",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Wrap {
+                    path: Some(StrictPath::new(s("tests/backup"))),
+                    game: Some(s("game1")),
+                    steam_id: Some(101),
+                    gog_id: Some(102),
+                    normalized: true,
+                    no_restore: true,
+                    no_backup: true,
+                    commands: vec![s("game.exe"), s("--fullscreen")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_wrap_with_only_steam_id() {
+        check_args(
+            &["ludusavi", "wrap", "--steam-id", "101", "--", "game.exe"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Wrap {
+                    path: None,
+                    game: None,
+                    steam_id: Some(101),
+                    gog_id: None,
+                    normalized: false,
+                    no_restore: false,
+                    no_backup: false,
+                    commands: vec![s("game.exe")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_wrap_without_game() {
+        check_args_err(
+            &["ludusavi", "wrap", "--", "game.exe"],
+            clap::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_wrap_without_command() {
+        check_args_err(
+            &["ludusavi", "wrap", "--game", "game1"],
+            clap::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_diff_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "diff", "game1"],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Diff {
+                    path: None,
+                    from: None,
+                    to: None,
+                    live: false,
+                    wine_prefix: None,
+                    api: false,
+                    game: s("game1"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_diff_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "diff",
+                "--path",
+                "tests/backup",
+                "--from",
+                "backup-1",
+                "--to",
+                "backup-2",
+                "--api",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Diff {
+                    path: Some(StrictPath::new(s("tests/backup"))),
+                    from: Some(s("backup-1")),
+                    to: Some(s("backup-2")),
+                    live: false,
+                    wine_prefix: None,
+                    api: true,
+                    game: s("game1"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_diff_with_live() {
+        check_args(
+            &[
+                "ludusavi",
+                "diff",
+                "--from",
+                "backup-1",
+                "--live",
+                "--wine-prefix",
+                "tests/wine-prefix",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                sub: Some(Subcommand::Diff {
+                    path: None,
+                    from: Some(s("backup-1")),
+                    to: None,
+                    live: true,
+                    wine_prefix: Some(StrictPath::new(s("tests/wine-prefix"))),
+                    api: false,
+                    game: s("game1"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_diff_with_to_and_live() {
+        check_args_err(
+            &["ludusavi", "diff", "--to", "backup-2", "--live", "game1"],
+            clap::ErrorKind::ArgumentConflict,
+        );
+    }
+
     #[test]
     fn accepts_cli_find_with_minimal_arguments() {
         check_args(
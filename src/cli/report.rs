@@ -1,7 +1,12 @@
 use crate::{
+    config::{GameBackupOverrides, HookFailureMode, RootsConfig},
+    hooks::{HookFailure, HookOperation},
     lang::Translator,
+    layout::FileVersion,
+    manifest::{Game, ManifestDiff, ManifestUpdate},
     prelude::{
-        BackupInfo, DuplicateDetector, OperationStatus, OperationStepDecision, ScanChange, ScanInfo, StrictPath,
+        BackupExclusion, BackupInfo, DuplicateDetector, FileDiffKind, GameDiff, OperationStatus, OperationStepDecision,
+        PathCandidate, PathCandidateCheck, PathCandidateSource, ScanChange, ScanInfo, ScannedFile, StrictPath,
     },
 };
 
@@ -11,6 +16,16 @@ struct ApiErrors {
     some_games_failed: Option<bool>,
     #[serde(rename = "unknownGames", skip_serializing_if = "Option::is_none")]
     unknown_games: Option<Vec<String>>,
+    #[serde(rename = "gamesWithoutBackup", skip_serializing_if = "Option::is_none")]
+    games_without_backup: Option<Vec<String>>,
+    #[serde(rename = "hookFailures", skip_serializing_if = "Vec::is_empty")]
+    hook_failures: Vec<HookFailure>,
+}
+
+#[derive(Debug, Default, serde::Serialize)]
+struct ApiWarnings {
+    #[serde(rename = "hookFailures", skip_serializing_if = "Vec::is_empty")]
+    hook_failures: Vec<HookFailure>,
 }
 
 #[derive(Debug, Default, serde::Serialize)]
@@ -25,6 +40,8 @@ struct ApiFile {
     original_path: Option<String>,
     #[serde(rename = "redirectedPath", skip_serializing_if = "Option::is_none")]
     redirected_path: Option<String>,
+    #[serde(rename = "excludedBy", skip_serializing_if = "Option::is_none")]
+    excluded_by: Option<BackupExclusion>,
     #[serde(
         rename = "duplicatedBy",
         serialize_with = "crate::serialization::ordered_set",
@@ -67,27 +84,294 @@ enum ApiGame {
         files: std::collections::HashMap<String, ApiFile>,
         #[serde(serialize_with = "crate::serialization::ordered_map")]
         registry: std::collections::HashMap<String, ApiRegistry>,
+        #[serde(rename = "sizeLimitExceeded", skip_serializing_if = "Option::is_none")]
+        size_limit_exceeded: Option<u64>,
     },
     Stored {
         backups: Vec<ApiBackup>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        overrides: Option<GameBackupOverrides>,
+    },
+    Compared {
+        from: Option<String>,
+        to: Option<String>,
+        files: std::collections::BTreeMap<String, ApiFileDiff>,
+    },
+    Versioned {
+        file: String,
+        versions: Vec<ApiFileVersion>,
+    },
+    Inspected {
+        entry: Game,
+        candidates: Vec<ApiPathCandidate>,
     },
     Found {},
 }
 
+#[derive(Debug, serde::Serialize)]
+struct ApiPathCandidate {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<RootsConfig>,
+    source: PathCandidateSource,
+    exists: bool,
+    files: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    excluded: Vec<ApiExclusion>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ApiExclusion {
+    reason: BackupExclusion,
+    files: usize,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ApiFileVersion {
+    backup: String,
+    when: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    bytes: u64,
+    hash: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ApiFileDiff {
+    change: FileDiffKind,
+    #[serde(rename = "oldBytes", skip_serializing_if = "Option::is_none")]
+    old_bytes: Option<u64>,
+    #[serde(rename = "newBytes", skip_serializing_if = "Option::is_none")]
+    new_bytes: Option<u64>,
+}
+
 #[derive(Debug, serde::Serialize)]
 struct ApiBackup {
     name: String,
     when: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, serde::Serialize)]
+struct ApiManifestUpdate {
+    url: String,
+    modified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<ManifestDiff>,
+    /// Games with existing backups whose entry was removed, renamed, or changed.
+    #[serde(
+        rename = "affectedBackups",
+        skip_serializing_if = "std::collections::BTreeSet::is_empty"
+    )]
+    affected_backups: std::collections::BTreeSet<String>,
+}
+
 #[derive(Debug, Default, serde::Serialize)]
 pub struct JsonOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     errors: Option<ApiErrors>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    warnings: Option<ApiWarnings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     overall: Option<OperationStatus>,
     #[serde(serialize_with = "crate::serialization::ordered_map")]
     games: std::collections::HashMap<String, ApiGame>,
+    #[serde(rename = "manifestUpdates", skip_serializing_if = "Vec::is_empty")]
+    manifest_updates: Vec<ApiManifestUpdate>,
+}
+
+/// Increment this when the event format changes in a way that could break existing consumers.
+pub const API_STREAM_VERSION: u32 = 1;
+
+#[derive(Debug, serde::Serialize)]
+struct ApiStreamLine<'a> {
+    version: u32,
+    #[serde(flatten)]
+    event: ApiEvent<'a>,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum ApiEvent<'a> {
+    #[serde(rename_all = "camelCase")]
+    Started {
+        operation: HookOperation,
+        total_games: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    GameScanned {
+        game: &'a str,
+        change: ScanChange,
+        total_bytes: u64,
+    },
+    FileProcessed {
+        game: &'a str,
+        path: String,
+        bytes: u64,
+        failed: bool,
+    },
+    GameCompleted {
+        game: &'a str,
+        #[serde(flatten)]
+        result: ApiGame,
+    },
+    Completed {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        errors: Option<&'a ApiErrors>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warnings: Option<&'a ApiWarnings>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        overall: Option<&'a OperationStatus>,
+    },
+}
+
+/// Prints `--api-stream` events as JSON Lines.
+/// This is separate from the `Reporter` so that it can be shared between threads.
+#[derive(Clone, Copy, Debug)]
+pub struct ApiStream;
+
+impl ApiStream {
+    fn render(event: ApiEvent) -> String {
+        serde_json::to_string(&ApiStreamLine {
+            version: API_STREAM_VERSION,
+            event,
+        })
+        .unwrap()
+    }
+
+    fn emit(&self, event: ApiEvent) {
+        use std::io::Write;
+
+        // Lock so that lines from different threads don't get interleaved.
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", Self::render(event));
+        let _ = stdout.flush();
+    }
+
+    pub fn started(&self, operation: HookOperation, total_games: usize) {
+        self.emit(ApiEvent::Started { operation, total_games });
+    }
+
+    pub fn game_scanned(&self, scan_info: &ScanInfo) {
+        if !scan_info.found_anything() {
+            return;
+        }
+        self.emit(ApiEvent::GameScanned {
+            game: &scan_info.game_name,
+            change: scan_info.count_changes().overall(),
+            total_bytes: scan_info.total_possible_bytes(),
+        });
+    }
+
+    /// This is meant to be called as each file is done, rather than after the whole game.
+    pub fn file_processed(&self, game: &str, file: &ScannedFile, restoring: bool, failed: bool) {
+        self.emit(ApiEvent::FileProcessed {
+            game,
+            path: file.readable(restoring),
+            bytes: file.size,
+            failed,
+        });
+    }
+
+    pub fn game_completed(&self, scan_info: &ScanInfo, backup_info: &BackupInfo, decision: &OperationStepDecision) {
+        if !scan_info.found_anything() {
+            return;
+        }
+
+        // Duplicates aren't known until every game has been scanned.
+        let (result, _) = api_game(scan_info, backup_info, decision, &DuplicateDetector::default());
+        self.emit(ApiEvent::GameCompleted {
+            game: &scan_info.game_name,
+            result,
+        });
+    }
+}
+
+/// The result of a backup or restore for one game, in the format of the `--api` output.
+/// Also returns whether the game was processed without failures.
+fn api_game(
+    scan_info: &ScanInfo,
+    backup_info: &BackupInfo,
+    decision: &OperationStepDecision,
+    duplicate_detector: &DuplicateDetector,
+) -> (ApiGame, bool) {
+    let mut successful = true;
+    let restoring = scan_info.restoring();
+
+    let decision = decision.clone();
+    let mut files = std::collections::HashMap::new();
+    let mut registry = std::collections::HashMap::new();
+
+    for entry in itertools::sorted(&scan_info.found_files) {
+        let mut api_file = ApiFile {
+            bytes: entry.size,
+            failed: backup_info.failed_files.contains(entry),
+            ignored: entry.ignored,
+            change: entry.change,
+            excluded_by: entry.excluded.clone(),
+            ..Default::default()
+        };
+        if duplicate_detector.is_file_duplicated(entry) {
+            let mut duplicated_by = duplicate_detector.file(entry);
+            duplicated_by.remove(&scan_info.game_name);
+            api_file.duplicated_by = duplicated_by;
+        }
+
+        if let Some(alt) = entry.alt_readable(restoring) {
+            if restoring {
+                api_file.original_path = Some(alt);
+            } else {
+                api_file.redirected_path = Some(alt);
+            }
+        }
+        if api_file.failed {
+            successful = false;
+        }
+
+        files.insert(entry.readable(restoring), api_file);
+    }
+    for entry in itertools::sorted(&scan_info.found_registry_keys) {
+        let mut api_registry = ApiRegistry {
+            failed: backup_info.failed_registry.contains(&entry.path),
+            ignored: entry.ignored,
+            change: entry.change,
+            values: entry
+                .values
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.clone(),
+                        ApiRegistryValue {
+                            change: v.change,
+                            ignored: v.ignored,
+                        },
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        };
+        if duplicate_detector.is_registry_duplicated(&entry.path) {
+            let mut duplicated_by = duplicate_detector.registry(&entry.path);
+            duplicated_by.remove(&scan_info.game_name);
+            api_registry.duplicated_by = duplicated_by;
+        }
+
+        if api_registry.failed {
+            successful = false;
+        }
+
+        registry.insert(entry.path.render(), api_registry);
+    }
+
+    (
+        ApiGame::Operative {
+            decision,
+            change: scan_info.count_changes().overall(),
+            files,
+            registry,
+            size_limit_exceeded: scan_info.size_limit_exceeded(),
+        },
+        successful,
+    )
 }
 
 #[derive(Debug)]
@@ -99,6 +383,9 @@ pub enum Reporter {
     },
     Json {
         output: JsonOutput,
+        /// Whether games were already reported as events while the operation was running,
+        /// so that only the summary is left to print at the end.
+        stream: bool,
     },
 }
 
@@ -115,9 +402,27 @@ impl Reporter {
         Self::Json {
             output: JsonOutput {
                 errors: Default::default(),
+                warnings: Default::default(),
                 overall: Some(Default::default()),
                 games: Default::default(),
+                manifest_updates: Default::default(),
             },
+            stream: false,
+        }
+    }
+
+    pub fn json_stream() -> Self {
+        match Self::json() {
+            Self::Json { output, .. } => Self::Json { output, stream: true },
+            standard => standard,
+        }
+    }
+
+    /// For reporting events while the operation is still running.
+    pub fn stream(&self) -> Option<ApiStream> {
+        match self {
+            Self::Json { stream: true, .. } => Some(ApiStream),
+            _ => None,
         }
     }
 
@@ -147,6 +452,60 @@ impl Reporter {
         }
     }
 
+    /// For games that had no backup yet at the requested point in time.
+    pub fn trip_games_without_backup(&mut self, games: Vec<String>) {
+        match self {
+            Self::Standard { parts, translator, .. } => {
+                parts.push(translator.cli_games_without_backup());
+                for game in &games {
+                    parts.push(format!("  - {game}"));
+                }
+                // Blank line before the summary.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => {
+                if let Some(errors) = &mut output.errors {
+                    errors.games_without_backup = Some(games);
+                } else {
+                    output.errors = Some(ApiErrors {
+                        games_without_backup: Some(games),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+
+    /// In error mode, the failures are reported as errors; otherwise, as warnings.
+    pub fn add_hook_failures(&mut self, failures: Vec<HookFailure>, mode: HookFailureMode) {
+        if failures.is_empty() {
+            return;
+        }
+
+        match self {
+            Self::Standard { parts, translator, .. } => {
+                parts.push(translator.cli_hook_failures());
+                for failure in &failures {
+                    parts.push(translator.cli_hook_failure(failure));
+                }
+                // Blank line before the summary.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => match mode {
+                HookFailureMode::Error => output
+                    .errors
+                    .get_or_insert_with(Default::default)
+                    .hook_failures
+                    .extend(failures),
+                HookFailureMode::Warning => output
+                    .warnings
+                    .get_or_insert_with(Default::default)
+                    .hook_failures
+                    .extend(failures),
+            },
+        }
+    }
+
     pub fn suppress_overall(&mut self) {
         match self {
             Self::Standard { status, .. } => {
@@ -186,6 +545,9 @@ impl Reporter {
                     duplicate_detector.is_game_duplicated(scan_info),
                     &scan_info.count_changes(),
                 ));
+                if let Some(limit) = scan_info.size_limit_exceeded() {
+                    parts.push(translator.cli_game_size_limit_exceeded(limit));
+                }
                 for entry in itertools::sorted(&scan_info.found_files) {
                     let entry_successful = !backup_info.failed_files.contains(entry);
                     if !entry_successful {
@@ -207,6 +569,9 @@ impl Reporter {
                             parts.push(translator.cli_game_line_item_redirecting(&alt));
                         }
                     }
+                    if let Some(excluded) = &entry.excluded {
+                        parts.push(translator.cli_game_line_item_excluded(excluded));
+                    }
                 }
                 for entry in itertools::sorted(&scan_info.found_registry_keys) {
                     let entry_successful = !backup_info.failed_registry.contains(&entry.path);
@@ -244,91 +609,22 @@ impl Reporter {
                     );
                 }
             }
-            Self::Json { output } => {
+            Self::Json { output, .. } => {
                 if !scan_info.found_anything() {
                     return true;
                 }
 
-                let decision = decision.clone();
-                let mut files = std::collections::HashMap::new();
-                let mut registry = std::collections::HashMap::new();
-
-                for entry in itertools::sorted(&scan_info.found_files) {
-                    let mut api_file = ApiFile {
-                        bytes: entry.size,
-                        failed: backup_info.failed_files.contains(entry),
-                        ignored: entry.ignored,
-                        change: entry.change,
-                        ..Default::default()
-                    };
-                    if duplicate_detector.is_file_duplicated(entry) {
-                        let mut duplicated_by = duplicate_detector.file(entry);
-                        duplicated_by.remove(&scan_info.game_name);
-                        api_file.duplicated_by = duplicated_by;
-                    }
-
-                    if let Some(alt) = entry.alt_readable(restoring) {
-                        if restoring {
-                            api_file.original_path = Some(alt);
-                        } else {
-                            api_file.redirected_path = Some(alt);
-                        }
-                    }
-                    if api_file.failed {
-                        successful = false;
-                    }
-
-                    files.insert(entry.readable(restoring), api_file);
-                }
-                for entry in itertools::sorted(&scan_info.found_registry_keys) {
-                    let mut api_registry = ApiRegistry {
-                        failed: backup_info.failed_registry.contains(&entry.path),
-                        ignored: entry.ignored,
-                        change: entry.change,
-                        values: entry
-                            .values
-                            .iter()
-                            .map(|(k, v)| {
-                                (
-                                    k.clone(),
-                                    ApiRegistryValue {
-                                        change: v.change,
-                                        ignored: v.ignored,
-                                    },
-                                )
-                            })
-                            .collect(),
-                        ..Default::default()
-                    };
-                    if duplicate_detector.is_registry_duplicated(&entry.path) {
-                        let mut duplicated_by = duplicate_detector.registry(&entry.path);
-                        duplicated_by.remove(&scan_info.game_name);
-                        api_registry.duplicated_by = duplicated_by;
-                    }
-
-                    if api_registry.failed {
-                        successful = false;
-                    }
-
-                    registry.insert(entry.path.render(), api_registry);
-                }
+                let game;
+                (game, successful) = api_game(scan_info, backup_info, decision, duplicate_detector);
 
                 if let Some(overall) = output.overall.as_mut() {
                     overall.add_game(
                         scan_info,
                         &Some(backup_info.clone()),
-                        decision == OperationStepDecision::Processed,
+                        decision == &OperationStepDecision::Processed,
                     );
                 }
-                output.games.insert(
-                    name.to_string(),
-                    ApiGame::Operative {
-                        decision,
-                        change: scan_info.count_changes().overall(),
-                        files,
-                        registry,
-                    },
-                );
+                output.games.insert(name.to_string(), game);
             }
         }
 
@@ -338,14 +634,17 @@ impl Reporter {
         successful
     }
 
-    pub fn add_backup(&mut self, name: &str, scan_info: &ScanInfo) {
+    pub fn add_backup(&mut self, name: &str, scan_info: &ScanInfo, overrides: Option<&GameBackupOverrides>) {
         match self {
-            Self::Standard { parts, .. } => {
+            Self::Standard { parts, translator, .. } => {
                 if scan_info.available_backups.is_empty() {
                     return;
                 }
 
-                parts.push(format!("{}:", name));
+                match overrides {
+                    Some(overrides) => parts.push(format!("{}{}:", name, translator.cli_game_overrides(overrides))),
+                    None => parts.push(format!("{}:", name)),
+                }
                 for backup in &scan_info.available_backups {
                     parts.push(format!(
                         "  - {} ({})",
@@ -357,7 +656,7 @@ impl Reporter {
                 // Blank line between games.
                 parts.push("".to_string());
             }
-            Self::Json { output } => {
+            Self::Json { output, .. } => {
                 if scan_info.available_backups.is_empty() {
                     return;
                 }
@@ -370,7 +669,146 @@ impl Reporter {
                     });
                 }
 
-                output.games.insert(name.to_string(), ApiGame::Stored { backups });
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Stored {
+                        backups,
+                        overrides: overrides.cloned(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// A missing `from` means there was no earlier backup,
+    /// and a missing `to` means the comparison was against the live files.
+    pub fn add_diff(&mut self, name: &str, from: Option<&str>, to: Option<&str>, diff: &GameDiff) {
+        match self {
+            Self::Standard { parts, translator, .. } => {
+                parts.push(
+                    translator.cli_diff_header(
+                        name,
+                        &from
+                            .map(|x| x.to_string())
+                            .unwrap_or_else(|| translator.no_backup_label()),
+                        &to.map(|x| x.to_string())
+                            .unwrap_or_else(|| translator.live_files_label()),
+                        diff.size_delta(),
+                    ),
+                );
+                if diff.is_empty() {
+                    parts.push(translator.cli_diff_no_changes());
+                }
+                for (path, file) in &diff.0 {
+                    parts.push(translator.cli_diff_line_item(path, file));
+                }
+
+                // Blank line between games.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => {
+                let files = diff
+                    .0
+                    .iter()
+                    .map(|(path, file)| {
+                        (
+                            path.clone(),
+                            ApiFileDiff {
+                                change: file.kind,
+                                old_bytes: file.old_size,
+                                new_bytes: file.new_size,
+                            },
+                        )
+                    })
+                    .collect();
+
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Compared {
+                        from: from.map(|x| x.to_string()),
+                        to: to.map(|x| x.to_string()),
+                        files,
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn add_file_history(&mut self, name: &str, file: &StrictPath, versions: &[FileVersion]) {
+        match self {
+            Self::Standard { parts, translator, .. } => {
+                parts.push(translator.cli_history_header(name, &file.render()));
+                if versions.is_empty() {
+                    parts.push(translator.cli_history_no_versions());
+                }
+                for version in versions {
+                    parts.push(translator.cli_history_line_item(version));
+                    if let Some(comment) = &version.comment {
+                        parts.push(translator.cli_history_line_item_comment(comment));
+                    }
+                }
+
+                // Blank line between games.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => {
+                let versions = versions
+                    .iter()
+                    .map(|version| ApiFileVersion {
+                        backup: version.backup.clone(),
+                        when: version.when,
+                        comment: version.comment.clone(),
+                        bytes: version.size,
+                        hash: version.hash.clone(),
+                    })
+                    .collect();
+
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Versioned {
+                        file: file.render(),
+                        versions,
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn add_manifest_info(&mut self, name: &str, game: &Game, candidates: &[(PathCandidate, PathCandidateCheck)]) {
+        match self {
+            Self::Standard { parts, translator, .. } => {
+                parts.push(format!("{}:", name));
+                let entry = serde_yaml::to_string(game).unwrap();
+                parts.push(translator.cli_manifest_info_entry(entry.trim_start_matches("---\n").trim_end()));
+                parts.push(translator.cli_manifest_info_candidates(candidates));
+            }
+            Self::Json { output, .. } => {
+                let candidates = candidates
+                    .iter()
+                    .map(|(candidate, check)| ApiPathCandidate {
+                        path: candidate.path.render(),
+                        root: candidate.root.clone(),
+                        source: candidate.source.clone(),
+                        exists: check.exists,
+                        files: check.files,
+                        excluded: check
+                            .excluded
+                            .iter()
+                            .map(|(reason, files)| ApiExclusion {
+                                reason: reason.clone(),
+                                files: *files,
+                            })
+                            .collect(),
+                    })
+                    .collect();
+
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Inspected {
+                        entry: game.clone(),
+                        candidates,
+                    },
+                );
             }
         }
     }
@@ -382,7 +820,7 @@ impl Reporter {
                     parts.push(name.to_owned());
                 }
             }
-            Self::Json { output } => {
+            Self::Json { output, .. } => {
                 for name in names {
                     output.games.insert(name.to_owned(), ApiGame::Found {});
                 }
@@ -390,7 +828,27 @@ impl Reporter {
         }
     }
 
-    fn render(&self, path: &StrictPath) -> String {
+    pub fn add_manifest_update(&mut self, update: &ManifestUpdate, backed_up: &std::collections::BTreeSet<String>) {
+        match self {
+            Self::Standard { parts, translator, .. } => {
+                parts.push(translator.manifest_update_report(update, backed_up));
+            }
+            Self::Json { output, .. } => {
+                let affected_backups = match &update.diff {
+                    Some(diff) => diff.affected_games().intersection(backed_up).cloned().collect(),
+                    None => Default::default(),
+                };
+                output.manifest_updates.push(ApiManifestUpdate {
+                    url: update.url.clone(),
+                    modified: update.modified,
+                    diff: update.diff.clone(),
+                    affected_backups,
+                });
+            }
+        }
+    }
+
+    pub fn render(&self, path: &StrictPath) -> String {
         match self {
             Self::Standard {
                 parts,
@@ -400,7 +858,12 @@ impl Reporter {
                 Some(status) => parts.join("\n") + "\n" + &translator.cli_summary(status, path),
                 None => parts.join("\n"),
             },
-            Self::Json { output } => serde_json::to_string_pretty(&output).unwrap(),
+            Self::Json { output, stream: false } => serde_json::to_string_pretty(&output).unwrap(),
+            Self::Json { output, stream: true } => ApiStream::render(ApiEvent::Completed {
+                errors: output.errors.as_ref(),
+                warnings: output.warnings.as_ref(),
+                overall: output.overall.as_ref(),
+            }),
         }
     }
 
@@ -420,11 +883,15 @@ impl Reporter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::manifest::GamePathsDiff;
     use crate::{
-        prelude::{RegistryItem, ScannedFile, ScannedRegistry},
+        config::{BackupFormat, BackupFormatOverrides, RetentionOverrides, ZipCompression, ZipConfigOverrides},
+        hooks::{HookEvent, HookOperation},
+        layout::{Backup, FullBackup},
+        prelude::{FileDiff, RegistryItem, ScannedFile, ScannedRegistry},
         testing::s,
     };
-    use maplit::hashset;
+    use maplit::{btreemap, btreeset, hashset};
     use pretty_assertions::assert_eq;
 
     fn drive() -> String {
@@ -475,6 +942,7 @@ Overall:
                         hash: "1".to_string(),
                         original_path: None,
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -485,6 +953,7 @@ Overall:
                         hash: "2".to_string(),
                         original_path: None,
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -544,6 +1013,7 @@ Overall:
                         hash: "1".to_string(),
                         original_path: None,
                         ignored: false,
+                        excluded: None,
                         change: ScanChange::Same,
                         container: None,
                         redirected: None,
@@ -570,6 +1040,7 @@ Overall:
                         hash: "2".to_string(),
                         original_path: None,
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -619,6 +1090,7 @@ Overall:
                         hash: "1".to_string(),
                         original_path: Some(StrictPath::new(format!("{}/original/file1", drive()))),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -629,6 +1101,7 @@ Overall:
                         hash: "2".to_string(),
                         original_path: Some(StrictPath::new(format!("{}/original/file2", drive()))),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -894,6 +1367,80 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_stream_events() {
+        let scan_info = ScanInfo {
+            game_name: s("foo"),
+            found_files: hashset! {
+                ScannedFile::new("/file1", 100, "1"),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            r#"{"version":1,"event":"started","operation":"backup","totalGames":2}"#,
+            ApiStream::render(ApiEvent::Started {
+                operation: HookOperation::Backup,
+                total_games: 2,
+            })
+        );
+        assert_eq!(
+            r#"{"version":1,"event":"gameScanned","game":"foo","change":"Same","totalBytes":100}"#,
+            ApiStream::render(ApiEvent::GameScanned {
+                game: "foo",
+                change: scan_info.count_changes().overall(),
+                total_bytes: scan_info.total_possible_bytes(),
+            })
+        );
+        assert_eq!(
+            r#"{"version":1,"event":"fileProcessed","game":"foo","path":"<drive>/file1","bytes":100,"failed":false}"#
+                .replace("<drive>", &drive()),
+            ApiStream::render(ApiEvent::FileProcessed {
+                game: "foo",
+                path: s("<drive>/file1").replace("<drive>", &drive()),
+                bytes: 100,
+                failed: false,
+            })
+        );
+        assert_eq!(
+            r#"{"version":1,"event":"gameCompleted","game":"foo","decision":"Processed","change":"Same","files":{"<drive>/file1":{"change":"Unknown","bytes":100}},"registry":{}}"#
+                .replace("<drive>", &drive()),
+            ApiStream::render(ApiEvent::GameCompleted {
+                game: "foo",
+                result: api_game(
+                    &scan_info,
+                    &BackupInfo::default(),
+                    &OperationStepDecision::Processed,
+                    &DuplicateDetector::default()
+                )
+                .0,
+            })
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_stream_mode() {
+        let mut reporter = Reporter::json_stream();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hashset! {
+                    ScannedFile::new("/file1", 100, "1"),
+                },
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"{"version":1,"event":"completed","overall":{"totalGames":1,"totalBytes":100,"processedGames":1,"processedBytes":100,"changedGames":{"new":0,"different":0,"same":1}}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
     #[test]
     fn can_render_in_json_mode_with_one_game_in_restore_mode() {
         let mut reporter = Reporter::json();
@@ -909,6 +1456,7 @@ Overall:
                         hash: "1".to_string(),
                         original_path: Some(StrictPath::new(format!("{}/original/file1", drive()))),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -919,6 +1467,7 @@ Overall:
                         hash: "2".to_string(),
                         original_path: Some(StrictPath::new(format!("{}/original/file2", drive()))),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -1119,4 +1668,715 @@ Overall:
             reporter.render(&StrictPath::new(s("/dev/null")))
         );
     }
+
+    #[test]
+    fn can_render_in_standard_mode_with_game_rules() {
+        let mut reporter = Reporter::standard(Translator::default());
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hashset! {
+                    ScannedFile::new("/save", 10, "1"),
+                    ScannedFile::new("/crash.dmp", 20, "").excluded(BackupExclusion::GameIgnoredPath(StrictPath::new(s("*.dmp")))),
+                    ScannedFile::new("/replay", 30, "").excluded(BackupExclusion::MaxFileSize(25)),
+                },
+                found_registry_keys: hashset! {},
+                max_total_size: Some(5),
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+foo [10 B]:
+  - Warning: larger than the size limit of 5 B
+  - [IGNORED] <drive>/crash.dmp
+    - Excluded by rule: *.dmp
+  - [IGNORED] <drive>/replay
+    - Excluded by rule: > 25 B
+  - <drive>/save
+
+Overall:
+  Games: 1
+  Size: 10 B / 60 B
+  Location: <drive>/dev/null
+            "#
+            .trim()
+            .replace("<drive>", &drive()),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_game_rules() {
+        let mut reporter = Reporter::json();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hashset! {
+                    ScannedFile::new("/save", 10, "1"),
+                    ScannedFile::new("/crash.dmp", 20, "").excluded(BackupExclusion::GameIgnoredPath(StrictPath::new(s("*.dmp")))),
+                    ScannedFile::new("/replay", 30, "").excluded(BackupExclusion::MaxFileSize(25)),
+                },
+                found_registry_keys: hashset! {},
+                max_total_size: Some(5),
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 1,
+    "totalBytes": 60,
+    "processedGames": 1,
+    "processedBytes": 10,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 1
+    }
+  },
+  "games": {
+    "foo": {
+      "decision": "Processed",
+      "change": "Same",
+      "files": {
+        "<drive>/crash.dmp": {
+          "ignored": true,
+          "change": "Unknown",
+          "bytes": 20,
+          "excludedBy": {
+            "gameIgnoredPath": "*.dmp"
+          }
+        },
+        "<drive>/replay": {
+          "ignored": true,
+          "change": "Unknown",
+          "bytes": 30,
+          "excludedBy": {
+            "maxFileSize": 25
+          }
+        },
+        "<drive>/save": {
+          "change": "Unknown",
+          "bytes": 10
+        }
+      },
+      "registry": {},
+      "sizeLimitExceeded": 5
+    }
+  }
+}
+            "#
+            .trim()
+            .replace("<drive>", &drive()),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    fn backups_with_overrides() -> (ScanInfo, GameBackupOverrides) {
+        (
+            ScanInfo {
+                game_name: s("foo"),
+                available_backups: vec![Backup::Full(FullBackup {
+                    name: s("backup-1"),
+                    when: chrono::DateTime::parse_from_rfc3339("2023-01-02T03:04:05Z")
+                        .unwrap()
+                        .with_timezone(&chrono::Utc),
+                    comment: None,
+                    files: Default::default(),
+                    registry: Default::default(),
+                    children: vec![],
+                })],
+                ..Default::default()
+            },
+            GameBackupOverrides {
+                retention: RetentionOverrides {
+                    full: Some(2),
+                    differential: None,
+                },
+                format: BackupFormatOverrides {
+                    chosen: Some(BackupFormat::Zip),
+                    zip: ZipConfigOverrides {
+                        compression: Some(ZipCompression::Zstd),
+                    },
+                    level: Some(10),
+                },
+            },
+        )
+    }
+
+    #[test]
+    fn can_render_backups_in_standard_mode_with_overrides() {
+        let mut reporter = Reporter::standard(Translator::default());
+        let (scan_info, overrides) = backups_with_overrides();
+
+        reporter.add_backup("foo", &scan_info, Some(&overrides));
+        assert_eq!(
+            format!(
+                r#"
+foo [Format: Zip, Compression: Zstd, Level: 10] [Full: 2]:
+  - backup-1 ({})
+
+Overall:
+  Games: 0
+  Size: 0 B
+  Location: <drive>/dev/null
+                "#,
+                scan_info.available_backups[0].when_local().format("%Y-%m-%dT%H:%M:%S")
+            )
+            .trim()
+            .replace("<drive>", &drive()),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_backups_in_json_mode_with_overrides() {
+        let mut reporter = Reporter::json();
+        let (scan_info, overrides) = backups_with_overrides();
+
+        reporter.add_backup("foo", &scan_info, Some(&overrides));
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 0,
+    "totalBytes": 0,
+    "processedGames": 0,
+    "processedBytes": 0,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 0
+    }
+  },
+  "games": {
+    "foo": {
+      "backups": [
+        {
+          "name": "backup-1",
+          "when": "2023-01-02T03:04:05Z"
+        }
+      ],
+      "overrides": {
+        "retention": {
+          "full": 2
+        },
+        "format": {
+          "chosen": "zip",
+          "zip": {
+            "compression": "zstd"
+          },
+          "level": 10
+        }
+      }
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    fn hook_failures() -> Vec<HookFailure> {
+        vec![
+            HookFailure {
+                command: s("systemctl --user stop syncthing"),
+                game: None,
+                operation: HookOperation::Backup,
+                event: HookEvent::Before,
+                reason: s("exit status: 1"),
+            },
+            HookFailure {
+                command: s("pkill game"),
+                game: Some(s("foo")),
+                operation: HookOperation::Backup,
+                event: HookEvent::After,
+                reason: s("exit status: 2"),
+            },
+        ]
+    }
+
+    #[test]
+    fn can_render_hook_failures_in_standard_mode() {
+        let mut reporter = Reporter::standard(Translator::default());
+
+        reporter.add_hook_failures(hook_failures(), HookFailureMode::Warning);
+        assert_eq!(
+            r#"
+Hook failures:
+  - [before backup] systemctl --user stop syncthing (exit status: 1)
+  - [after backup] foo: pkill game (exit status: 2)
+
+Overall:
+  Games: 0
+  Size: 0 B
+  Location: <drive>/dev/null
+            "#
+            .trim()
+            .replace("<drive>", &drive()),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_hook_failures_in_json_mode() {
+        let mut warning = Reporter::json();
+        warning.suppress_overall();
+        warning.add_hook_failures(hook_failures(), HookFailureMode::Warning);
+        assert_eq!(
+            r#"
+{
+  "warnings": {
+    "hookFailures": [
+      {
+        "command": "systemctl --user stop syncthing",
+        "operation": "backup",
+        "event": "before",
+        "reason": "exit status: 1"
+      },
+      {
+        "command": "pkill game",
+        "game": "foo",
+        "operation": "backup",
+        "event": "after",
+        "reason": "exit status: 2"
+      }
+    ]
+  },
+  "games": {}
+}
+            "#
+            .trim(),
+            warning.render(&StrictPath::new(s("/dev/null")))
+        );
+
+        let mut error = Reporter::json();
+        error.suppress_overall();
+        error.add_hook_failures(hook_failures()[..1].to_vec(), HookFailureMode::Error);
+        assert_eq!(
+            r#"
+{
+  "errors": {
+    "hookFailures": [
+      {
+        "command": "systemctl --user stop syncthing",
+        "operation": "backup",
+        "event": "before",
+        "reason": "exit status: 1"
+      }
+    ]
+  },
+  "games": {}
+}
+            "#
+            .trim(),
+            error.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    fn game_diff() -> GameDiff {
+        GameDiff(btreemap! {
+            s("/added") => FileDiff { kind: FileDiffKind::Added, old_size: None, new_size: Some(10) },
+            s("/modified") => FileDiff { kind: FileDiffKind::Modified, old_size: Some(5), new_size: Some(2) },
+            s("/removed") => FileDiff { kind: FileDiffKind::Removed, old_size: Some(1), new_size: None },
+        })
+    }
+
+    #[test]
+    fn can_render_diff_in_standard_mode() {
+        let mut reporter = Reporter::standard(Translator::default());
+        reporter.suppress_overall();
+        reporter.add_diff("foo", Some("backup-1"), None, &game_diff());
+        reporter.add_diff("bar", None, Some("backup-2"), &GameDiff::default());
+        assert_eq!(
+            r#"
+foo (backup-1 -> Live files) [+6 B]:
+  - [+] /added [+10 B]
+  - [Δ] /modified [-3 B]
+  - [-] /removed [-1 B]
+
+bar (No backup -> backup-2) [+0 B]:
+  No changes
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim_end()
+        );
+    }
+
+    fn file_versions() -> Vec<FileVersion> {
+        vec![
+            FileVersion {
+                backup: s("backup-1"),
+                when: chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+                    .unwrap()
+                    .and_hms_opt(3, 4, 5)
+                    .unwrap()
+                    .and_local_timezone(chrono::Utc)
+                    .unwrap(),
+                comment: Some(s("before boss")),
+                size: 5,
+                hash: s("abc"),
+            },
+            FileVersion {
+                backup: s("backup-2"),
+                when: chrono::NaiveDate::from_ymd_opt(2000, 1, 3)
+                    .unwrap()
+                    .and_hms_opt(3, 4, 5)
+                    .unwrap()
+                    .and_local_timezone(chrono::Utc)
+                    .unwrap(),
+                comment: None,
+                size: 10,
+                hash: s("def"),
+            },
+        ]
+    }
+
+    #[test]
+    fn can_render_file_history_in_standard_mode() {
+        let mut reporter = Reporter::standard(Translator::default());
+        reporter.suppress_overall();
+        let versions = file_versions();
+        reporter.add_file_history("foo", &StrictPath::new(s("/save.dat")), &versions);
+        assert_eq!(
+            format!(
+                r#"
+foo (/save.dat):
+  - backup-1 ({}) [5 B] abc
+    - Comment: before boss
+  - backup-2 ({}) [10 B] def
+                "#,
+                versions[0].label(),
+                versions[1].label(),
+            )
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim_end()
+        );
+    }
+
+    #[test]
+    fn can_render_file_history_in_json_mode() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+        reporter.add_file_history("foo", &StrictPath::new(s("/save.dat")), &file_versions());
+        assert_eq!(
+            r#"
+{
+  "games": {
+    "foo": {
+      "file": "/save.dat",
+      "versions": [
+        {
+          "backup": "backup-1",
+          "when": "2000-01-02T03:04:05Z",
+          "comment": "before boss",
+          "bytes": 5,
+          "hash": "abc"
+        },
+        {
+          "backup": "backup-2",
+          "when": "2000-01-03T03:04:05Z",
+          "bytes": 10,
+          "hash": "def"
+        }
+      ]
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_games_without_backup_in_standard_mode() {
+        let mut reporter = Reporter::standard(Translator::default());
+        reporter.suppress_overall();
+        reporter.trip_games_without_backup(vec![s("foo"), s("bar")]);
+        assert_eq!(
+            r#"
+No backup existed yet at that time for these games:
+  - foo
+  - bar
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim_end()
+        );
+    }
+
+    #[test]
+    fn can_render_games_without_backup_in_json_mode() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+        reporter.trip_games_without_backup(vec![s("foo")]);
+        assert_eq!(
+            r#"
+{
+  "errors": {
+    "gamesWithoutBackup": [
+      "foo"
+    ]
+  },
+  "games": {}
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_diff_in_json_mode() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+        reporter.add_diff("foo", Some("backup-1"), Some("backup-2"), &game_diff());
+        assert_eq!(
+            r#"
+{
+  "games": {
+    "foo": {
+      "from": "backup-1",
+      "to": "backup-2",
+      "files": {
+        "/added": {
+          "change": "Added",
+          "newBytes": 10
+        },
+        "/modified": {
+          "change": "Modified",
+          "oldBytes": 5,
+          "newBytes": 2
+        },
+        "/removed": {
+          "change": "Removed",
+          "oldBytes": 1
+        }
+      }
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    fn manifest_update() -> ManifestUpdate {
+        ManifestUpdate {
+            url: s("example.com"),
+            etag: None,
+            timestamp: chrono::DateTime::default(),
+            modified: true,
+            diff: Some(ManifestDiff {
+                added: btreeset! { s("new") },
+                removed: btreeset! { s("gone"), s("other") },
+                renamed: btreemap! { s("old name") => s("new name") },
+                changed: btreemap! {
+                    s("new name") => GamePathsDiff {
+                        added_files: btreeset! { s("<base>/added") },
+                        removed_files: btreeset! { s("<base>/removed") },
+                        ..Default::default()
+                    },
+                },
+            }),
+        }
+    }
+
+    #[test]
+    fn can_render_manifest_update_in_standard_mode() {
+        let mut reporter = Reporter::standard(Translator::default());
+        reporter.suppress_overall();
+        reporter.add_manifest_update(&manifest_update(), &btreeset! { s("gone"), s("old name") });
+        assert_eq!(
+            r#"
+Updated manifest: example.com
+  Added games:
+    - new
+  Removed games:
+    - gone [has backups]
+    - other
+  Renamed games:
+    - old name -> new name [has backups]
+  Games with changed paths:
+    - new name [has backups]
+      + <base>/added
+      - <base>/removed
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim_end()
+        );
+    }
+
+    #[test]
+    fn can_render_manifest_update_in_json_mode() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+        reporter.add_manifest_update(&manifest_update(), &btreeset! { s("gone"), s("old name") });
+        assert_eq!(
+            r#"
+{
+  "games": {},
+  "manifestUpdates": [
+    {
+      "url": "example.com",
+      "modified": true,
+      "diff": {
+        "added": [
+          "new"
+        ],
+        "removed": [
+          "gone",
+          "other"
+        ],
+        "renamed": {
+          "old name": "new name"
+        },
+        "changed": {
+          "new name": {
+            "addedFiles": [
+              "<base>/added"
+            ],
+            "removedFiles": [
+              "<base>/removed"
+            ]
+          }
+        }
+      },
+      "affectedBackups": [
+        "gone",
+        "old name"
+      ]
+    }
+  ]
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    fn manifest_info() -> (Game, Vec<(PathCandidate, PathCandidateCheck)>) {
+        let game = Game {
+            files: Some(btreemap! { s("<base>/saves") => Default::default() }),
+            ..Default::default()
+        };
+        let candidates = vec![
+            (
+                PathCandidate {
+                    root: Some(RootsConfig {
+                        path: StrictPath::new(s("/games")),
+                        store: crate::manifest::Store::Other,
+                    }),
+                    source: PathCandidateSource::Manifest(s("<base>/saves")),
+                    path: StrictPath::new(s("/games/foo/saves")),
+                    case_sensitive: None,
+                },
+                PathCandidateCheck {
+                    exists: true,
+                    files: 2,
+                    excluded: btreemap! { BackupExclusion::Toggled => 1 },
+                },
+            ),
+            (
+                PathCandidate {
+                    root: None,
+                    source: PathCandidateSource::WineRegistry,
+                    path: StrictPath::new(s("/prefix/*.reg")),
+                    case_sensitive: None,
+                },
+                PathCandidateCheck::default(),
+            ),
+        ];
+        (game, candidates)
+    }
+
+    #[test]
+    fn can_render_manifest_info_in_standard_mode() {
+        let mut reporter = Reporter::standard(Translator::default());
+        reporter.suppress_overall();
+        let (game, candidates) = manifest_info();
+        reporter.add_manifest_info("foo", &game, &candidates);
+        assert_eq!(
+            r#"
+foo:
+  Manifest entry:
+    files:
+      "<base>/saves": {}
+  Candidate paths:
+    - /games/foo/saves [2 files]
+      - From: <base>/saves
+      - Root: [Other] /games
+      - Deselected for this game: 1 file
+    - /prefix/*.reg [not found]
+      - From: Wine registry files
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim_end()
+        );
+    }
+
+    #[test]
+    fn can_render_manifest_info_in_json_mode() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+        let (game, candidates) = manifest_info();
+        reporter.add_manifest_info("foo", &game, &candidates);
+        assert_eq!(
+            r#"
+{
+  "games": {
+    "foo": {
+      "entry": {
+        "files": {
+          "<base>/saves": {}
+        }
+      },
+      "candidates": [
+        {
+          "path": "/games/foo/saves",
+          "root": {
+            "path": "/games",
+            "store": "other"
+          },
+          "source": {
+            "manifest": "<base>/saves"
+          },
+          "exists": true,
+          "files": 2,
+          "excluded": [
+            {
+              "reason": "toggled",
+              "files": 1
+            }
+          ]
+        },
+        {
+          "path": "/prefix/*.reg",
+          "source": "wineRegistry",
+          "exists": false,
+          "files": 0
+        }
+      ]
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
 }
@@ -0,0 +1,926 @@
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex, RwLock},
+};
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use tiny_http::Method;
+
+use crate::{
+    cache::Cache,
+    cli::{report::Reporter, watch::Stamps, GameSubjects},
+    config::{Config, RootsConfig, Sort, SortKey, ToggledRegistry},
+    hooks::{HookFailure, HookOperation, HookRunner},
+    lang::Translator,
+    launchers::Launchers,
+    layout::BackupLayout,
+    manifest::Manifest,
+    prelude::{
+        app_dir, back_up_game, prepare_backup_target, scan_game_for_backup, scan_game_for_restoration, BackupId,
+        BackupInfo, DuplicateDetector, Error, InstallDirRanking, OperationStepDecision, ScanInfo, SteamShortcuts,
+        StrictPath, TitleFinder,
+    },
+    serialization::ResourceFile,
+};
+
+/// Requests with a larger body are rejected.
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
+/// How many requests can be handled at once.
+/// Progress can still be checked while a backup or restore is running.
+const WORKERS: usize = 4;
+
+/// What the server is busy with, for the `/progress` endpoint.
+#[derive(Clone, Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Progress {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    operation: Option<HookOperation>,
+    preview: bool,
+    total_games: usize,
+    processed_games: usize,
+}
+
+/// Clears the progress when an operation ends, even if it ends early.
+struct Running<'a>(&'a Mutex<Progress>);
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        *self.0.lock().unwrap() = Progress::default();
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+struct FindRequest {
+    names: Vec<String>,
+    steam_id: Option<u32>,
+    gog_id: Option<u64>,
+    normalized: bool,
+    backup: bool,
+    restore: bool,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct GamesRequest {
+    games: Vec<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RestoreRequest {
+    games: Vec<String>,
+    backup: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, serde_json::json!({ "error": message }).to_string())
+    }
+}
+
+/// One game's result, before it's added to the report.
+struct Step<'a> {
+    name: &'a String,
+    scan_info: ScanInfo,
+    backup_info: BackupInfo,
+    decision: OperationStepDecision,
+    hook_failures: Vec<HookFailure>,
+}
+
+/// Everything that would otherwise be loaded again for each command,
+/// kept until the config or manifest changes.
+struct Session {
+    config: Config,
+    games: Manifest,
+    backup_layout: BackupLayout,
+    restore_layout: BackupLayout,
+    title_finder: TitleFinder,
+    aliases: BTreeMap<String, String>,
+    roots: Vec<RootsConfig>,
+    launchers: Launchers,
+    steam_shortcuts: SteamShortcuts,
+    stamps: Stamps,
+}
+
+impl Session {
+    fn load() -> Result<Self, Error> {
+        let config = Config::load()?;
+        let stamps = Stamps::current(&config);
+        let mut games = Manifest::load_with_secondary(&config)?;
+        games.load_custom_games(&config);
+
+        let roots = config.expanded_roots();
+        let backup_layout = BackupLayout::new(config.backup.path.clone(), config.backup.retention.clone())
+            .with_game_retention(config.backup.retention_overrides());
+        let restore_layout = BackupLayout::new(config.restore.path.clone(), config.backup.retention.clone());
+        let aliases = Cache::load().unwrap_or_default().game_aliases(&config);
+        let title_finder = TitleFinder::new(&games, &restore_layout).with_aliases(&aliases);
+        let launchers = Launchers::scan(&roots, &title_finder, None);
+        let steam_shortcuts = SteamShortcuts::scan();
+
+        Ok(Self {
+            config,
+            games,
+            backup_layout,
+            restore_layout,
+            title_finder,
+            aliases,
+            roots,
+            launchers,
+            steam_shortcuts,
+            stamps,
+        })
+    }
+
+    /// Pick up backups that were made after the session was loaded.
+    fn refresh_backups(&mut self) {
+        self.backup_layout = BackupLayout::new(self.config.backup.path.clone(), self.config.backup.retention.clone())
+            .with_game_retention(self.config.backup.retention_overrides());
+        self.restore_layout = BackupLayout::new(self.config.restore.path.clone(), self.config.backup.retention.clone());
+        self.title_finder = TitleFinder::new(&self.games, &self.restore_layout).with_aliases(&self.aliases);
+    }
+}
+
+struct Server {
+    session: RwLock<Session>,
+    progress: Mutex<Progress>,
+    address: SocketAddr,
+    token: Option<String>,
+    translator: Translator,
+}
+
+impl Server {
+    fn respond(&self, request: &mut tiny_http::Request) -> Response {
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv(name))
+                .map(|header| header.value.as_str().to_string())
+        };
+
+        // Web pages can send requests to local addresses, either directly or through DNS rebinding,
+        // so anything from a browser is refused.
+        if header("Origin").is_some() {
+            return Response::error(403, "requests from web pages are not allowed");
+        }
+        if !self.allowed_host(header("Host").as_deref()) {
+            return Response::error(403, "unexpected host");
+        }
+        if !authorized(self.token.as_deref(), header("Authorization").as_deref()) {
+            return Response::error(401, "missing or invalid token");
+        }
+        if request.method() == &Method::Post && !is_json(header("Content-Type").as_deref()) {
+            return Response::error(415, "expected content type application/json");
+        }
+
+        if request.body_length().is_some_and(|x| x as u64 > MAX_BODY_BYTES) {
+            return Response::error(413, "request body is too large");
+        }
+        let mut body = String::new();
+        if let Err(e) = request.as_reader().take(MAX_BODY_BYTES + 1).read_to_string(&mut body) {
+            return Response::error(400, &e.to_string());
+        }
+        if body.len() as u64 > MAX_BODY_BYTES {
+            return Response::error(413, "request body is too large");
+        }
+
+        let path = request.url().split('?').next().unwrap_or_default().to_string();
+        let method = request.method().clone();
+        self.route(&method, &path, &body)
+    }
+
+    /// With a token, a request can't have come from a web page that merely knows our address,
+    /// so other computers may use whatever address they reached us by.
+    fn allowed_host(&self, host: Option<&str>) -> bool {
+        if self.token.is_some() {
+            return true;
+        }
+        let Some(host) = host else {
+            return false;
+        };
+        let port = self.address.port();
+
+        let mut allowed = vec![format!("127.0.0.1:{port}"), format!("localhost:{port}")];
+        if !self.address.ip().is_unspecified() {
+            allowed.push(self.address.to_string());
+        }
+        allowed.iter().any(|x| x.eq_ignore_ascii_case(host))
+    }
+
+    fn route(&self, method: &Method, path: &str, body: &str) -> Response {
+        let expected = match path {
+            "/progress" => Method::Get,
+            "/find" | "/backups" | "/backup" | "/backup/preview" | "/restore" | "/restore/preview" => Method::Post,
+            _ => return Response::error(404, "unknown endpoint"),
+        };
+        if method != &expected {
+            return Response::error(405, &format!("expected {expected}"));
+        }
+
+        if path == "/progress" {
+            let progress = self.progress.lock().unwrap().clone();
+            return Response::json(200, serde_json::to_string(&progress).unwrap());
+        }
+
+        self.reload_if_changed();
+
+        match path {
+            "/find" => match parse_body(body) {
+                Ok(request) => self.find(request),
+                Err(response) => response,
+            },
+            "/backups" => match parse_body(body) {
+                Ok(request) => self.backups(request),
+                Err(response) => response,
+            },
+            "/backup" | "/backup/preview" => match parse_body(body) {
+                Ok(request) => self.back_up(request, path == "/backup/preview"),
+                Err(response) => response,
+            },
+            _ => match parse_body(body) {
+                Ok(request) => self.restore(request, path == "/restore/preview"),
+                Err(response) => response,
+            },
+        }
+    }
+
+    fn reload_if_changed(&self) {
+        let stamps = {
+            let session = self.session.read().unwrap();
+            let stamps = Stamps::current(&session.config);
+            if session.stamps == stamps {
+                return;
+            }
+            stamps
+        };
+
+        log::info!("config or manifest changed, loading them again");
+        match Session::load() {
+            Ok(reloaded) => {
+                *self.session.write().unwrap() = reloaded;
+            }
+            Err(e) => {
+                eprintln!("{}", self.translator.handle_error(&e));
+                self.session.write().unwrap().stamps = stamps;
+            }
+        }
+    }
+
+    fn fail(&self, status: u16, error: &Error) -> Response {
+        Response::error(status, &self.translator.handle_error(error))
+    }
+
+    /// Only one backup or restore can run at a time, including previews.
+    fn begin(&self, operation: HookOperation, preview: bool) -> Option<Running<'_>> {
+        let mut progress = self.progress.lock().unwrap();
+        if progress.operation.is_some() {
+            return None;
+        }
+        *progress = Progress {
+            operation: Some(operation),
+            preview,
+            ..Default::default()
+        };
+        Some(Running(&self.progress))
+    }
+
+    fn find(&self, request: FindRequest) -> Response {
+        let session = self.session.read().unwrap();
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+
+        let found = session.title_finder.find(
+            &request.names,
+            &request.steam_id,
+            &request.gog_id,
+            request.normalized,
+            request.backup,
+            request.restore,
+        );
+        reporter.add_found_titles(&found);
+
+        if found.is_empty() {
+            let mut invalid = request.names;
+            if let Some(steam_id) = request.steam_id {
+                invalid.push(steam_id.to_string());
+            }
+            if let Some(gog_id) = request.gog_id {
+                invalid.push(gog_id.to_string());
+            }
+            reporter.trip_unknown_games(invalid);
+            return Response::json(404, reporter.render(&session.config.restore.path));
+        }
+
+        Response::json(200, reporter.render(&session.config.restore.path))
+    }
+
+    fn backups(&self, request: GamesRequest) -> Response {
+        let session = self.session.read().unwrap();
+        let restore_dir = &session.config.restore.path;
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+
+        let subjects = GameSubjects::new(
+            session.restore_layout.restorable_games(),
+            request.games,
+            false,
+            &session.games,
+        );
+        if !subjects.invalid.is_empty() {
+            reporter.trip_unknown_games(subjects.invalid);
+            return Response::json(404, reporter.render(restore_dir));
+        }
+
+        let info: Vec<_> = subjects
+            .valid
+            .par_iter()
+            .map(|name| {
+                let mut layout = session.restore_layout.game_layout(name);
+                let scan_info =
+                    scan_game_for_restoration(name, &BackupId::Latest, &mut layout, &session.config.redirects);
+                (name, scan_info)
+            })
+            .collect();
+
+        for (name, scan_info) in info {
+            reporter.add_backup(name, &scan_info, session.config.backup.overrides.get(name));
+        }
+        Response::json(200, reporter.render(restore_dir))
+    }
+
+    fn back_up(&self, request: GamesRequest, preview: bool) -> Response {
+        let Some(running) = self.begin(HookOperation::Backup, preview) else {
+            return Response::error(409, "another operation is already running");
+        };
+
+        let session = self.session.read().unwrap();
+        let config = &session.config;
+        let backup_dir = &config.backup.path;
+        let merge = config.backup.merge;
+        let mut reporter = Reporter::json();
+
+        let games_specified = !request.games.is_empty();
+        let subjects = GameSubjects::new(
+            session.games.0.keys().cloned().collect(),
+            request.games,
+            false,
+            &session.games,
+        );
+        if !subjects.invalid.is_empty() {
+            reporter.trip_unknown_games(subjects.invalid);
+            return Response::json(404, reporter.render(backup_dir));
+        }
+
+        let hooks = HookRunner::new(&config.hooks, HookOperation::Backup, backup_dir);
+        if !preview {
+            let failures = hooks.before_all();
+            let blocked = hooks.blocks(&failures);
+            reporter.add_hook_failures(failures, hooks.failure_mode());
+            if blocked {
+                return Response::json(500, reporter.render(backup_dir));
+            }
+            if let Err(e) = prepare_backup_target(backup_dir, merge) {
+                hooks.after_all(false);
+                return self.fail(500, &e);
+            }
+        }
+
+        // The backup target may have just been cleared, so we can't rely on the cached layout.
+        let layout = if preview {
+            session.backup_layout.clone()
+        } else {
+            BackupLayout::new(backup_dir.clone(), config.backup.retention.clone())
+                .with_game_retention(config.backup.retention_overrides())
+        };
+        let ranking = InstallDirRanking::scan(&session.roots, &session.games, &subjects.valid);
+        let filter = config.backup.filter.compile();
+        self.progress.lock().unwrap().total_games = subjects.valid.len();
+
+        log::info!("beginning backup with {} steps", subjects.valid.len());
+        let steps: Vec<_> = subjects
+            .valid
+            .par_iter()
+            .map(|name| {
+                let game = &session.games.0[name];
+                let steam_id = game.steam.as_ref().and_then(|x| x.id);
+                let scan = || {
+                    scan_game_for_backup(
+                        game,
+                        name,
+                        &session.roots,
+                        &StrictPath::from_std_path_buf(&app_dir()),
+                        &session.launchers,
+                        &steam_id,
+                        &filter,
+                        &None,
+                        &ranking,
+                        &config.backup.toggled_paths,
+                        &config.backup.toggled_registry,
+                        layout.latest_backup(name, false, &config.redirects),
+                        &config.redirects,
+                        &session.steam_shortcuts,
+                    )
+                };
+                let scan_info = scan();
+                let ignored = !config.is_game_enabled_for_backup(name) && !games_specified;
+                let decision = if ignored {
+                    OperationStepDecision::Ignored
+                } else {
+                    OperationStepDecision::Processed
+                };
+                let (scan_info, backup_info, hook_failures) = if preview || ignored {
+                    (scan_info, BackupInfo::default(), vec![])
+                } else {
+                    hooks.around_game(
+                        scan_info,
+                        None,
+                        |_| scan(),
+                        |scan_info| {
+                            back_up_game(
+                                scan_info,
+                                layout.game_layout(name),
+                                merge,
+                                &chrono::Utc::now(),
+                                &config.backup.format_for(name),
+                                &|_, _| {},
+                            )
+                        },
+                        || layout.latest_backup_name(name),
+                    )
+                };
+
+                self.progress.lock().unwrap().processed_games += 1;
+                Step {
+                    name,
+                    scan_info,
+                    backup_info,
+                    decision,
+                    hook_failures,
+                }
+            })
+            .collect();
+        log::info!("completed backup");
+
+        let response = self.report(reporter, steps, &config.backup.sort, &hooks, preview, backup_dir);
+
+        drop(session);
+        if !preview {
+            self.session.write().unwrap().refresh_backups();
+        }
+        drop(running);
+        response
+    }
+
+    fn restore(&self, request: RestoreRequest, preview: bool) -> Response {
+        let Some(_running) = self.begin(HookOperation::Restore, preview) else {
+            return Response::error(409, "another operation is already running");
+        };
+
+        let session = self.session.read().unwrap();
+        let config = &session.config;
+        let restore_dir = &config.restore.path;
+        let mut reporter = Reporter::json();
+
+        if request.backup.is_some() && request.games.len() != 1 {
+            return self.fail(400, &Error::CliBackupIdWithMultipleGames);
+        }
+        let backup_id = match &request.backup {
+            Some(backup) => BackupId::Named(backup.clone()),
+            None => BackupId::Latest,
+        };
+
+        let games_specified = !request.games.is_empty();
+        let subjects = GameSubjects::new(
+            session.restore_layout.restorable_games(),
+            request.games,
+            false,
+            &session.games,
+        );
+        if !subjects.invalid.is_empty() {
+            reporter.trip_unknown_games(subjects.invalid);
+            return Response::json(404, reporter.render(restore_dir));
+        }
+
+        let hooks = HookRunner::new(&config.hooks, HookOperation::Restore, restore_dir);
+        if !preview {
+            let failures = hooks.before_all();
+            let blocked = hooks.blocks(&failures);
+            reporter.add_hook_failures(failures, hooks.failure_mode());
+            if blocked {
+                return Response::json(500, reporter.render(restore_dir));
+            }
+        }
+        self.progress.lock().unwrap().total_games = subjects.valid.len();
+
+        log::info!("beginning restore with {} steps", subjects.valid.len());
+        let steps: Result<Vec<_>, Error> = subjects
+            .valid
+            .par_iter()
+            .map(|name| {
+                let mut layout = session.restore_layout.game_layout(name);
+                let mut scan_info = scan_game_for_restoration(name, &backup_id, &mut layout, &config.redirects);
+                scan_info.update_ignored(&config.restore.toggled_paths, &ToggledRegistry::default());
+                let ignored = !config.is_game_enabled_for_restore(name) && !games_specified;
+                let decision = if ignored {
+                    OperationStepDecision::Ignored
+                } else {
+                    OperationStepDecision::Processed
+                };
+
+                if let Some(backup) = &request.backup {
+                    if let Some(BackupId::Named(scanned_backup)) = scan_info.backup.as_ref().map(|x| x.id()) {
+                        if backup != &scanned_backup {
+                            return Err(Error::CliInvalidBackupId);
+                        }
+                    }
+                }
+
+                let backup_name = scan_info.backup.as_ref().map(|x| x.name().to_string());
+                let (scan_info, backup_info, hook_failures) = match backup_name {
+                    Some(backup_name) if !preview && !ignored => hooks.around_game(
+                        scan_info,
+                        Some(&backup_name),
+                        |scan_info| scan_info,
+                        |scan_info| layout.restore(scan_info, &|_, _| {}),
+                        || Some(backup_name.clone()),
+                    ),
+                    _ => (scan_info, BackupInfo::default(), vec![]),
+                };
+
+                self.progress.lock().unwrap().processed_games += 1;
+                Ok(Step {
+                    name,
+                    scan_info,
+                    backup_info,
+                    decision,
+                    hook_failures,
+                })
+            })
+            .collect();
+        log::info!("completed restore");
+
+        match steps {
+            Ok(steps) => self.report(reporter, steps, &config.restore.sort, &hooks, preview, restore_dir),
+            Err(e) => {
+                if !preview {
+                    hooks.after_all(false);
+                }
+                self.fail(400, &e)
+            }
+        }
+    }
+
+    fn report(
+        &self,
+        mut reporter: Reporter,
+        mut steps: Vec<Step>,
+        sort: &Sort,
+        hooks: &HookRunner,
+        preview: bool,
+        path: &StrictPath,
+    ) -> Response {
+        let mut duplicate_detector = DuplicateDetector::default();
+        for step in &steps {
+            if step.scan_info.found_anything() {
+                duplicate_detector.add_game(&step.scan_info);
+            }
+        }
+
+        match sort.key {
+            SortKey::Name => steps.sort_by(|x, y| crate::prelude::compare_games_by_name(x.name, y.name)),
+            SortKey::Size => steps.sort_by(|x, y| {
+                crate::prelude::compare_games_by_size(
+                    &x.scan_info,
+                    &Some(x.backup_info.clone()),
+                    &y.scan_info,
+                    &Some(y.backup_info.clone()),
+                )
+            }),
+        }
+        if sort.reversed {
+            steps.reverse();
+        }
+
+        let mut failed = false;
+        let mut hook_failures = vec![];
+        for step in steps {
+            if !reporter.add_game(
+                step.name,
+                &step.scan_info,
+                &step.backup_info,
+                &step.decision,
+                &duplicate_detector,
+            ) {
+                failed = true;
+            }
+            hook_failures.extend(step.hook_failures);
+        }
+        if !preview {
+            hook_failures.extend(hooks.after_all(!failed));
+        }
+        reporter.add_hook_failures(hook_failures, hooks.failure_mode());
+
+        Response::json(200, reporter.render(path))
+    }
+}
+
+fn authorized(token: Option<&str>, authorization: Option<&str>) -> bool {
+    match token {
+        None => true,
+        Some(token) => authorization.and_then(|x| x.strip_prefix("Bearer ")) == Some(token),
+    }
+}
+
+fn is_json(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|x| x.split(';').next())
+        .is_some_and(|x| x.trim().eq_ignore_ascii_case("application/json"))
+}
+
+/// An empty body is treated like an empty JSON object.
+fn parse_body<T: Default + serde::de::DeserializeOwned>(body: &str) -> Result<T, Response> {
+    if body.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(body).map_err(|e| Response::error(400, &e.to_string()))
+}
+
+pub fn run(
+    config: &Config,
+    translator: Translator,
+    cache: &mut Cache,
+    port: u16,
+    host: IpAddr,
+    token: Option<String>,
+) -> Result<(), Error> {
+    if let Err(e) = Manifest::update_mut(config, cache, false) {
+        eprintln!("{}", translator.handle_error(&e));
+    }
+
+    let session = Session::load()?;
+    let address = SocketAddr::new(host, port);
+    let http = tiny_http::Server::http(address).map_err(|e| Error::ServerDidNotStart { why: e.to_string() })?;
+
+    if token.is_none() && !host.is_loopback() {
+        eprintln!("{}", translator.cli_serve_without_token());
+    }
+    println!("{}", translator.cli_serve_started(&format!("http://{address}")));
+
+    let server = Arc::new(Server {
+        session: RwLock::new(session),
+        progress: Mutex::new(Progress::default()),
+        address,
+        token,
+        translator,
+    });
+    let http = Arc::new(http);
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let server = server.clone();
+            let http = http.clone();
+            std::thread::spawn(move || {
+                for mut request in http.incoming_requests() {
+                    log::debug!("handling request: {} {}", request.method(), request.url());
+                    let response = server.respond(&mut request);
+                    let response = tiny_http::Response::from_string(response.body)
+                        .with_status_code(response.status)
+                        .with_header(tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap());
+                    if let Err(e) = request.respond(response) {
+                        log::warn!("unable to send response: {e}");
+                    }
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tiny_http::{Header, TestRequest};
+
+    fn server(token: Option<&str>) -> Server {
+        let games = Manifest::default();
+        let layout = BackupLayout::default();
+        Server {
+            session: RwLock::new(Session {
+                config: Config::default(),
+                title_finder: TitleFinder::new(&games, &layout),
+                games,
+                backup_layout: layout.clone(),
+                restore_layout: layout,
+                aliases: Default::default(),
+                roots: vec![],
+                launchers: Default::default(),
+                steam_shortcuts: Default::default(),
+                stamps: Stamps::current(&Config::default()),
+            }),
+            progress: Mutex::new(Progress::default()),
+            address: SocketAddr::from(([127, 0, 0, 1], 61234)),
+            token: token.map(|x| x.to_string()),
+            translator: Translator::default(),
+        }
+    }
+
+    fn header(field: &str, value: &str) -> Header {
+        Header::from_bytes(field, value).unwrap()
+    }
+
+    fn respond(server: &Server, request: TestRequest) -> Response {
+        server.respond(&mut request.into())
+    }
+
+    fn progress() -> TestRequest {
+        TestRequest::new()
+            .with_path("/progress")
+            .with_header(header("Host", "127.0.0.1:61234"))
+    }
+
+    fn find() -> TestRequest {
+        TestRequest::new()
+            .with_method(Method::Post)
+            .with_path("/find")
+            .with_header(header("Host", "localhost:61234"))
+    }
+
+    #[test]
+    fn can_accept_local_requests() {
+        let server = server(None);
+        assert_eq!(200, respond(&server, progress()).status);
+        assert_eq!(
+            200,
+            respond(&server, progress().with_header(header("Host", "LOCALHOST:61234"))).status
+        );
+        assert_eq!(
+            404,
+            respond(
+                &server,
+                find().with_header(header("Content-Type", "application/json; charset=utf-8"))
+            )
+            .status
+        );
+        assert_eq!(404, respond(&server, progress().with_path("/nope")).status);
+        assert_eq!(
+            405,
+            respond(
+                &server,
+                progress()
+                    .with_method(Method::Post)
+                    .with_header(header("Content-Type", "application/json"))
+            )
+            .status
+        );
+    }
+
+    #[test]
+    fn rejects_requests_from_web_pages() {
+        let server = server(None);
+        assert_eq!(
+            403,
+            respond(&server, progress().with_header(header("Origin", "https://example.com"))).status
+        );
+        assert_eq!(403, respond(&server, TestRequest::new().with_path("/progress")).status);
+        assert_eq!(
+            403,
+            respond(
+                &server,
+                TestRequest::new()
+                    .with_path("/progress")
+                    .with_header(header("Host", "example.com:61234"))
+            )
+            .status
+        );
+        assert_eq!(
+            403,
+            respond(
+                &server,
+                TestRequest::new()
+                    .with_path("/progress")
+                    .with_header(header("Host", "127.0.0.1:80"))
+            )
+            .status
+        );
+        assert_eq!(415, respond(&server, find()).status);
+        assert_eq!(
+            415,
+            respond(&server, find().with_header(header("Content-Type", "text/plain"))).status
+        );
+    }
+
+    #[test]
+    fn rejects_requests_without_token() {
+        let server = server(Some("foo"));
+        assert_eq!(401, respond(&server, progress()).status);
+        assert_eq!(
+            401,
+            respond(&server, progress().with_header(header("Authorization", "Bearer bar"))).status
+        );
+        assert_eq!(
+            200,
+            respond(&server, progress().with_header(header("Authorization", "Bearer foo"))).status
+        );
+    }
+
+    #[test]
+    fn accepts_any_host_with_token() {
+        let other_computer = || {
+            TestRequest::new()
+                .with_path("/progress")
+                .with_header(header("Host", "192.168.1.5:61234"))
+        };
+
+        let mut without_token = server(None);
+        without_token.address = SocketAddr::from(([0, 0, 0, 0], 61234));
+        assert_eq!(403, respond(&without_token, other_computer()).status);
+
+        let mut with_token = server(Some("foo"));
+        with_token.address = SocketAddr::from(([0, 0, 0, 0], 61234));
+        assert_eq!(
+            200,
+            respond(
+                &with_token,
+                other_computer().with_header(header("Authorization", "Bearer foo"))
+            )
+            .status
+        );
+        assert_eq!(
+            403,
+            respond(
+                &with_token,
+                other_computer()
+                    .with_header(header("Authorization", "Bearer foo"))
+                    .with_header(header("Origin", "https://example.com"))
+            )
+            .status
+        );
+    }
+
+    #[test]
+    fn rejects_large_request_body() {
+        let server = server(None);
+        let body: &'static str = Box::leak(" ".repeat(MAX_BODY_BYTES as usize + 1).into_boxed_str());
+        assert_eq!(
+            413,
+            respond(
+                &server,
+                find()
+                    .with_header(header("Content-Type", "application/json"))
+                    .with_body(body)
+            )
+            .status
+        );
+    }
+
+    #[test]
+    fn can_check_token() {
+        assert!(authorized(None, None));
+        assert!(authorized(None, Some("Bearer foo")));
+        assert!(authorized(Some("foo"), Some("Bearer foo")));
+        assert!(!authorized(Some("foo"), None));
+        assert!(!authorized(Some("foo"), Some("foo")));
+        assert!(!authorized(Some("foo"), Some("Bearer bar")));
+    }
+
+    #[test]
+    fn can_parse_request_body() {
+        let request: RestoreRequest = parse_body("").unwrap();
+        assert_eq!(Vec::<String>::new(), request.games);
+        assert_eq!(None, request.backup);
+
+        let request: RestoreRequest = parse_body(r#"{"games": ["foo"], "backup": "bar"}"#).unwrap();
+        assert_eq!(vec!["foo".to_string()], request.games);
+        assert_eq!(Some("bar".to_string()), request.backup);
+
+        let request: FindRequest = parse_body(r#"{"steamId": 10, "normalized": true}"#).unwrap();
+        assert_eq!(Some(10), request.steam_id);
+        assert!(request.normalized);
+
+        assert_eq!(
+            400,
+            parse_body::<GamesRequest>(r#"{"game": "foo"}"#).unwrap_err().status
+        );
+        assert_eq!(400, parse_body::<GamesRequest>("[").unwrap_err().status);
+    }
+}
@@ -0,0 +1,852 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Component, Path, PathBuf},
+    sync::mpsc::{channel, RecvTimeoutError, Sender},
+    time::{Duration, Instant, SystemTime},
+};
+
+use notify::{event::ModifyKind, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{
+    cache::Cache,
+    cli::{report::Reporter, GameSubjects},
+    config::{CompiledFilter, Config, RootsConfig, SecondaryManifestConfig},
+    hooks::{HookOperation, HookRunner},
+    lang::Translator,
+    launchers::Launchers,
+    layout::BackupLayout,
+    manifest::Manifest,
+    prelude::{
+        app_dir, back_up_game, find_path_candidates, prepare_backup_target, scan_game_for_backup, DuplicateDetector,
+        Error, InstallDirRanking, OperationStepDecision, ScanChange, SteamShortcuts, StrictPath, TitleFinder,
+        CASE_INSENSITIVE_OS,
+    },
+    serialization::ResourceFile,
+};
+
+/// How often to check for config/manifest changes and for games whose debounce period has elapsed.
+const TICK: Duration = Duration::from_secs(1);
+
+type EventSender = Sender<notify::Result<notify::Event>>;
+
+/// Modification times of the files that determine which save locations we watch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct Stamps {
+    config: Option<SystemTime>,
+    manifest: Option<SystemTime>,
+    secondary_manifests: Vec<Option<SystemTime>>,
+}
+
+impl Stamps {
+    pub(super) fn current(config: &Config) -> Self {
+        fn modified(path: PathBuf) -> Option<SystemTime> {
+            std::fs::metadata(path).and_then(|x| x.modified()).ok()
+        }
+
+        Self {
+            config: modified(Config::path()),
+            manifest: modified(Manifest::path()),
+            secondary_manifests: config
+                .manifest
+                .secondary
+                .iter()
+                .map(|secondary| {
+                    let path = match secondary {
+                        SecondaryManifestConfig::Local { path } => path.clone(),
+                        SecondaryManifestConfig::Remote { url } => Manifest::secondary_path(url),
+                    };
+                    modified(path.as_std_path_buf())
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How a changed path relates to a possible save location.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Relation {
+    /// A folder that may lead to a match, but isn't one yet.
+    Leading,
+    /// The path matches the save location.
+    Match,
+    /// The path is inside of a match.
+    Inside,
+}
+
+fn is_glob(component: &str) -> bool {
+    component.contains(['*', '?', '['])
+}
+
+fn component_matches(pattern: &str, actual: &std::ffi::OsStr) -> bool {
+    let actual = actual.to_string_lossy();
+    if !is_glob(pattern) {
+        return if CASE_INSENSITIVE_OS {
+            pattern.to_lowercase() == actual.to_lowercase()
+        } else {
+            pattern == actual
+        };
+    }
+
+    globetter::Pattern::new(pattern)
+        .map(|x| {
+            x.matches_with(
+                &actual,
+                globetter::MatchOptions {
+                    case_sensitive: !CASE_INSENSITIVE_OS,
+                    require_literal_separator: true,
+                    require_literal_leading_dot: false,
+                },
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Check whether a path could contain, or be inside of, a match for a save location pattern.
+fn relation(pattern: &Path, path: &Path) -> Option<Relation> {
+    let pattern: Vec<_> = pattern.components().collect();
+    let path: Vec<_> = path.components().collect();
+
+    for (i, (expected, actual)) in pattern.iter().zip(path.iter()).enumerate() {
+        match (expected, actual) {
+            (Component::Normal(expected), Component::Normal(actual)) => {
+                let expected = expected.to_string_lossy();
+                if expected == "**" {
+                    return Some(if i + 1 == pattern.len() && path.len() == pattern.len() {
+                        Relation::Match
+                    } else {
+                        Relation::Inside
+                    });
+                }
+                if !component_matches(&expected, actual) {
+                    return None;
+                }
+            }
+            (expected, actual) if expected == actual => {}
+            _ => return None,
+        }
+    }
+
+    Some(match path.len().cmp(&pattern.len()) {
+        std::cmp::Ordering::Less => Relation::Leading,
+        std::cmp::Ordering::Equal => Relation::Match,
+        std::cmp::Ordering::Greater => Relation::Inside,
+    })
+}
+
+/// Recursive watches take precedence, since they cover everything that a non-recursive one would.
+fn add_target(targets: &mut HashMap<PathBuf, RecursiveMode>, dir: PathBuf, mode: RecursiveMode) {
+    let entry = targets.entry(dir).or_insert(mode);
+    if mode == RecursiveMode::Recursive {
+        *entry = mode;
+    }
+}
+
+/// The narrowest folders that we need to watch to notice changes to a save location.
+/// Fixed folders leading up to the location aren't watched at all,
+/// and folders matching a glob pattern are only watched non-recursively
+/// so that we see the next level of the pattern being created.
+/// Only folders that fully match the pattern are watched recursively.
+/// If the location doesn't exist yet, then we watch its closest existing parent non-recursively,
+/// and `SaveLocations::creates_candidate` will tell us to start watching again once it's created.
+fn candidate_targets(pattern: &Path) -> Vec<(PathBuf, RecursiveMode)> {
+    let prefix: PathBuf = pattern
+        .components()
+        .take_while(|x| !is_glob(&x.as_os_str().to_string_lossy()))
+        .collect();
+    let rest: Vec<_> = pattern
+        .components()
+        .skip(prefix.components().count())
+        .map(|x| x.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    if !prefix.is_dir() {
+        let parent = match rest.is_empty() && prefix.is_file() {
+            true => prefix.parent(),
+            false => prefix.ancestors().skip(1).find(|x| x.is_dir()),
+        };
+        return parent
+            .map(|x| vec![(x.to_path_buf(), RecursiveMode::NonRecursive)])
+            .unwrap_or_default();
+    }
+
+    let mut targets = vec![];
+    let mut level = vec![prefix];
+    for component in rest {
+        if component == "**" {
+            break;
+        }
+        let mut next = vec![];
+        for dir in level {
+            if let Ok(entries) = std::fs::read_dir(&dir) {
+                next.extend(
+                    entries
+                        .filter_map(|x| x.ok())
+                        .filter(|x| x.path().is_dir() && component_matches(&component, &x.file_name()))
+                        .map(|x| x.path()),
+                );
+            }
+            targets.push((dir, RecursiveMode::NonRecursive));
+        }
+        level = next;
+    }
+    targets.extend(level.into_iter().map(|x| (x, RecursiveMode::Recursive)));
+
+    targets
+}
+
+/// Where the watched games keep their saves.
+#[derive(Clone, Debug, Default)]
+struct SaveLocations {
+    /// Folders containing save files, mapped to the games that store files there.
+    dirs: HashMap<PathBuf, BTreeSet<String>>,
+    /// Places where saves may be, even if they don't exist yet, mapped to the games that use them.
+    /// These may contain glob patterns.
+    candidates: HashMap<PathBuf, BTreeSet<String>>,
+}
+
+impl SaveLocations {
+    fn add(&mut self, name: &str, dirs: BTreeSet<PathBuf>, candidates: BTreeSet<PathBuf>) {
+        for dir in dirs {
+            self.dirs.entry(dir).or_default().insert(name.to_string());
+        }
+        for candidate in candidates {
+            self.candidates.entry(candidate).or_default().insert(name.to_string());
+        }
+    }
+
+    fn count_games(&self) -> usize {
+        self.dirs
+            .values()
+            .chain(self.candidates.values())
+            .flatten()
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Folders with known save files are only watched non-recursively,
+    /// since any new subfolders would be covered by the save locations.
+    fn watch_targets(&self) -> HashMap<PathBuf, RecursiveMode> {
+        let mut targets = HashMap::new();
+
+        for dir in self.dirs.keys() {
+            add_target(&mut targets, dir.clone(), RecursiveMode::NonRecursive);
+        }
+
+        for candidate in self.candidates.keys() {
+            for (dir, mode) in candidate_targets(candidate) {
+                add_target(&mut targets, dir, mode);
+            }
+        }
+
+        targets
+    }
+
+    fn affected_games(&self, event: &notify::Event) -> BTreeSet<String> {
+        let mut games = BTreeSet::new();
+
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) | EventKind::Any
+        ) {
+            return games;
+        }
+
+        for path in &event.paths {
+            for dir in [Some(path.as_path()), path.parent()].into_iter().flatten() {
+                if let Some(names) = self.dirs.get(dir) {
+                    games.extend(names.iter().cloned());
+                }
+            }
+            for (candidate, names) in &self.candidates {
+                let affected = match relation(candidate, path) {
+                    None => false,
+                    Some(Relation::Match | Relation::Inside) => true,
+                    // A folder may be created or moved in along with its files, which we won't see separately.
+                    Some(Relation::Leading) => Self::is_creation(event),
+                };
+                if affected {
+                    games.extend(names.iter().cloned());
+                }
+            }
+        }
+
+        games
+    }
+
+    fn is_creation(event: &notify::Event) -> bool {
+        matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_)) | EventKind::Any
+        )
+    }
+
+    /// Check if a save location (or a folder leading to it) was just created,
+    /// in which case we need to start watching it.
+    fn creates_candidate(&self, event: &notify::Event) -> bool {
+        if !Self::is_creation(event) {
+            return false;
+        }
+
+        event.paths.iter().filter(|path| path.is_dir()).any(|path| {
+            self.candidates
+                .keys()
+                .any(|candidate| matches!(relation(candidate, path), Some(Relation::Leading | Relation::Match)))
+        })
+    }
+}
+
+/// Games with changes that haven't settled down yet, and when they last changed.
+#[derive(Clone, Debug, Default)]
+struct Pending(HashMap<String, Instant>);
+
+impl Pending {
+    fn changed(&mut self, name: String, when: Instant) {
+        self.0.insert(name, when);
+    }
+
+    /// Take the games that haven't changed again for at least `debounce`.
+    fn settled(&mut self, debounce: Duration, now: Instant) -> Vec<String> {
+        let mut ready: Vec<_> = self
+            .0
+            .iter()
+            .filter(|(_, changed)| now.saturating_duration_since(**changed) >= debounce)
+            .map(|(name, _)| name.clone())
+            .collect();
+        ready.sort();
+        for name in &ready {
+            self.0.remove(name);
+        }
+        ready
+    }
+}
+
+/// Everything needed to scan and back up the watched games,
+/// resolved once and then reused until the config or manifest changes.
+struct Session {
+    config: Config,
+    filter: CompiledFilter,
+    games: Manifest,
+    backup_dir: StrictPath,
+    layout: BackupLayout,
+    roots: Vec<RootsConfig>,
+    launchers: Launchers,
+    ranking: InstallDirRanking,
+    steam_shortcuts: SteamShortcuts,
+    wine_prefix: Option<StrictPath>,
+    locations: SaveLocations,
+    stamps: Stamps,
+}
+
+impl Session {
+    fn load(path: &Option<StrictPath>, wine_prefix: &Option<StrictPath>, games: &[String]) -> Result<Self, Error> {
+        let config = Config::load()?;
+        let stamps = Stamps::current(&config);
+        let mut all_games = Manifest::load_with_secondary(&config)?;
+        all_games.load_custom_games(&config);
+
+        let subjects = GameSubjects::new(all_games.0.keys().cloned().collect(), games.to_vec(), false, &all_games);
+        if !subjects.invalid.is_empty() {
+            return Err(Error::CliUnrecognizedGames {
+                games: subjects.invalid,
+            });
+        }
+        let subjects: Vec<_> = if games.is_empty() {
+            subjects
+                .valid
+                .into_iter()
+                .filter(|name| config.is_game_enabled_for_backup(name))
+                .collect()
+        } else {
+            subjects.valid
+        };
+
+        let backup_dir = path.clone().unwrap_or_else(|| config.backup.path.clone());
+        let roots = config.expanded_roots();
+        let layout = BackupLayout::new(backup_dir.clone(), config.backup.retention.clone())
+            .with_game_retention(config.backup.retention_overrides());
+        let aliases = Cache::load().unwrap_or_default().game_aliases(&config);
+        let title_finder = TitleFinder::new(&all_games, &layout).with_aliases(&aliases);
+        let launchers = Launchers::scan(&roots, &title_finder, None);
+        let ranking = InstallDirRanking::scan(&roots, &all_games, &subjects);
+        let steam_shortcuts = SteamShortcuts::scan();
+
+        let mut session = Self {
+            filter: config.backup.filter.compile(),
+            config,
+            games: all_games,
+            backup_dir,
+            layout,
+            roots,
+            launchers,
+            ranking,
+            steam_shortcuts,
+            wine_prefix: wine_prefix.clone(),
+            locations: SaveLocations::default(),
+            stamps,
+        };
+
+        log::info!("resolving save locations for {} games", subjects.len());
+        let found: Vec<_> = subjects
+            .par_iter()
+            .map(|name| (name, session.save_dirs(name), session.save_candidates(name)))
+            .collect();
+        for (name, dirs, candidates) in found {
+            session.locations.add(name, dirs, candidates);
+        }
+        log::info!(
+            "resolved {} folders and {} possible save locations to watch",
+            session.locations.dirs.len(),
+            session.locations.candidates.len()
+        );
+
+        Ok(session)
+    }
+
+    fn scan(&self, name: &str) -> crate::prelude::ScanInfo {
+        let game = &self.games.0[name];
+        let steam_id = game.steam.as_ref().and_then(|x| x.id);
+        let previous = self.layout.latest_backup(name, false, &self.config.redirects);
+
+        scan_game_for_backup(
+            game,
+            name,
+            &self.roots,
+            &StrictPath::from_std_path_buf(&app_dir()),
+            &self.launchers,
+            &steam_id,
+            &self.filter,
+            &self.wine_prefix,
+            &self.ranking,
+            &self.config.backup.toggled_paths,
+            &self.config.backup.toggled_registry,
+            previous,
+            &self.config.redirects,
+            &self.steam_shortcuts,
+        )
+    }
+
+    fn save_dirs(&self, name: &str) -> BTreeSet<PathBuf> {
+        self.scan(name)
+            .found_files
+            .iter()
+            .filter(|file| !file.ignored && file.container.is_none())
+            .filter_map(|file| Path::new(&file.path.interpret()).parent().map(|x| x.to_path_buf()))
+            .collect()
+    }
+
+    /// Where the game's saves could be, whether or not they exist yet.
+    /// These may still contain glob patterns.
+    fn save_candidates(&self, name: &str) -> BTreeSet<PathBuf> {
+        let game = &self.games.0[name];
+        let steam_id = game.steam.as_ref().and_then(|x| x.id);
+
+        find_path_candidates(
+            game,
+            name,
+            &self.roots,
+            &StrictPath::from_std_path_buf(&app_dir()),
+            &self.launchers,
+            &steam_id,
+            &self.config.backup.filter,
+            &self.wine_prefix,
+            &self.ranking,
+            &self.steam_shortcuts,
+        )
+        .into_iter()
+        .filter(|candidate| !self.filter.is_path_ignored(&candidate.path))
+        .filter_map(|candidate| {
+            candidate
+                .fixed_prefix()
+                .map(|_| PathBuf::from(candidate.path.interpret()))
+        })
+        .collect()
+    }
+
+    fn watch(&self, sender: EventSender, poll: bool) -> Option<Box<dyn Watcher>> {
+        let config = notify::Config::default().with_poll_interval(TICK);
+
+        let mut watcher: Box<dyn Watcher> = if poll {
+            Box::new(PollWatcher::new(sender, config).ok()?)
+        } else {
+            match RecommendedWatcher::new(sender.clone(), config) {
+                Ok(x) => Box::new(x),
+                Err(e) => {
+                    log::warn!("unable to use native file watcher, falling back to polling: {e}");
+                    Box::new(PollWatcher::new(sender, config).ok()?)
+                }
+            }
+        };
+
+        let targets = self.locations.watch_targets();
+        log::info!("watching {} folders", targets.len());
+        for (dir, mode) in targets {
+            if let Err(e) = watcher.watch(&dir, mode) {
+                log::warn!("unable to watch folder ({mode:?}): {:?} | {e}", dir);
+            }
+        }
+
+        Some(watcher)
+    }
+
+    /// Back up the games whose changes have settled, between the global backup hooks.
+    fn back_up_all(&mut self, names: &[String], translator: Translator) {
+        let hooks = HookRunner::new(&self.config.hooks, HookOperation::Backup, &self.backup_dir);
+
+        let mut hook_failures = hooks.before_all();
+        if hooks.blocks(&hook_failures) {
+            let error = Error::HookFailed {
+                command: hook_failures[0].command.clone(),
+                why: hook_failures[0].reason.clone(),
+            };
+            eprintln!("{}", translator.handle_error(&error));
+            return;
+        }
+
+        let mut successful = true;
+        for name in names {
+            if !self.back_up(name, &hooks, translator) {
+                successful = false;
+                eprintln!("{}", translator.handle_error(&Error::SomeEntriesFailed));
+            }
+        }
+
+        hook_failures.extend(hooks.after_all(successful));
+        if !hook_failures.is_empty() {
+            let mut reporter = Reporter::standard(translator);
+            reporter.suppress_overall();
+            reporter.add_hook_failures(hook_failures, hooks.failure_mode());
+            reporter.print(&self.backup_dir);
+        }
+    }
+
+    fn back_up(&mut self, name: &str, hooks: &HookRunner, translator: Translator) -> bool {
+        if !self.games.0.contains_key(name) {
+            // The game was removed from the manifest while its changes were pending.
+            return true;
+        }
+        log::info!("backing up {name} after changes were detected");
+
+        let known = self.layout.latest_backup(name, false, &self.config.redirects).is_some();
+        let scan_info = self.scan(name);
+        if !scan_info.found_anything() {
+            return true;
+        }
+
+        let (scan_info, backup_info, hook_failures) = hooks.around_game(
+            scan_info,
+            None,
+            |_| self.scan(name),
+            |scan_info| {
+                back_up_game(
+                    scan_info,
+                    self.layout.game_layout(name),
+                    true,
+                    &chrono::Utc::now(),
+                    &self.config.backup.format_for(name),
+                    &|_, _| {},
+                )
+            },
+            || self.layout.latest_backup_name(name),
+        );
+        if !known {
+            // Pick up the new game folder so that later backups can detect changes against this one.
+            self.layout = BackupLayout::new(self.backup_dir.clone(), self.config.backup.retention.clone())
+                .with_game_retention(self.config.backup.retention_overrides());
+        }
+
+        if scan_info.count_changes().overall() == ScanChange::Same
+            && backup_info.successful()
+            && hook_failures.is_empty()
+        {
+            log::info!("no changes to back up for {name}");
+            return true;
+        }
+
+        let mut reporter = Reporter::standard(translator);
+        reporter.suppress_overall();
+        let mut successful = reporter.add_game(
+            name,
+            &scan_info,
+            &backup_info,
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        if hooks.blocks(&hook_failures) {
+            successful = false;
+        }
+        reporter.add_hook_failures(hook_failures, hooks.failure_mode());
+        reporter.print(&self.backup_dir);
+
+        successful
+    }
+}
+
+pub fn run(
+    config: &Config,
+    translator: Translator,
+    cache: &mut Cache,
+    path: Option<StrictPath>,
+    debounce: u64,
+    poll: bool,
+    wine_prefix: Option<StrictPath>,
+    games: Vec<String>,
+) -> Result<(), Error> {
+    let debounce = Duration::from_secs(debounce);
+
+    if let Err(e) = Manifest::update_mut(config, cache, false) {
+        eprintln!("{}", translator.handle_error(&e));
+    }
+
+    let mut session = Session::load(&path, &wine_prefix, &games)?;
+    prepare_backup_target(&session.backup_dir, true)?;
+
+    let mut pending = Pending::default();
+
+    let mut reloaded = true;
+    loop {
+        let (sender, receiver) = channel();
+        let _watcher = session.watch(sender, poll);
+        if reloaded {
+            println!("{}", translator.cli_watch_started(session.locations.count_games()));
+        }
+        let mut created = false;
+
+        loop {
+            match receiver.recv_timeout(TICK) {
+                Ok(Ok(event)) => {
+                    for name in session.locations.affected_games(&event) {
+                        log::debug!("change detected for {name}: {:?}", &event);
+                        pending.changed(name, Instant::now());
+                    }
+                    created |= session.locations.creates_candidate(&event);
+                }
+                Ok(Err(e)) => {
+                    log::warn!("error while watching for changes: {e}");
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    // The watcher could not be started, but we can still pick up config changes.
+                    std::thread::sleep(TICK);
+                }
+            }
+
+            let settled = pending.settled(debounce, Instant::now());
+            if !settled.is_empty() {
+                session.back_up_all(&settled, translator);
+            }
+
+            if created || Stamps::current(&session.config) != session.stamps {
+                break;
+            }
+        }
+
+        reloaded = Stamps::current(&session.config) != session.stamps;
+        if !reloaded {
+            log::info!("a save location was created, watching again");
+            continue;
+        }
+
+        log::info!("config or manifest changed, resolving save locations again");
+        match Session::load(&path, &wine_prefix, &games) {
+            Ok(reloaded) => {
+                session = reloaded;
+            }
+            Err(e) => {
+                eprintln!("{}", translator.handle_error(&e));
+                session.stamps = Stamps::current(&session.config);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{repo_raw, s};
+    use maplit::{btreeset, hashmap};
+    use notify::event::{CreateKind, DataChange, RemoveKind};
+    use pretty_assertions::assert_eq;
+
+    fn path(relative: &str) -> PathBuf {
+        Path::new(&repo_raw()).join(relative)
+    }
+
+    fn event(kind: EventKind, relative: &str) -> notify::Event {
+        notify::Event::new(kind).add_path(path(relative))
+    }
+
+    fn locations() -> SaveLocations {
+        SaveLocations {
+            dirs: hashmap! {
+                path("tests/root1/game1") => btreeset! { s("game1") },
+            },
+            candidates: hashmap! {
+                path("tests/root*/game2/*.txt") => btreeset! { s("game2") },
+                path("tests/root3/game5/data") => btreeset! { s("game5") },
+                path("tests/root3/missing/saves") => btreeset! { s("missing") },
+            },
+        }
+    }
+
+    #[test]
+    fn can_relate_paths_to_save_locations() {
+        let pattern = path("tests/root*/game2/*.txt");
+        assert_eq!(Some(Relation::Leading), relation(&pattern, &path("tests/root9")));
+        assert_eq!(Some(Relation::Leading), relation(&pattern, &path("tests/root9/game2")));
+        assert_eq!(
+            Some(Relation::Match),
+            relation(&pattern, &path("tests/root9/game2/new.txt"))
+        );
+        assert_eq!(
+            Some(Relation::Inside),
+            relation(&pattern, &path("tests/root9/game2/new.txt/x"))
+        );
+        assert_eq!(None, relation(&pattern, &path("tests/other/game2")));
+        assert_eq!(None, relation(&pattern, &path("tests/root9/game2/new.dat")));
+        assert_eq!(None, relation(&pattern, &path("tests/root9/game3/new.txt")));
+
+        let pattern = path("tests/**");
+        assert_eq!(Some(Relation::Inside), relation(&pattern, &path("tests/a/b")));
+    }
+
+    #[test]
+    fn only_watches_the_folders_that_can_lead_to_saves() {
+        assert_eq!(
+            hashmap! {
+                path("tests/root1/game1") => RecursiveMode::NonRecursive,
+                // Each level of `root*/game2/*.txt`:
+                path("tests") => RecursiveMode::NonRecursive,
+                path("tests/root2") => RecursiveMode::NonRecursive,
+                path("tests/root2/game2") => RecursiveMode::NonRecursive,
+                path("tests/root3") => RecursiveMode::NonRecursive,
+                path("tests/root1") => RecursiveMode::NonRecursive,
+                // An existing save folder:
+                path("tests/root3/game5/data") => RecursiveMode::Recursive,
+            },
+            locations().watch_targets(),
+        );
+    }
+
+    #[test]
+    fn watches_the_closest_parent_of_missing_save_locations() {
+        assert_eq!(
+            vec![(path("tests/root3"), RecursiveMode::NonRecursive)],
+            candidate_targets(&path("tests/root3/missing/saves")),
+        );
+        assert_eq!(
+            vec![(path("tests/root3/game5/data"), RecursiveMode::NonRecursive)],
+            candidate_targets(&path("tests/root3/game5/data/file1.txt")),
+        );
+    }
+
+    #[test]
+    fn can_find_games_affected_by_a_change() {
+        let locations = locations();
+
+        assert_eq!(
+            btreeset! { s("game1") },
+            locations.affected_games(&event(
+                EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+                "tests/root1/game1/ignored.txt"
+            )),
+        );
+        assert_eq!(
+            btreeset! { s("game2") },
+            locations.affected_games(&event(
+                EventKind::Remove(RemoveKind::File),
+                "tests/root2/game2/file1.txt"
+            )),
+        );
+        assert_eq!(
+            btreeset! { s("game5") },
+            locations.affected_games(&event(
+                EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+                "tests/root3/game5/data/nested/file.txt"
+            )),
+        );
+        assert_eq!(
+            btreeset! { s("missing") },
+            locations.affected_games(&event(EventKind::Create(CreateKind::Folder), "tests/root3/missing")),
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            locations.affected_games(&event(EventKind::Remove(RemoveKind::Folder), "tests/root3/missing")),
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            locations.affected_games(&event(
+                EventKind::Access(notify::event::AccessKind::Any),
+                "tests/root1/game1"
+            )),
+        );
+    }
+
+    #[test]
+    fn can_tell_when_a_save_location_is_created() {
+        let locations = locations();
+
+        assert!(locations.creates_candidate(&event(EventKind::Create(CreateKind::Folder), "tests/root2")));
+        assert!(locations.creates_candidate(&event(EventKind::Create(CreateKind::Folder), "tests/root2/game2")));
+        assert!(!locations.creates_candidate(&event(EventKind::Create(CreateKind::Folder), "tests/root1/game1")));
+        assert!(!locations.creates_candidate(&event(EventKind::Remove(RemoveKind::Folder), "tests/root2")));
+        // Files don't need to be watched separately.
+        assert!(!locations.creates_candidate(&event(
+            EventKind::Create(CreateKind::File),
+            "tests/root2/game2/file1.txt"
+        )));
+    }
+
+    #[test]
+    fn waits_for_changes_to_settle() {
+        let start = Instant::now();
+        let debounce = Duration::from_secs(5);
+        let mut pending = Pending::default();
+
+        pending.changed(s("foo"), start);
+        pending.changed(s("bar"), start + Duration::from_secs(2));
+        assert_eq!(
+            Vec::<String>::new(),
+            pending.settled(debounce, start + Duration::from_secs(4))
+        );
+
+        pending.changed(s("bar"), start + Duration::from_secs(4));
+        assert_eq!(
+            vec![s("foo")],
+            pending.settled(debounce, start + Duration::from_secs(5))
+        );
+        assert_eq!(
+            Vec::<String>::new(),
+            pending.settled(debounce, start + Duration::from_secs(8))
+        );
+        assert_eq!(
+            vec![s("bar")],
+            pending.settled(debounce, start + Duration::from_secs(9))
+        );
+        assert_eq!(
+            Vec::<String>::new(),
+            pending.settled(debounce, start + Duration::from_secs(20))
+        );
+    }
+
+    #[test]
+    fn notices_when_a_secondary_manifest_changes() {
+        let manifest = StrictPath::from_std_path_buf(
+            &std::env::temp_dir().join(format!("ludusavi-test-watch-{}.yaml", std::process::id())),
+        );
+        std::fs::write(manifest.interpret(), "").unwrap();
+        let mut config = Config::default();
+        config.manifest.secondary = vec![SecondaryManifestConfig::Local { path: manifest.clone() }];
+
+        let before = Stamps::current(&config);
+        assert_eq!(before, Stamps::current(&config));
+
+        manifest
+            .set_mtime(manifest.get_mtime().unwrap() + Duration::from_secs(1))
+            .unwrap();
+        let after = Stamps::current(&config);
+        let _ = manifest.remove();
+
+        assert_ne!(before, after);
+    }
+}
@@ -0,0 +1,527 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cache::Cache,
+    cli::{report::Reporter, run_hooks_before_all},
+    config::{Config, ToggledRegistry},
+    hooks::{HookOperation, HookRunner},
+    lang::Translator,
+    launchers::Launchers,
+    layout::BackupLayout,
+    manifest::Manifest,
+    prelude::{
+        app_dir, back_up_game, prepare_backup_target, scan_game_for_backup, scan_game_for_restoration, BackupId,
+        DuplicateDetector, Error, InstallDirRanking, OperationStepDecision, ScanChange, ScanInfo, SteamShortcuts,
+        StrictPath, TitleFinder,
+    },
+};
+
+/// How often to check whether the game's child processes are still running.
+#[cfg(target_os = "linux")]
+const CHILD_POLL: std::time::Duration = std::time::Duration::from_secs(1);
+
+pub fn run(
+    config: &Config,
+    translator: Translator,
+    cache: &mut Cache,
+    path: Option<StrictPath>,
+    game: Option<String>,
+    steam_id: Option<u32>,
+    gog_id: Option<u64>,
+    normalized: bool,
+    no_restore: bool,
+    no_backup: bool,
+    commands: Vec<String>,
+) -> Result<i32, Error> {
+    if let Err(e) = Manifest::update_mut(config, cache, false) {
+        eprintln!("{}", translator.handle_error(&e));
+    }
+    let mut manifest = Manifest::load_with_secondary(config).unwrap_or_default();
+    manifest.load_custom_games(config);
+
+    let restore_dir = path.clone().unwrap_or_else(|| config.restore.path.clone());
+    let backup_dir = path.unwrap_or_else(|| config.backup.path.clone());
+
+    let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+    let aliases = cache.game_aliases(config);
+    let name = match identify(&manifest, &layout, &aliases, game, steam_id, gog_id, normalized) {
+        Ok(name) => Some(name),
+        Err(e) => {
+            eprintln!("{}", translator.handle_error(&e));
+            None
+        }
+    };
+
+    if let Some(name) = &name {
+        if !no_restore {
+            if let Err(e) = restore(config, translator, &restore_dir, name) {
+                eprintln!("{}", translator.handle_error(&e));
+            }
+        }
+    }
+
+    // Even if the game didn't launch properly, it may have changed some files before failing,
+    // so we still back up and then report the launch error at the end.
+    let launched = launch(&commands);
+
+    if let Some(name) = &name {
+        if !no_backup {
+            if let Err(e) = back_up(config, translator, &manifest, &backup_dir, name) {
+                eprintln!("{}", translator.handle_error(&e));
+            }
+        }
+    }
+
+    launched
+}
+
+fn identify(
+    manifest: &Manifest,
+    layout: &BackupLayout,
+    aliases: &BTreeMap<String, String>,
+    game: Option<String>,
+    steam_id: Option<u32>,
+    gog_id: Option<u64>,
+    normalized: bool,
+) -> Result<String, Error> {
+    let title_finder = TitleFinder::new(manifest, layout).with_aliases(aliases);
+
+    let names: Vec<_> = game.into_iter().collect();
+    let found = title_finder.find(&names, &steam_id, &gog_id, normalized, false, false);
+
+    match found.len() {
+        0 => {
+            let mut invalid = names;
+            if let Some(steam_id) = steam_id {
+                invalid.push(steam_id.to_string());
+            }
+            if let Some(gog_id) = gog_id {
+                invalid.push(gog_id.to_string());
+            }
+            Err(Error::CliUnrecognizedGames { games: invalid })
+        }
+        1 => Ok(found.into_iter().next().unwrap()),
+        _ => Err(Error::CliAmbiguousGame {
+            games: found.into_iter().collect(),
+        }),
+    }
+}
+
+/// The latest backup should be restored if it differs from the current files
+/// and was made after any of them were last modified.
+fn should_restore(scan_info: &ScanInfo) -> bool {
+    let Some(backup) = &scan_info.backup else {
+        return false;
+    };
+
+    let mut changed = false;
+    for file in scan_info.found_files.iter().filter(|x| !x.ignored) {
+        if file.change == ScanChange::Same {
+            continue;
+        }
+        changed = true;
+
+        if let Ok(mtime) = file.effective().get_mtime() {
+            if chrono::DateTime::<chrono::Utc>::from(mtime) > *backup.when() {
+                log::info!(
+                    "not restoring because live file is newer than backup: {:?}",
+                    file.effective()
+                );
+                return false;
+            }
+        }
+    }
+
+    changed
+}
+
+fn restore(config: &Config, translator: Translator, restore_dir: &StrictPath, name: &str) -> Result<(), Error> {
+    let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+    let mut game_layout = layout.game_layout(name);
+    let mut scan_info = scan_game_for_restoration(name, &BackupId::Latest, &mut game_layout, &config.redirects);
+    scan_info.update_ignored(&config.restore.toggled_paths, &ToggledRegistry::default());
+
+    if !should_restore(&scan_info) {
+        log::info!("skipping restore for {name}");
+        return Ok(());
+    }
+
+    // Registry values are left alone so that we never roll back settings that are newer than the files.
+    scan_info.found_registry_keys.clear();
+
+    let mut reporter = Reporter::standard(translator);
+    reporter.suppress_overall();
+    let hooks = HookRunner::new(&config.hooks, HookOperation::Restore, restore_dir);
+    run_hooks_before_all(&hooks, &mut reporter)?;
+
+    log::info!("restoring {name} before launch");
+    let backup_name = scan_info.backup.as_ref().map(|x| x.name().to_string());
+    let (scan_info, restore_info, mut hook_failures) = hooks.around_game(
+        scan_info,
+        backup_name.as_deref(),
+        |scan_info| scan_info,
+        |scan_info| game_layout.restore(scan_info, &|_, _| {}),
+        || backup_name.clone(),
+    );
+
+    let mut successful = reporter.add_game(
+        name,
+        &scan_info,
+        &restore_info,
+        &OperationStepDecision::Processed,
+        &DuplicateDetector::default(),
+    );
+    hook_failures.extend(hooks.after_all(successful));
+    if hooks.blocks(&hook_failures) {
+        successful = false;
+    }
+    reporter.add_hook_failures(hook_failures, hooks.failure_mode());
+    reporter.print(restore_dir);
+
+    if successful {
+        Ok(())
+    } else {
+        Err(Error::SomeEntriesFailed)
+    }
+}
+
+fn back_up(
+    config: &Config,
+    translator: Translator,
+    manifest: &Manifest,
+    backup_dir: &StrictPath,
+    name: &str,
+) -> Result<(), Error> {
+    let mut reporter = Reporter::standard(translator);
+    reporter.suppress_overall();
+    let hooks = HookRunner::new(&config.hooks, HookOperation::Backup, backup_dir);
+    run_hooks_before_all(&hooks, &mut reporter)?;
+    if let Err(e) = prepare_backup_target(backup_dir, true) {
+        hooks.after_all(false);
+        return Err(e);
+    }
+
+    let roots = config.expanded_roots();
+    let layout = BackupLayout::new(backup_dir.clone(), config.backup.retention.clone())
+        .with_game_retention(config.backup.retention_overrides());
+    let title_finder = TitleFinder::new(manifest, &layout);
+    let launchers = Launchers::scan(&roots, &title_finder, None);
+    let ranking = InstallDirRanking::scan(&roots, manifest, &[name.to_string()]);
+    let steam_shortcuts = SteamShortcuts::scan();
+
+    let game = &manifest.0[name];
+    let steam_id = game.steam.as_ref().and_then(|x| x.id);
+    let filter = config.backup.filter.compile();
+
+    log::info!("backing up {name} after exit");
+    let scan = || {
+        scan_game_for_backup(
+            game,
+            name,
+            &roots,
+            &StrictPath::from_std_path_buf(&app_dir()),
+            &launchers,
+            &steam_id,
+            &filter,
+            &None,
+            &ranking,
+            &config.backup.toggled_paths,
+            &config.backup.toggled_registry,
+            layout.latest_backup(name, false, &config.redirects),
+            &config.redirects,
+            &steam_shortcuts,
+        )
+    };
+    let (scan_info, backup_info, mut hook_failures) = hooks.around_game(
+        scan(),
+        None,
+        |_| scan(),
+        |scan_info| {
+            back_up_game(
+                scan_info,
+                layout.game_layout(name),
+                true,
+                &chrono::Utc::now(),
+                &config.backup.format_for(name),
+                &|_, _| {},
+            )
+        },
+        || layout.latest_backup_name(name),
+    );
+
+    let mut successful = reporter.add_game(
+        name,
+        &scan_info,
+        &backup_info,
+        &OperationStepDecision::Processed,
+        &DuplicateDetector::default(),
+    );
+    hook_failures.extend(hooks.after_all(successful));
+    if hooks.blocks(&hook_failures) {
+        successful = false;
+    }
+    reporter.add_hook_failures(hook_failures, hooks.failure_mode());
+    reporter.print(backup_dir);
+
+    if successful {
+        Ok(())
+    } else {
+        Err(Error::SomeEntriesFailed)
+    }
+}
+
+/// Run the game and wait for it to exit, returning its exit code.
+///
+/// The game stays in our process group, so Ctrl+C and closing the terminal still reach it.
+/// We just keep ourselves alive for those signals so that we can back up once it exits.
+fn launch(commands: &[String]) -> Result<i32, Error> {
+    let mut command = std::process::Command::new(&commands[0]);
+    command.args(&commands[1..]);
+
+    #[cfg(unix)]
+    keep_running_on_interrupt();
+
+    // Any processes that the game leaves behind will be reparented to us,
+    // so that we can tell when they have exited too.
+    #[cfg(target_os = "linux")]
+    if unsafe { libc::prctl(libc::PR_SET_CHILD_SUBREAPER, 1) } != 0 {
+        log::warn!(
+            "unable to track the game's child processes: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+
+    log::info!("launching game: {:?}", commands);
+    let mut child = command
+        .spawn()
+        .map_err(|e| Error::GameDidNotLaunch { why: e.to_string() })?;
+    let status = child
+        .wait()
+        .map_err(|e| Error::GameDidNotLaunch { why: e.to_string() })?;
+
+    #[cfg(target_os = "linux")]
+    while children_alive() {
+        std::thread::sleep(CHILD_POLL);
+    }
+
+    log::info!("game exited: {:?}", status);
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Handle interrupts and hangups with a no-op instead of exiting.
+/// Unlike ignoring the signals, a handler is reset when the game is executed,
+/// so the game itself still receives them normally.
+#[cfg(unix)]
+fn keep_running_on_interrupt() {
+    extern "C" fn handle(_signal: libc::c_int) {}
+
+    for signal in [libc::SIGINT, libc::SIGHUP] {
+        // This is safe because the handler does nothing.
+        unsafe {
+            libc::signal(signal, handle as *const () as libc::sighandler_t);
+        }
+    }
+}
+
+/// Check if any of our child processes are still running.
+/// Since we're a subreaper, this covers everything that the game started, even after the game itself exits.
+/// Finished children are reaped along the way.
+#[cfg(target_os = "linux")]
+fn children_alive() -> bool {
+    loop {
+        // This is safe because we don't need the status of any remaining children.
+        let reaped = unsafe { libc::waitpid(-1, std::ptr::null_mut(), libc::WNOHANG) };
+        if reaped <= 0 {
+            break;
+        }
+    }
+
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return false;
+    };
+    let parent = std::process::id();
+
+    entries.filter_map(|entry| entry.ok()).any(|entry| {
+        let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+            return false;
+        };
+        // Format: `pid (comm) state ppid ...`, where `comm` may contain spaces or parentheses.
+        let Some((_, rest)) = stat.rsplit_once(')') else {
+            return false;
+        };
+        let fields: Vec<_> = rest.split_whitespace().take(2).collect();
+        match fields.as_slice() {
+            // Zombies are already finished, even if they haven't been reaped yet.
+            ["Z", ..] => false,
+            [_, ppid] => ppid.parse::<u32>().map(|x| x == parent).unwrap_or(false),
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Retention,
+        layout::{Backup, FullBackup},
+        prelude::ScannedFile,
+        serialization::ResourceFile,
+        testing::{repo, s},
+    };
+    use maplit::{btreemap, hashset};
+    use pretty_assertions::assert_eq;
+
+    fn live_file() -> String {
+        format!("{}/tests/root2/game1/file1.txt", repo())
+    }
+
+    fn scan_info(when: chrono::DateTime<chrono::Utc>, files: std::collections::HashSet<ScannedFile>) -> ScanInfo {
+        ScanInfo {
+            game_name: s("game1"),
+            found_files: files,
+            backup: Some(Backup::Full(FullBackup {
+                name: s("backup-1"),
+                when,
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+
+    fn future() -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now() + chrono::Duration::days(1)
+    }
+
+    fn past() -> chrono::DateTime<chrono::Utc> {
+        chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap()
+            .and_local_timezone(chrono::Utc)
+            .unwrap()
+    }
+
+    #[test]
+    fn restores_a_backup_newer_than_the_live_files() {
+        assert!(should_restore(&scan_info(
+            future(),
+            hashset! {
+                ScannedFile::new("backup-1/file1.txt", 1, "old").original(live_file()).change(ScanChange::Different),
+            },
+        )));
+    }
+
+    #[test]
+    fn does_not_restore_over_newer_live_files() {
+        assert!(!should_restore(&scan_info(
+            past(),
+            hashset! {
+                ScannedFile::new("backup-1/file1.txt", 1, "old").original(live_file()).change(ScanChange::Different),
+            },
+        )));
+    }
+
+    #[test]
+    fn restores_missing_live_files() {
+        assert!(should_restore(&scan_info(
+            past(),
+            hashset! {
+                ScannedFile::new("backup-1/missing.txt", 1, "old")
+                    .original(format!("{}/tests/root2/game1/missing.txt", repo()))
+                    .change_new(),
+            },
+        )));
+    }
+
+    #[test]
+    fn does_not_restore_without_changes() {
+        assert!(!should_restore(&scan_info(
+            future(),
+            hashset! {
+                ScannedFile::new("backup-1/file1.txt", 1, "old").original(live_file()).change(ScanChange::Same),
+                ScannedFile::new("backup-1/missing.txt", 1, "old")
+                    .original(format!("{}/tests/root2/game1/missing.txt", repo()))
+                    .change_new()
+                    .ignored(),
+            },
+        )));
+        assert!(!should_restore(&ScanInfo {
+            game_name: s("game1"),
+            found_files: hashset! {
+                ScannedFile::new(live_file(), 1, "old").change_new(),
+            },
+            ..Default::default()
+        }));
+    }
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            Some Game:
+              steam:
+                id: 101
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn layout() -> BackupLayout {
+        BackupLayout::new(
+            StrictPath::new(format!("{}/tests/backup", repo())),
+            Retention::default(),
+        )
+    }
+
+    #[test]
+    fn can_identify_game_by_name_or_id() {
+        let identify = |game: Option<&str>, steam_id: Option<u32>, normalized: bool| {
+            identify(
+                &manifest(),
+                &layout(),
+                &btreemap! { s("Old Game") => s("Some Game") },
+                game.map(s),
+                steam_id,
+                None,
+                normalized,
+            )
+        };
+
+        assert_eq!(Ok(s("Some Game")), identify(Some("Some Game"), None, false));
+        assert_eq!(Ok(s("Some Game")), identify(None, Some(101), false));
+        assert_eq!(Ok(s("Some Game")), identify(Some("Old Game"), None, false));
+        assert_eq!(Ok(s("Some Game")), identify(Some("some game"), None, true));
+    }
+
+    #[test]
+    fn reports_unknown_game() {
+        assert_eq!(
+            Err(Error::CliUnrecognizedGames {
+                games: vec![s("Other Game"), s("102"), s("103")],
+            }),
+            identify(
+                &manifest(),
+                &layout(),
+                &BTreeMap::new(),
+                Some(s("Other Game")),
+                Some(102),
+                Some(103),
+                false,
+            ),
+        );
+        assert_eq!(
+            Err(Error::CliUnrecognizedGames {
+                games: vec![s("some game")]
+            }),
+            identify(
+                &manifest(),
+                &layout(),
+                &BTreeMap::new(),
+                Some(s("some game")),
+                None,
+                None,
+                false,
+            ),
+        );
+    }
+}
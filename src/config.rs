@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
+use regex::{Regex, RegexBuilder};
+
 use crate::{
     lang::Language,
-    manifest::Store,
-    prelude::{app_dir, Error, RegistryItem, StrictPath},
+    manifest::{GameFileConstraint, GameRegistryConstraint, GogMetadata, Os, SteamMetadata, Store, Tag},
+    prelude::{app_dir, BackupExclusion, Error, RegistryItem, StrictPath},
     serialization::{ResourceFile, SaveableResourceFile},
 };
 
@@ -27,6 +31,11 @@ pub struct Config {
     pub restore: RestoreConfig,
     #[serde(default, rename = "customGames")]
     pub custom_games: Vec<CustomGame>,
+    /// Old game names mapped to their current names in the manifest.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub aliases: std::collections::BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -35,6 +44,72 @@ pub struct ManifestConfig {
     #[serde(default)]
     #[deprecated(note = "use cache")]
     pub etag: Option<String>,
+    /// Additional manifests, which take precedence over the primary one.
+    /// Later entries take precedence over earlier ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secondary: Vec<SecondaryManifestConfig>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum SecondaryManifestConfig {
+    Local { path: StrictPath },
+    Remote { url: String },
+}
+
+impl SecondaryManifestConfig {
+    pub fn kind(&self) -> SecondaryManifestConfigKind {
+        match self {
+            Self::Local { .. } => SecondaryManifestConfigKind::Local,
+            Self::Remote { .. } => SecondaryManifestConfigKind::Remote,
+        }
+    }
+
+    pub fn value(&self) -> String {
+        match self {
+            Self::Local { path } => path.raw(),
+            Self::Remote { url } => url.clone(),
+        }
+    }
+
+    pub fn set_value(&mut self, value: String) {
+        match self {
+            Self::Local { path } => path.reset(value),
+            Self::Remote { url } => *url = value,
+        }
+    }
+
+    pub fn convert(&mut self, kind: SecondaryManifestConfigKind) {
+        let value = self.value();
+        *self = match kind {
+            SecondaryManifestConfigKind::Local => Self::Local {
+                path: StrictPath::new(value),
+            },
+            SecondaryManifestConfigKind::Remote => Self::Remote { url: value },
+        };
+    }
+}
+
+impl Default for SecondaryManifestConfig {
+    fn default() -> Self {
+        Self::Remote { url: String::new() }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecondaryManifestConfigKind {
+    Local,
+    Remote,
+}
+
+impl SecondaryManifestConfigKind {
+    pub const ALL: &'static [Self] = &[Self::Local, Self::Remote];
+}
+
+impl ToString for SecondaryManifestConfigKind {
+    fn to_string(&self) -> String {
+        crate::lang::Translator::default().secondary_manifest_kind(self)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -105,6 +180,205 @@ impl ToString for RedirectKind {
     }
 }
 
+/// Entries in `ignoredPaths`/`ignoredRegistry` with this prefix are regular expressions.
+const REGEX_PREFIX: &str = "regex:";
+
+/// Whether a backup filter entry is a glob pattern rather than a plain path.
+fn is_glob_pattern(raw: &str) -> bool {
+    raw.contains(['*', '?'])
+}
+
+/// Check whether an entry is a usable glob or regex.
+/// Plain paths are always valid.
+pub fn is_valid_filter_pattern(raw: &str) -> bool {
+    if let Some(pattern) = raw.strip_prefix(REGEX_PREFIX) {
+        Regex::new(pattern).is_ok()
+    } else if is_glob_pattern(raw) {
+        globetter::Pattern::new(&raw.replace('\\', "/")).is_ok()
+    } else {
+        true
+    }
+}
+
+/// A glob or regex entry from a backup filter, ready for matching.
+#[derive(Clone, Debug)]
+enum FilterPattern {
+    Glob(globetter::Pattern),
+    Regex(Regex),
+    /// The entry could not be compiled, so it never matches.
+    Invalid,
+}
+
+impl FilterPattern {
+    /// Compile an entry from `ignoredPaths`, or return nothing if it's a plain path.
+    fn for_path(entry: &StrictPath) -> Option<Self> {
+        let raw = entry.raw();
+        if let Some(pattern) = raw.strip_prefix(REGEX_PREFIX) {
+            Some(Self::regex(RegexBuilder::new(pattern).build(), pattern))
+        } else if is_glob_pattern(&raw) {
+            // Relative patterns like `*.log` can match anywhere.
+            let relative = !raw.starts_with('~') && !std::path::Path::new(&raw).is_absolute();
+            let pattern = if relative {
+                format!("**/{}", raw.replace('\\', "/"))
+            } else {
+                entry.render()
+            };
+            Some(Self::glob(&pattern))
+        } else {
+            None
+        }
+    }
+
+    /// Compile an entry from `ignoredRegistry`, or return nothing if it's a plain key.
+    /// Registry keys are case-insensitive, so these patterns are too.
+    fn for_registry(entry: &RegistryItem) -> Option<Self> {
+        let raw = entry.raw();
+        if let Some(pattern) = raw.strip_prefix(REGEX_PREFIX) {
+            Some(Self::regex(
+                RegexBuilder::new(pattern).case_insensitive(true).build(),
+                pattern,
+            ))
+        } else if is_glob_pattern(&raw) {
+            // Relative patterns (not starting with a hive) can match anywhere.
+            let pattern = if raw.to_uppercase().starts_with("HKEY_") {
+                entry.render()
+            } else {
+                format!("**/{}", entry.render())
+            };
+            Some(Self::glob(&pattern))
+        } else {
+            None
+        }
+    }
+
+    fn regex(compiled: Result<Regex, regex::Error>, pattern: &str) -> Self {
+        match compiled {
+            Ok(x) => Self::Regex(x),
+            Err(e) => {
+                log::warn!("Invalid regex in backup filter: {pattern} | {e}");
+                Self::Invalid
+            }
+        }
+    }
+
+    fn glob(pattern: &str) -> Self {
+        match globetter::Pattern::new(pattern) {
+            Ok(x) => Self::Glob(x),
+            Err(e) => {
+                log::warn!("Invalid glob in backup filter: {pattern} | {e}");
+                Self::Invalid
+            }
+        }
+    }
+
+    /// Globs match a path (using `/` as the separator) or any of its ancestors,
+    /// so that matching a folder also matches everything inside of it.
+    fn matches(&self, path: &str, case_sensitive: bool) -> bool {
+        match self {
+            Self::Glob(pattern) => {
+                let options = globetter::MatchOptions {
+                    case_sensitive,
+                    require_literal_separator: true,
+                    require_literal_leading_dot: false,
+                };
+
+                let path = path.trim_end_matches('/');
+                pattern.matches_with(path, options)
+                    || path
+                        .match_indices('/')
+                        .any(|(i, _)| i > 0 && pattern.matches_with(&path[..i], options))
+            }
+            Self::Regex(re) => re.is_match(path),
+            Self::Invalid => false,
+        }
+    }
+}
+
+/// A `BackupFilter` with its globs and regexes compiled ahead of time,
+/// so that they don't need to be parsed for every file.
+/// This should be built once where a scan starts, since it does not follow later edits to the filter.
+#[derive(Clone, Debug, Default)]
+pub struct CompiledFilter {
+    pub rules: BackupFilter,
+    /// Keyed by the raw entries.
+    paths: HashMap<String, FilterPattern>,
+    registry: HashMap<String, FilterPattern>,
+}
+
+impl CompiledFilter {
+    pub fn new(rules: &BackupFilter) -> Self {
+        let mut paths = HashMap::new();
+        let mut registry = HashMap::new();
+
+        let game_paths = rules.games.values().flat_map(|x| x.ignored_paths.iter());
+        for entry in rules.ignored_paths.iter().chain(game_paths) {
+            if let Some(pattern) = FilterPattern::for_path(entry) {
+                paths.insert(entry.raw(), pattern);
+            }
+        }
+        for entry in &rules.ignored_registry {
+            if let Some(pattern) = FilterPattern::for_registry(entry) {
+                registry.insert(entry.raw(), pattern);
+            }
+        }
+
+        Self {
+            rules: rules.clone(),
+            paths,
+            registry,
+        }
+    }
+
+    /// Find which of a game's own rules excludes this file, if any.
+    pub fn game_file_exclusion(&self, game: &str, item: &StrictPath, size: u64) -> Option<BackupExclusion> {
+        self.rules.game_file_exclusion_with(game, item, size, &self.paths)
+    }
+
+    pub fn is_path_ignored(&self, item: &StrictPath) -> bool {
+        self.path_exclusion(item).is_some()
+    }
+
+    /// Find which part of the filter excludes this path, if any.
+    pub fn path_exclusion(&self, item: &StrictPath) -> Option<BackupExclusion> {
+        self.rules.path_exclusion_with(item, &self.paths)
+    }
+
+    #[allow(dead_code)]
+    pub fn is_registry_ignored(&self, item: &RegistryItem) -> bool {
+        self.rules.is_registry_ignored_with(item, &self.registry)
+    }
+}
+
+/// Use the precompiled pattern for an entry if there is one,
+/// or else compile it on the spot.
+fn compiled_pattern(
+    compiled: &HashMap<String, FilterPattern>,
+    raw: String,
+    compile: impl FnOnce() -> Option<FilterPattern>,
+) -> Option<std::borrow::Cow<'_, FilterPattern>> {
+    match compiled.get(&raw) {
+        Some(pattern) => Some(std::borrow::Cow::Borrowed(pattern)),
+        None => compile().map(std::borrow::Cow::Owned),
+    }
+}
+
+/// Find the first entry that matches the path,
+/// whether it's a plain path, a glob, or a regular expression.
+fn find_ignored_path<'a>(
+    entries: &'a [StrictPath],
+    item: &StrictPath,
+    compiled: &HashMap<String, FilterPattern>,
+) -> Option<&'a StrictPath> {
+    let interpreted = item.interpret();
+    let rendered = item.render();
+    entries.iter().find(
+        |x| match compiled_pattern(compiled, x.raw(), || FilterPattern::for_path(x)) {
+            Some(pattern) => pattern.matches(&rendered, !crate::prelude::CASE_INSENSITIVE_OS),
+            None => x.is_prefix_of(item) || x.interpret() == interpreted,
+        },
+    )
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct BackupFilter {
     #[serde(
@@ -117,28 +391,101 @@ pub struct BackupFilter {
     pub ignored_paths: Vec<StrictPath>,
     #[serde(default, rename = "ignoredRegistry")]
     pub ignored_registry: Vec<RegistryItem>,
+    /// Only back up Steam `userdata` files for these account IDs.
+    /// When empty, all users are included.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", rename = "steamUsers")]
+    pub steam_users: Vec<String>,
+    /// Extra rules for specific games, by name.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub games: std::collections::BTreeMap<String, GameFilter>,
+}
+
+/// Backup rules that only apply to one game.
+/// Files excluded by these rules are still reported, but they are never backed up.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GameFilter {
+    /// Interpreted the same way as `BackupFilter::ignored_paths`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", rename = "ignoredPaths")]
+    pub ignored_paths: Vec<StrictPath>,
+    /// Files larger than this many bytes are excluded.
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "maxFileSize")]
+    pub max_file_size: Option<u64>,
+    /// A warning is shown when the game's backup would be larger than this many bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "maxTotalSize")]
+    pub max_total_size: Option<u64>,
+}
+
+impl GameFilter {
+    pub fn is_empty(&self) -> bool {
+        self.ignored_paths.is_empty() && self.max_file_size.is_none() && self.max_total_size.is_none()
+    }
+
+    /// Find which rule excludes this file, if any.
+    fn file_exclusion(
+        &self,
+        item: &StrictPath,
+        size: u64,
+        compiled: &HashMap<String, FilterPattern>,
+    ) -> Option<BackupExclusion> {
+        if let Some(rule) = find_ignored_path(&self.ignored_paths, item, compiled) {
+            return Some(BackupExclusion::GameIgnoredPath(rule.clone()));
+        }
+        match self.max_file_size {
+            Some(max) if size > max => Some(BackupExclusion::MaxFileSize(max)),
+            _ => None,
+        }
+    }
 }
 
 impl BackupFilter {
-    pub fn is_path_ignored(&self, item: &StrictPath) -> bool {
+    /// Compile the globs and regexes in the filter so that they don't need to be parsed for every file.
+    pub fn compile(&self) -> CompiledFilter {
+        CompiledFilter::new(self)
+    }
+
+    fn game_file_exclusion_with(
+        &self,
+        game: &str,
+        item: &StrictPath,
+        size: u64,
+        compiled: &HashMap<String, FilterPattern>,
+    ) -> Option<BackupExclusion> {
+        self.games
+            .get(game)
+            .and_then(|x| x.file_exclusion(item, size, compiled))
+    }
+
+    fn path_exclusion_with(
+        &self,
+        item: &StrictPath,
+        compiled: &HashMap<String, FilterPattern>,
+    ) -> Option<BackupExclusion> {
+        if !self.steam_users.is_empty() {
+            if let Some(user) = crate::steam::user_id_in_path(item) {
+                if !self.steam_users.contains(&user) {
+                    return Some(BackupExclusion::SteamUser(user));
+                }
+            }
+        }
         if self.ignored_paths.is_empty() {
-            return false;
+            return None;
         }
-        let interpreted = item.interpret();
-        self.ignored_paths
-            .iter()
-            .any(|x| x.is_prefix_of(item) || x.interpret() == interpreted)
+        find_ignored_path(&self.ignored_paths, item, compiled).map(|x| BackupExclusion::IgnoredPath(x.clone()))
     }
 
     #[allow(dead_code)]
-    pub fn is_registry_ignored(&self, item: &RegistryItem) -> bool {
+    fn is_registry_ignored_with(&self, item: &RegistryItem, compiled: &HashMap<String, FilterPattern>) -> bool {
         if self.ignored_registry.is_empty() {
             return false;
         }
         let interpreted = item.interpret();
-        self.ignored_registry
-            .iter()
-            .any(|x| x.is_prefix_of(item) || x.interpret() == interpreted)
+        let rendered = item.render();
+        self.ignored_registry.iter().any(|x| {
+            match compiled_pattern(compiled, x.raw(), || FilterPattern::for_registry(x)) {
+                Some(pattern) => pattern.matches(&rendered, false),
+                None => x.is_prefix_of(item) || x.interpret() == interpreted,
+            }
+        })
     }
 }
 
@@ -323,7 +670,9 @@ impl ToString for BackupFormat {
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct BackupFormats {
+    #[serde(default)]
     pub chosen: BackupFormat,
+    #[serde(default)]
     pub zip: ZipConfig,
     #[serde(default)]
     pub compression: Compression,
@@ -511,6 +860,173 @@ pub struct BackupConfig {
     pub retention: Retention,
     #[serde(default)]
     pub format: BackupFormats,
+    /// Settings that replace parts of `retention` and `format` for specific games, by name.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub overrides: std::collections::BTreeMap<String, GameBackupOverrides>,
+}
+
+impl BackupConfig {
+    /// The format to use for a game, with any overridden fields applied on top of the global format.
+    pub fn format_for(&self, game: &str) -> BackupFormats {
+        let mut format = self.format.clone();
+        if let Some(overrides) = self.overrides.get(game) {
+            overrides.format.apply(&mut format);
+        }
+        format
+    }
+
+    /// The retention to use for a game, with any overridden fields applied on top of the global retention.
+    pub fn retention_for(&self, game: &str) -> Retention {
+        let mut retention = self.retention.clone();
+        if let Some(overrides) = self.overrides.get(game) {
+            overrides.retention.apply(&mut retention);
+        }
+        retention
+    }
+
+    /// Each game's retention, for games that override it.
+    pub fn retention_overrides(&self) -> std::collections::BTreeMap<String, Retention> {
+        self.overrides
+            .iter()
+            .filter(|(_, x)| !x.retention.is_empty())
+            .map(|(game, _)| (game.clone(), self.retention_for(game)))
+            .collect()
+    }
+}
+
+/// Backup settings for one game. Anything unset falls back to the global setting.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GameBackupOverrides {
+    #[serde(default, skip_serializing_if = "RetentionOverrides::is_empty")]
+    pub retention: RetentionOverrides,
+    #[serde(default, skip_serializing_if = "BackupFormatOverrides::is_empty")]
+    pub format: BackupFormatOverrides,
+}
+
+impl GameBackupOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.retention.is_empty() && self.format.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RetentionOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub differential: Option<u8>,
+}
+
+impl RetentionOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.full.is_none() && self.differential.is_none()
+    }
+
+    pub fn apply(&self, retention: &mut Retention) {
+        if let Some(full) = self.full {
+            retention.full = full;
+        }
+        if let Some(differential) = self.differential {
+            retention.differential = differential;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BackupFormatOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chosen: Option<BackupFormat>,
+    #[serde(default, skip_serializing_if = "ZipConfigOverrides::is_empty")]
+    pub zip: ZipConfigOverrides,
+    /// Compression level for the effective compression method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<i32>,
+}
+
+impl BackupFormatOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.chosen.is_none() && self.zip.is_empty() && self.level.is_none()
+    }
+
+    pub fn apply(&self, format: &mut BackupFormats) {
+        if let Some(chosen) = self.chosen {
+            format.chosen = chosen;
+        }
+        if let Some(compression) = self.zip.compression {
+            format.zip.compression = compression;
+        }
+        if let Some(level) = self.level {
+            format.compression.set_level(&format.zip.compression, level);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ZipConfigOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<ZipCompression>,
+}
+
+impl ZipConfigOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.compression.is_none()
+    }
+}
+
+/// Commands to run before and after backups and restores.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HooksConfig {
+    #[serde(default, rename = "onFailure")]
+    pub on_failure: HookFailureMode,
+    #[serde(default, skip_serializing_if = "OperationHooks::is_empty")]
+    pub backup: OperationHooks,
+    #[serde(default, skip_serializing_if = "OperationHooks::is_empty")]
+    pub restore: OperationHooks,
+    /// Hooks that only run around a specific game, by name.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub games: std::collections::BTreeMap<String, GameHooks>,
+}
+
+impl HooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.on_failure == HookFailureMode::default()
+            && self.backup.is_empty()
+            && self.restore.is_empty()
+            && self.games.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum HookFailureMode {
+    /// Report the failure, but carry on with the operation.
+    #[default]
+    #[serde(rename = "warning")]
+    Warning,
+    /// Report the failure, skip whatever the hook was guarding, and fail the operation.
+    #[serde(rename = "error")]
+    Error,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OperationHooks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
+impl OperationHooks {
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GameHooks {
+    #[serde(default, skip_serializing_if = "OperationHooks::is_empty")]
+    pub backup: OperationHooks,
+    #[serde(default, skip_serializing_if = "OperationHooks::is_empty")]
+    pub restore: OperationHooks,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -528,19 +1044,182 @@ pub struct RestoreConfig {
     #[serde(default, skip_serializing)]
     #[deprecated(note = "use global redirect list instead")]
     pub redirects: Vec<RedirectConfig>,
+    #[serde(default, rename = "toggledPaths")]
+    pub toggled_paths: ToggledPaths,
     #[serde(default)]
     pub sort: Sort,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct CustomGame {
     pub name: String,
     #[serde(default, skip_serializing_if = "crate::serialization::is_false")]
     pub ignore: bool,
+    #[serde(default, skip_serializing_if = "crate::serialization::is_default")]
+    pub integration: Integration,
     #[serde(default)]
-    pub files: Vec<String>,
+    pub files: Vec<CustomGameFile>,
     #[serde(default)]
-    pub registry: Vec<String>,
+    pub registry: Vec<CustomGameRegistry>,
+    #[serde(default, rename = "installDir", skip_serializing_if = "Vec::is_empty")]
+    pub install_dir: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steam: Option<SteamMetadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gog: Option<GogMetadata>,
+}
+
+/// How a custom game is combined with a manifest entry of the same name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Integration {
+    /// Replace the manifest entry's files and registry.
+    #[default]
+    #[serde(rename = "override")]
+    Override,
+    /// Keep the manifest entry's files and registry, and add the custom ones.
+    #[serde(rename = "extend")]
+    Extend,
+}
+
+impl Integration {
+    pub const ALL: &'static [Self] = &[Self::Override, Self::Extend];
+}
+
+impl ToString for Integration {
+    fn to_string(&self) -> String {
+        crate::lang::Translator::default().integration(self)
+    }
+}
+
+pub type CustomGameFile = CustomGameEntry<GameFileConstraint>;
+pub type CustomGameRegistry = CustomGameEntry<GameRegistryConstraint>;
+
+/// A file or registry path for a custom game, with the same tags and constraints as in the manifest.
+/// When there are no tags or constraints, this is serialized as just the path.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(
+    from = "RawCustomGameEntry<C>",
+    into = "RawCustomGameEntry<C>",
+    bound(
+        serialize = "C: Clone + serde::Serialize",
+        deserialize = "C: serde::Deserialize<'de>"
+    )
+)]
+pub struct CustomGameEntry<C: Clone> {
+    pub path: String,
+    pub tags: Vec<Tag>,
+    pub when: Vec<C>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged, bound(deserialize = "C: serde::Deserialize<'de>"))]
+enum RawCustomGameEntry<C> {
+    Simple(String),
+    Detailed {
+        path: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<Tag>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        when: Vec<C>,
+    },
+}
+
+impl<C: Clone> From<RawCustomGameEntry<C>> for CustomGameEntry<C> {
+    fn from(raw: RawCustomGameEntry<C>) -> Self {
+        match raw {
+            RawCustomGameEntry::Simple(path) => Self {
+                path,
+                tags: vec![],
+                when: vec![],
+            },
+            RawCustomGameEntry::Detailed { path, tags, when } => Self { path, tags, when },
+        }
+    }
+}
+
+impl<C: Clone> From<CustomGameEntry<C>> for RawCustomGameEntry<C> {
+    fn from(entry: CustomGameEntry<C>) -> Self {
+        if entry.tags.is_empty() && entry.when.is_empty() {
+            Self::Simple(entry.path)
+        } else {
+            Self::Detailed {
+                path: entry.path,
+                tags: entry.tags,
+                when: entry.when,
+            }
+        }
+    }
+}
+
+impl<C: Clone> CustomGameEntry<C> {
+    #[cfg(test)]
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            tags: vec![],
+            when: vec![],
+        }
+    }
+
+    pub fn has_tag(&self, tag: &Tag) -> bool {
+        self.tags.contains(tag)
+    }
+
+    pub fn set_tag(&mut self, tag: Tag, enabled: bool) {
+        self.tags.retain(|x| *x != tag);
+        if enabled {
+            self.tags.push(tag);
+        }
+    }
+}
+
+// The GUI only edits the first constraint, which covers most manifest entries.
+// Any further constraints from the config file are left as they are.
+
+impl CustomGameFile {
+    pub fn os(&self) -> Option<Os> {
+        self.when.first().and_then(|x| x.os.clone())
+    }
+
+    pub fn set_os(&mut self, os: Option<Os>) {
+        if self.when.is_empty() {
+            self.when.push(GameFileConstraint::default());
+        }
+        self.when[0].os = os;
+        if self.when[0] == GameFileConstraint::default() {
+            self.when.remove(0);
+        }
+    }
+
+    pub fn store(&self) -> Option<Store> {
+        self.when.first().and_then(|x| x.store)
+    }
+
+    pub fn set_store(&mut self, store: Option<Store>) {
+        if self.when.is_empty() {
+            self.when.push(GameFileConstraint::default());
+        }
+        self.when[0].store = store;
+        if self.when[0] == GameFileConstraint::default() {
+            self.when.remove(0);
+        }
+    }
+}
+
+impl CustomGameRegistry {
+    pub fn store(&self) -> Option<Store> {
+        self.when.first().and_then(|x| x.store)
+    }
+
+    pub fn set_store(&mut self, store: Option<Store>) {
+        if self.when.is_empty() {
+            self.when.push(GameRegistryConstraint::default());
+        }
+        self.when[0].store = store;
+        if self.when[0] == GameRegistryConstraint::default() {
+            self.when.remove(0);
+        }
+    }
 }
 
 impl Default for ManifestConfig {
@@ -549,6 +1228,7 @@ impl Default for ManifestConfig {
         Self {
             url: MANIFEST_URL.to_string(),
             etag: None,
+            secondary: vec![],
         }
     }
 }
@@ -567,6 +1247,7 @@ impl Default for BackupConfig {
             sort: Default::default(),
             retention: Retention::default(),
             format: Default::default(),
+            overrides: Default::default(),
         }
     }
 }
@@ -579,6 +1260,7 @@ impl Default for RestoreConfig {
             ignored_games: std::collections::HashSet::new(),
             recent_games: vec![],
             redirects: vec![],
+            toggled_paths: Default::default(),
             sort: Default::default(),
         }
     }
@@ -608,8 +1290,9 @@ impl ResourceFile for Config {
             .ignored_registry
             .retain(|x| !x.raw().trim().is_empty());
         for item in &mut self.custom_games {
-            item.files.retain(|x| !x.trim().is_empty());
-            item.registry.retain(|x| !x.trim().is_empty());
+            item.files.retain(|x| !x.path.trim().is_empty());
+            item.registry.retain(|x| !x.path.trim().is_empty());
+            item.install_dir.retain(|x| !x.trim().is_empty());
         }
         self.custom_games
             .retain(|x| !x.name.trim().is_empty() || !x.files.is_empty() || !x.registry.is_empty());
@@ -673,6 +1356,15 @@ impl Config {
                 "~/.var/app/com.heroicgameslauncher.hgl/config/heroic".to_string(),
                 Store::Heroic,
             ),
+            // Bottles:
+            ("~/.local/share/bottles".to_string(), Store::Bottles),
+            (
+                "~/.var/app/com.usebottles.bottles/data/bottles".to_string(),
+                Store::Bottles,
+            ),
+            // Lutris:
+            ("~/.local/share/lutris".to_string(), Store::Lutris),
+            ("~/.var/app/net.lutris.Lutris/data/lutris".to_string(), Store::Lutris),
             // Uplay:
             (format!("{}/Ubisoft/Ubisoft Game Launcher", pf32), Store::Uplay),
             (format!("{}/Ubisoft/Ubisoft Game Launcher", pf64), Store::Uplay),
@@ -693,6 +1385,13 @@ impl Config {
             ));
         }
 
+        // itch:
+        #[cfg(target_os = "linux")]
+        candidates.extend([
+            ("~/.config/itch".to_string(), Store::Itch),
+            ("~/.var/app/io.itch.itch/config/itch".to_string(), Store::Itch),
+        ]);
+
         let detected_steam = match steamlocate::SteamDir::locate() {
             Some(mut steam_dir) => steam_dir
                 .libraryfolders()
@@ -808,8 +1507,7 @@ impl Config {
         self.custom_games.push(CustomGame {
             name: "".to_string(),
             ignore: false,
-            files: vec![],
-            registry: vec![],
+            ..Default::default()
         });
     }
 
@@ -1101,7 +1799,7 @@ impl ToggledRegistry {
 mod tests {
     use super::*;
     use crate::testing::s;
-    use maplit::hashset;
+    use maplit::{btreemap, hashset};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -1126,6 +1824,7 @@ mod tests {
                 manifest: ManifestConfig {
                     url: s("example.com"),
                     etag: None,
+                    secondary: vec![],
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -1145,15 +1844,19 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    overrides: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
                     ignored_games: std::collections::HashSet::new(),
                     recent_games: Default::default(),
                     redirects: vec![],
+                    toggled_paths: Default::default(),
                     sort: Default::default(),
                 },
                 custom_games: vec![],
+                aliases: Default::default(),
+                hooks: Default::default(),
             },
             config,
         );
@@ -1185,6 +1888,12 @@ mod tests {
               merge: true
               filter:
                 excludeStoreScreenshots: true
+                games:
+                  Backup Game 3:
+                    ignoredPaths:
+                      - "*.dmp"
+                    maxFileSize: 1000
+                    maxTotalSize: 5000
             restore:
               path: ~/restore
               ignoredGames:
@@ -1202,6 +1911,8 @@ mod tests {
                   - Custom Registry 1
                   - Custom Registry 2
                   - Custom Registry 2
+            aliases:
+              Old Game Name: New Game Name
             "#,
         )
         .unwrap();
@@ -1211,6 +1922,7 @@ mod tests {
                 manifest: ManifestConfig {
                     url: s("example.com"),
                     etag: Some(s("foo")),
+                    secondary: vec![],
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -1239,6 +1951,13 @@ mod tests {
                     merge: true,
                     filter: BackupFilter {
                         exclude_store_screenshots: true,
+                        games: btreemap! {
+                            s("Backup Game 3") => GameFilter {
+                                ignored_paths: vec![StrictPath::new(s("*.dmp"))],
+                                max_file_size: Some(1000),
+                                max_total_size: Some(5000),
+                            },
+                        },
                         ..Default::default()
                     },
                     toggled_paths: Default::default(),
@@ -1246,6 +1965,7 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    overrides: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -1255,22 +1975,35 @@ mod tests {
                     },
                     recent_games: Default::default(),
                     redirects: vec![],
+                    toggled_paths: Default::default(),
                     sort: Default::default(),
                 },
                 custom_games: vec![
                     CustomGame {
                         name: s("Custom Game 1"),
                         ignore: false,
-                        files: vec![],
-                        registry: vec![],
+                        ..Default::default()
                     },
                     CustomGame {
                         name: s("Custom Game 2"),
                         ignore: false,
-                        files: vec![s("Custom File 1"), s("Custom File 2"), s("Custom File 2"),],
-                        registry: vec![s("Custom Registry 1"), s("Custom Registry 2"), s("Custom Registry 2"),],
+                        files: vec![
+                            CustomGameFile::new("Custom File 1"),
+                            CustomGameFile::new("Custom File 2"),
+                            CustomGameFile::new("Custom File 2"),
+                        ],
+                        registry: vec![
+                            CustomGameRegistry::new("Custom Registry 1"),
+                            CustomGameRegistry::new("Custom Registry 2"),
+                            CustomGameRegistry::new("Custom Registry 2"),
+                        ],
+                        ..Default::default()
                     },
                 ],
+                aliases: btreemap! {
+                    s("Old Game Name") => s("New Game Name"),
+                },
+                hooks: Default::default(),
             },
             config,
         );
@@ -1303,6 +2036,7 @@ mod tests {
                 manifest: ManifestConfig {
                     url: s("example.com"),
                     etag: None,
+                    secondary: vec![],
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -1325,15 +2059,19 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    overrides: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
                     ignored_games: std::collections::HashSet::new(),
                     recent_games: Default::default(),
                     redirects: vec![],
+                    toggled_paths: Default::default(),
                     sort: Default::default(),
                 },
                 custom_games: vec![],
+                aliases: Default::default(),
+                hooks: Default::default(),
             },
             config,
         );
@@ -1390,6 +2128,7 @@ mod tests {
                 manifest: ManifestConfig {
                     url: s("example.com"),
                     etag: Some(s("foo")),
+                    secondary: vec![],
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -1425,6 +2164,7 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    overrides: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -1434,27 +2174,125 @@ mod tests {
                     },
                     recent_games: Default::default(),
                     redirects: vec![],
+                    toggled_paths: Default::default(),
                     sort: Default::default(),
                 },
                 custom_games: vec![
                     CustomGame {
                         name: s("Custom Game 1"),
                         ignore: false,
-                        files: vec![],
-                        registry: vec![],
+                        ..Default::default()
                     },
                     CustomGame {
                         name: s("Custom Game 2"),
                         ignore: false,
-                        files: vec![s("Custom File 1"), s("Custom File 2"), s("Custom File 2"),],
-                        registry: vec![s("Custom Registry 1"), s("Custom Registry 2"), s("Custom Registry 2"),],
+                        files: vec![
+                            CustomGameFile::new("Custom File 1"),
+                            CustomGameFile::new("Custom File 2"),
+                            CustomGameFile::new("Custom File 2"),
+                        ],
+                        registry: vec![
+                            CustomGameRegistry::new("Custom Registry 1"),
+                            CustomGameRegistry::new("Custom Registry 2"),
+                            CustomGameRegistry::new("Custom Registry 2"),
+                        ],
+                        ..Default::default()
                     },
                 ],
+                aliases: Default::default(),
+                hooks: Default::default(),
             },
             config,
         );
     }
 
+    #[test]
+    fn can_parse_and_serialize_secondary_manifests() {
+        #[allow(deprecated)]
+        let manifest = ManifestConfig {
+            url: s("example.com"),
+            etag: None,
+            secondary: vec![
+                SecondaryManifestConfig::Local {
+                    path: StrictPath::new(s("~/manifest.yaml")),
+                },
+                SecondaryManifestConfig::Remote {
+                    url: s("example.com/secondary.yaml"),
+                },
+            ],
+        };
+        let serialized = r#"
+---
+url: example.com
+etag: ~
+secondary:
+  - path: ~/manifest.yaml
+  - url: example.com/secondary.yaml
+"#
+        .trim();
+
+        assert_eq!(serialized, serde_yaml::to_string(&manifest).unwrap().trim());
+        assert_eq!(manifest, serde_yaml::from_str::<ManifestConfig>(serialized).unwrap());
+    }
+
+    #[test]
+    fn can_parse_and_serialize_custom_game_with_all_fields() {
+        let game = CustomGame {
+            name: s("Custom Game"),
+            ignore: false,
+            integration: Integration::Extend,
+            files: vec![
+                CustomGameFile::new("<base>/saves"),
+                CustomGameFile {
+                    path: s("<home>/.config/game"),
+                    tags: vec![Tag::Config],
+                    when: vec![GameFileConstraint {
+                        os: Some(Os::Linux),
+                        store: None,
+                    }],
+                },
+            ],
+            registry: vec![CustomGameRegistry {
+                path: s("HKEY_CURRENT_USER/Software/Game"),
+                tags: vec![Tag::Save],
+                when: vec![GameRegistryConstraint {
+                    store: Some(Store::Steam),
+                }],
+            }],
+            install_dir: vec![s("Game Folder")],
+            steam: Some(SteamMetadata { id: Some(101) }),
+            gog: Some(GogMetadata { id: Some(102) }),
+        };
+        let serialized = r#"
+---
+name: Custom Game
+integration: extend
+files:
+  - "<base>/saves"
+  - path: "<home>/.config/game"
+    tags:
+      - config
+    when:
+      - os: linux
+registry:
+  - path: HKEY_CURRENT_USER/Software/Game
+    tags:
+      - save
+    when:
+      - store: steam
+installDir:
+  - Game Folder
+steam:
+  id: 101
+gog:
+  id: 102
+"#
+        .trim();
+
+        assert_eq!(serialized, serde_yaml::to_string(&game).unwrap().trim());
+        assert_eq!(game, serde_yaml::from_str::<CustomGame>(serialized).unwrap());
+    }
+
     #[test]
     #[allow(deprecated)]
     fn can_be_serialized() {
@@ -1511,6 +2349,7 @@ restore:
     - Restore Game 1
     - Restore Game 2
     - Restore Game 3
+  toggledPaths: {}
   sort:
     key: name
     reversed: false
@@ -1533,6 +2372,7 @@ customGames:
                 manifest: ManifestConfig {
                     url: s("example.com"),
                     etag: Some(s("foo")),
+                    secondary: vec![],
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -1569,6 +2409,7 @@ customGames:
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    overrides: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -1579,22 +2420,33 @@ customGames:
                     },
                     recent_games: Default::default(),
                     redirects: vec![],
+                    toggled_paths: Default::default(),
                     sort: Default::default(),
                 },
                 custom_games: vec![
                     CustomGame {
                         name: s("Custom Game 1"),
                         ignore: false,
-                        files: vec![],
-                        registry: vec![],
+                        ..Default::default()
                     },
                     CustomGame {
                         name: s("Custom Game 2"),
                         ignore: false,
-                        files: vec![s("Custom File 1"), s("Custom File 2"), s("Custom File 2"),],
-                        registry: vec![s("Custom Registry 1"), s("Custom Registry 2"), s("Custom Registry 2"),],
+                        files: vec![
+                            CustomGameFile::new("Custom File 1"),
+                            CustomGameFile::new("Custom File 2"),
+                            CustomGameFile::new("Custom File 2"),
+                        ],
+                        registry: vec![
+                            CustomGameRegistry::new("Custom Registry 1"),
+                            CustomGameRegistry::new("Custom Registry 2"),
+                            CustomGameRegistry::new("Custom Registry 2"),
+                        ],
+                        ..Default::default()
                     },
                 ],
+                aliases: Default::default(),
+                hooks: Default::default(),
             })
             .unwrap()
             .trim(),
@@ -2015,4 +2867,312 @@ customGames:
             );
         }
     }
+
+    mod backup_filter {
+        use super::*;
+        use crate::testing::repo;
+        use pretty_assertions::assert_eq;
+
+        fn path(path: &str) -> StrictPath {
+            StrictPath::new(format!("{}/{}", repo(), path))
+        }
+
+        fn filter(ignored_paths: Vec<StrictPath>) -> CompiledFilter {
+            BackupFilter {
+                ignored_paths,
+                ..Default::default()
+            }
+            .compile()
+        }
+
+        #[test]
+        fn can_ignore_plain_paths_and_their_children() {
+            let filter = filter(vec![path("game/cache")]);
+            assert!(filter.is_path_ignored(&path("game/cache")));
+            assert!(filter.is_path_ignored(&path("game/cache/file.dat")));
+            assert!(!filter.is_path_ignored(&path("game/cache2/file.dat")));
+            assert!(!filter.is_path_ignored(&path("game/save.dat")));
+        }
+
+        #[test]
+        fn can_ignore_relative_globs_anywhere() {
+            let filter = filter(vec![StrictPath::new(s("*.log")), StrictPath::new(s("**/shadercache"))]);
+            assert!(filter.is_path_ignored(&path("game/debug.log")));
+            assert!(filter.is_path_ignored(&path("game/logs/nested/debug.log")));
+            assert!(filter.is_path_ignored(&path("game/shadercache/abc/def.bin")));
+            assert!(filter.is_path_ignored(&path("other/shadercache")));
+            assert!(!filter.is_path_ignored(&path("game/debug.log.bak")));
+            assert!(!filter.is_path_ignored(&path("game/shadercache2/def.bin")));
+        }
+
+        #[test]
+        fn can_ignore_absolute_globs() {
+            let filter = filter(vec![path("games/*/cache")]);
+            assert!(filter.is_path_ignored(&path("games/foo/cache/file.dat")));
+            assert!(!filter.is_path_ignored(&path("games/foo/bar/cache/file.dat")));
+            assert!(!filter.is_path_ignored(&path("other/foo/cache/file.dat")));
+        }
+
+        #[test]
+        fn can_ignore_regex() {
+            let filter = filter(vec![StrictPath::new(s(r#"regex:/crash-\d+\.dmp$"#))]);
+            assert!(filter.is_path_ignored(&path("game/crash-123.dmp")));
+            assert!(!filter.is_path_ignored(&path("game/crash-abc.dmp")));
+        }
+
+        #[test]
+        fn reports_which_entry_excluded_a_path() {
+            let filter = filter(vec![path("game/cache"), StrictPath::new(s("*.log"))]);
+            assert_eq!(
+                Some(BackupExclusion::IgnoredPath(StrictPath::new(s("*.log")))),
+                filter.path_exclusion(&path("game/debug.log")),
+            );
+            assert_eq!(None, filter.path_exclusion(&path("game/save.dat")));
+        }
+
+        #[test]
+        fn can_exclude_files_by_game_rules() {
+            let rules = GameFilter {
+                ignored_paths: vec![StrictPath::new(s("*.dmp")), StrictPath::new(s("**/replays"))],
+                max_file_size: Some(100),
+                max_total_size: None,
+            };
+            assert!(GameFilter::default().is_empty());
+            assert!(!rules.is_empty());
+
+            let filter = BackupFilter {
+                games: btreemap! { s("game") => rules },
+                ..Default::default()
+            }
+            .compile();
+            assert_eq!(
+                Some(BackupExclusion::GameIgnoredPath(StrictPath::new(s("*.dmp")))),
+                filter.game_file_exclusion("game", &path("game/crash.dmp"), 1),
+            );
+            assert_eq!(
+                Some(BackupExclusion::GameIgnoredPath(StrictPath::new(s("**/replays")))),
+                filter.game_file_exclusion("game", &path("game/replays/1.rep"), 1),
+            );
+            assert_eq!(
+                Some(BackupExclusion::MaxFileSize(100)),
+                filter.game_file_exclusion("game", &path("game/save.dat"), 101),
+            );
+            assert_eq!(None, filter.game_file_exclusion("game", &path("game/save.dat"), 100));
+            assert_eq!(None, filter.game_file_exclusion("other", &path("game/crash.dmp"), 1));
+        }
+
+        #[test]
+        fn can_ignore_registry_patterns() {
+            let filter = BackupFilter {
+                ignored_registry: vec![
+                    RegistryItem::new(s("HKEY_CURRENT_USER/Software/Plain")),
+                    RegistryItem::new(s("HKEY_CURRENT_USER/Software/Glob*")),
+                    RegistryItem::new(s("Telemetry*")),
+                    RegistryItem::new(s("regex:/Temp\\d$")),
+                ],
+                ..Default::default()
+            }
+            .compile();
+            assert!(filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/Software/Plain/Sub"))));
+            assert!(filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/Software/GlobGame/Sub"))));
+            assert!(filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/software/globgame"))));
+            assert!(filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/Software/Game/Telemetry"))));
+            assert!(filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/Software/Game/Temp1"))));
+            assert!(!filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/Software/Game/Temp"))));
+            assert!(!filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/Software/Game"))));
+        }
+
+        #[test]
+        fn can_ignore_registry_regex_with_different_casing() {
+            let filter = BackupFilter {
+                ignored_registry: vec![RegistryItem::new(s("regex:Software/Game/Temp\\d$"))],
+                ..Default::default()
+            }
+            .compile();
+            assert!(filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/SOFTWARE/game/temp1"))));
+            assert!(!filter.is_registry_ignored(&RegistryItem::new(s("HKEY_CURRENT_USER/SOFTWARE/game/temp"))));
+        }
+
+        #[test]
+        fn can_validate_patterns() {
+            assert!(is_valid_filter_pattern("~/some/path"));
+            assert!(is_valid_filter_pattern("**/*.log"));
+            assert!(is_valid_filter_pattern("regex:\\.log$"));
+            assert!(!is_valid_filter_pattern("***"));
+            assert!(!is_valid_filter_pattern("regex:(unclosed"));
+        }
+    }
+
+    mod backup_overrides {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn can_override_format_and_retention_per_game() {
+            let config = Config::load_from_string(
+                r#"
+                manifest:
+                  url: example.com
+                  etag: null
+                roots: []
+                backup:
+                  path: ~/backup
+                  overrides:
+                    Big Game:
+                      retention:
+                        full: 2
+                        differential: 0
+                      format:
+                        chosen: zip
+                        zip:
+                          compression: zstd
+                    Small Game:
+                      retention:
+                        full: 1
+                        differential: 20
+                restore:
+                  path: ~/restore
+                "#,
+            )
+            .unwrap();
+
+            assert_eq!(BackupFormat::Zip, config.backup.format_for("Big Game").chosen);
+            assert_eq!(
+                ZipCompression::Zstd,
+                config.backup.format_for("Big Game").zip.compression
+            );
+            assert_eq!(config.backup.format, config.backup.format_for("Small Game"));
+            assert_eq!(config.backup.format, config.backup.format_for("Other Game"));
+            assert_eq!(
+                btreemap! {
+                    s("Big Game") => Retention { full: 2, differential: 0 },
+                    s("Small Game") => Retention { full: 1, differential: 20 },
+                },
+                config.backup.retention_overrides()
+            );
+        }
+
+        #[test]
+        fn merges_partial_overrides_onto_global_settings() {
+            let config = Config::load_from_string(
+                r#"
+                manifest:
+                  url: example.com
+                  etag: null
+                roots: []
+                backup:
+                  path: ~/backup
+                  retention:
+                    full: 3
+                    differential: 5
+                  format:
+                    chosen: zip
+                    zip:
+                      compression: deflate
+                    compression:
+                      deflate:
+                        level: 6
+                      bzip2:
+                        level: 6
+                      zstd:
+                        level: 4
+                  overrides:
+                    Big Game:
+                      retention:
+                        differential: 1
+                      format:
+                        zip:
+                          compression: zstd
+                restore:
+                  path: ~/restore
+                "#,
+            )
+            .unwrap();
+
+            let format = config.backup.format_for("Big Game");
+            assert_eq!(BackupFormat::Zip, format.chosen);
+            assert_eq!(ZipCompression::Zstd, format.zip.compression);
+            assert_eq!(Some(4), format.level());
+            assert_eq!(
+                Retention {
+                    full: 3,
+                    differential: 1
+                },
+                config.backup.retention_for("Big Game")
+            );
+        }
+
+        #[test]
+        fn clamps_overridden_level_to_the_compression_range() {
+            let mut config = Config::default();
+            config.backup.format.chosen = BackupFormat::Zip;
+            config.backup.overrides.insert(
+                s("Big Game"),
+                GameBackupOverrides {
+                    format: BackupFormatOverrides {
+                        level: Some(100),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            );
+
+            assert_eq!(Some(9), config.backup.format_for("Big Game").level());
+        }
+    }
+
+    mod hooks {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn can_parse_global_and_game_hooks() {
+            let config = Config::load_from_string(
+                r#"
+                manifest:
+                  url: example.com
+                  etag: null
+                roots: []
+                backup:
+                  path: ~/backup
+                restore:
+                  path: ~/restore
+                hooks:
+                  onFailure: error
+                  backup:
+                    before:
+                      - systemctl --user stop syncthing
+                    after:
+                      - rsync -a "$LUDUSAVI_BACKUP_DIR" remote:backups
+                  games:
+                    Some Game:
+                      restore:
+                        before:
+                          - pkill some-game
+                "#,
+            )
+            .unwrap();
+
+            assert_eq!(
+                HooksConfig {
+                    on_failure: HookFailureMode::Error,
+                    backup: OperationHooks {
+                        before: vec![s("systemctl --user stop syncthing")],
+                        after: vec![s(r#"rsync -a "$LUDUSAVI_BACKUP_DIR" remote:backups"#)],
+                    },
+                    restore: OperationHooks::default(),
+                    games: btreemap! {
+                        s("Some Game") => GameHooks {
+                            backup: OperationHooks::default(),
+                            restore: OperationHooks {
+                                before: vec![s("pkill some-game")],
+                                after: vec![],
+                            },
+                        },
+                    },
+                },
+                config.hooks
+            );
+        }
+    }
 }
@@ -17,6 +17,7 @@ pub mod redirect_editor;
 pub mod restore_screen;
 pub mod root_editor;
 pub mod search;
+pub mod secondary_manifest_editor;
 pub mod style;
 pub mod undoable;
 pub mod widget;
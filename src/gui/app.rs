@@ -1,6 +1,9 @@
 use crate::{
     cache::Cache,
-    config::{Config, CustomGame, RootsConfig},
+    config::{
+        BackupFormatOverrides, Config, CustomGame, CustomGameFile, CustomGameRegistry, RetentionOverrides, RootsConfig,
+        SecondaryManifestConfig, ToggledRegistry,
+    },
     gui::{
         backup_screen::BackupScreenComponent,
         common::*,
@@ -12,15 +15,17 @@ use crate::{
         redirect_editor::RedirectEditorRow,
         restore_screen::RestoreScreenComponent,
         root_editor::RootEditorRow,
+        secondary_manifest_editor::SecondaryManifestEditorRow,
         style,
     },
-    heroic::HeroicGames,
+    hooks::{HookFailure, HookOperation, HookRunner},
     lang::Translator,
+    launchers::Launchers,
     layout::BackupLayout,
-    manifest::{Manifest, Store},
+    manifest::{GogMetadata, Manifest, SteamMetadata, Store},
     prelude::{
-        app_dir, back_up_game, prepare_backup_target, scan_game_for_backup, scan_game_for_restoration, BackupId, Error,
-        InstallDirRanking, OperationStepDecision, SteamShortcuts, StrictPath, TitleFinder,
+        app_dir, back_up_game, parse_as_of, prepare_backup_target, scan_game_for_backup, scan_game_for_restoration,
+        BackupId, Error, InstallDirRanking, OperationStepDecision, SteamShortcuts, StrictPath, TitleFinder,
     },
     registry_compat::RegistryItem,
     serialization::{ResourceFile, SaveableResourceFile},
@@ -47,6 +52,23 @@ fn make_nav_button<'a>(text: String, screen: Screen, current_screen: Screen) ->
     })
 }
 
+/// Backed up games that were renamed in the manifest and whose backups can be moved to the new name.
+fn find_renamed_games(
+    config: &Config,
+    cache: &Cache,
+    manifest: &Manifest,
+) -> std::collections::BTreeMap<String, String> {
+    let aliases = cache.game_aliases(config);
+    if aliases.is_empty() {
+        return Default::default();
+    }
+
+    let mut manifest = manifest.clone();
+    manifest.load_custom_games(config);
+    let layout = BackupLayout::new(config.restore.path.clone(), config.backup.retention.clone());
+    layout.find_renamed_games(&manifest, &aliases)
+}
+
 #[derive(Default)]
 struct Progress {
     pub max: f32,
@@ -68,10 +90,14 @@ pub struct App {
     custom_games_screen: CustomGamesScreenComponent,
     other_screen: OtherScreenComponent,
     operation_should_cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Hook failures during the ongoing operation, collected from every step.
+    operation_hook_failures: std::sync::Arc<std::sync::Mutex<Vec<HookFailure>>>,
     operation_steps: Vec<Command<Message>>,
     operation_steps_active: usize,
     progress: Progress,
     backups_to_restore: std::collections::HashMap<String, BackupId>,
+    /// Games that had no backup yet at the requested point in time.
+    restore_games_without_backup: Vec<String>,
     updating_manifest: bool,
     notify_on_single_game_scanned: Option<(String, Screen)>,
     timed_notification: Option<Notification>,
@@ -95,6 +121,87 @@ impl App {
         self.modal_theme = Some(ModalTheme::Error { variant: error });
     }
 
+    fn hook_runner(&self, operation: HookOperation) -> HookRunner {
+        let path = match operation {
+            HookOperation::Backup => &self.config.backup.path,
+            HookOperation::Restore => &self.config.restore.path,
+        };
+        HookRunner::new(&self.config.hooks, operation, path)
+    }
+
+    /// Run the global `before` hooks in the background, and then start the queued steps.
+    fn start_operation_steps(&mut self, preview: bool, full: bool, operation: HookOperation) -> Command<Message> {
+        if preview {
+            self.operation_steps_active = 100.min(self.operation_steps.len());
+            return Command::batch(self.operation_steps.drain(..self.operation_steps_active));
+        }
+
+        let hooks = self.hook_runner(operation);
+        Command::perform(async move { hooks.before_all() }, move |failures| {
+            Message::OperationHooksStarted {
+                operation,
+                full,
+                failures,
+            }
+        })
+    }
+
+    /// Run the global `after` hooks in the background, and then report the operation.
+    fn complete_operation(&mut self, operation: HookOperation, ran: bool, failed: bool) -> Command<Message> {
+        if !ran {
+            self.finish_operation(operation, failed, vec![]);
+            return Command::none();
+        }
+
+        let hooks = self.hook_runner(operation);
+        Command::perform(async move { hooks.after_all(!failed) }, move |failures| {
+            Message::OperationHooksCompleted {
+                operation,
+                failed,
+                failures,
+            }
+        })
+    }
+
+    fn finish_operation(&mut self, operation: HookOperation, failed: bool, after_failures: Vec<HookFailure>) {
+        let hooks = self.hook_runner(operation);
+        let mut failures = std::mem::take(&mut *self.operation_hook_failures.lock().unwrap());
+        failures.extend(after_failures);
+
+        // In error mode, a failed hook makes the whole operation fail.
+        // Otherwise, any failures are shown as a notification.
+        let hook_error = match failures.first() {
+            Some(first) if hooks.blocks(&failures) => Some(Error::HookFailed {
+                command: first.command.clone(),
+                why: first.reason.clone(),
+            }),
+            Some(_) => {
+                let msg = self.translator.notify_hook_failures(&failures);
+                self.timed_notification = Some(Notification::new(msg).expires(10));
+                None
+            }
+            None => None,
+        };
+
+        self.go_idle();
+
+        if operation == HookOperation::Restore && !self.restore_games_without_backup.is_empty() {
+            self.restore_games_without_backup.sort();
+            let msg = self
+                .translator
+                .notify_games_without_backup(&self.restore_games_without_backup);
+            self.timed_notification = Some(Notification::new(msg).expires(10));
+        }
+
+        if let Some(error) = hook_error {
+            self.show_error(error);
+        } else if failed {
+            self.modal_theme = Some(ModalTheme::Error {
+                variant: Error::SomeEntriesFailed,
+            });
+        }
+    }
+
     fn confirm_backup_start(&mut self, games: Option<Vec<String>>) -> Command<Message> {
         self.modal_theme = Some(ModalTheme::ConfirmBackup { games });
         Command::none()
@@ -172,22 +279,17 @@ impl App {
         let full = games.is_none();
 
         let mut all_games = self.manifest.clone();
-        for custom_game in &self.config.custom_games {
-            if custom_game.ignore {
-                continue;
-            }
-            all_games.add_custom_game(custom_game.clone());
-        }
+        all_games.load_custom_games(&self.config);
 
         if preview && full {
             self.backup_screen.previewed_games.clear();
         }
 
-        let layout = std::sync::Arc::new(BackupLayout::new(
-            self.config.backup.path.clone(),
-            self.config.backup.retention.clone(),
-        ));
-        let title_finder = TitleFinder::new(&all_games, &layout);
+        let layout = std::sync::Arc::new(
+            BackupLayout::new(self.config.backup.path.clone(), self.config.backup.retention.clone())
+                .with_game_retention(self.config.backup.retention_overrides()),
+        );
+        let title_finder = TitleFinder::new(&all_games, &layout).with_aliases(&self.cache.game_aliases(&self.config));
 
         if let Some(games) = &games {
             all_games.0.retain(|k, _| games.contains(k));
@@ -237,22 +339,25 @@ impl App {
 
         let config = std::sync::Arc::new(self.config.clone());
         let roots = std::sync::Arc::new(config.expanded_roots());
-        let heroic_games = std::sync::Arc::new(HeroicGames::scan(&roots, &title_finder, None));
-        let filter = std::sync::Arc::new(self.config.backup.filter.clone());
+        let launchers = std::sync::Arc::new(Launchers::scan(&roots, &title_finder, None));
+        let filter = std::sync::Arc::new(self.config.backup.filter.compile());
         let ranking = std::sync::Arc::new(InstallDirRanking::scan(&roots, &all_games, &subjects));
         let steam_shortcuts = std::sync::Arc::new(SteamShortcuts::scan());
+        let hooks = std::sync::Arc::new(self.hook_runner(HookOperation::Backup));
 
         for key in subjects {
             let game = all_games.0[&key].clone();
             let config = config.clone();
             let roots = roots.clone();
-            let heroic_games = heroic_games.clone();
+            let launchers = launchers.clone();
             let layout = layout.clone();
             let filter = filter.clone();
             let ranking = ranking.clone();
             let steam_shortcuts = steam_shortcuts.clone();
+            let hooks = hooks.clone();
             let steam_id = game.steam.as_ref().and_then(|x| x.id);
             let cancel_flag = self.operation_should_cancel.clone();
+            let hook_failures = self.operation_hook_failures.clone();
             let merge = self.config.backup.merge;
             self.operation_steps.push(Command::perform(
                 async move {
@@ -265,38 +370,50 @@ impl App {
                         return (None, None, OperationStepDecision::Cancelled);
                     }
 
-                    let previous = layout.latest_backup(&key, false, &config.redirects);
-
-                    let scan_info = scan_game_for_backup(
-                        &game,
-                        &key,
-                        &roots,
-                        &StrictPath::from_std_path_buf(&app_dir()),
-                        &heroic_games,
-                        &steam_id,
-                        &filter,
-                        &None,
-                        &ranking,
-                        &config.backup.toggled_paths,
-                        &config.backup.toggled_registry,
-                        previous,
-                        &config.redirects,
-                        &steam_shortcuts,
-                    );
+                    let scan = || {
+                        scan_game_for_backup(
+                            &game,
+                            &key,
+                            &roots,
+                            &StrictPath::from_std_path_buf(&app_dir()),
+                            &launchers,
+                            &steam_id,
+                            &filter,
+                            &None,
+                            &ranking,
+                            &config.backup.toggled_paths,
+                            &config.backup.toggled_registry,
+                            layout.latest_backup(&key, false, &config.redirects),
+                            &config.redirects,
+                            &steam_shortcuts,
+                        )
+                    };
+                    let scan_info = scan();
                     if !config.is_game_enabled_for_backup(&key) {
                         return (Some(scan_info), None, OperationStepDecision::Ignored);
                     }
 
-                    let backup_info = if !preview {
-                        Some(back_up_game(
-                            &scan_info,
-                            layout.game_layout(&key),
-                            merge,
-                            &chrono::Utc::now(),
-                            &config.backup.format,
-                        ))
+                    let (scan_info, backup_info) = if !preview {
+                        let (scan_info, backup_info, failures) = hooks.around_game(
+                            scan_info,
+                            None,
+                            |_| scan(),
+                            |scan_info| {
+                                back_up_game(
+                                    scan_info,
+                                    layout.game_layout(&key),
+                                    merge,
+                                    &chrono::Utc::now(),
+                                    &config.backup.format_for(&key),
+                                    &|_, _| {},
+                                )
+                            },
+                            || layout.latest_backup_name(&key),
+                        );
+                        hook_failures.lock().unwrap().extend(failures);
+                        (scan_info, Some(backup_info))
                     } else {
-                        None
+                        (scan_info, None)
                     };
                     (Some(scan_info), backup_info, OperationStepDecision::Processed)
                 },
@@ -310,8 +427,7 @@ impl App {
             ));
         }
 
-        self.operation_steps_active = 100.min(self.operation_steps.len());
-        Command::batch(self.operation_steps.drain(..self.operation_steps_active))
+        self.start_operation_steps(preview, full, HookOperation::Backup)
     }
 
     fn start_restore(&mut self, preview: bool, games: Option<Vec<String>>) -> Command<Message> {
@@ -374,12 +490,23 @@ impl App {
         log::info!("beginning restore with {} steps", self.progress.max);
 
         self.register_notify_on_single_game_scanned(&games);
+        self.restore_games_without_backup.clear();
+
+        let as_of = parse_as_of(&self.restore_screen.as_of).map(BackupId::AsOf);
+        let hooks = std::sync::Arc::new(self.hook_runner(HookOperation::Restore));
 
         for name in restorables {
             let config = config.clone();
             let layout = layout.clone();
+            let hooks = hooks.clone();
             let cancel_flag = self.operation_should_cancel.clone();
-            let backup_id = self.backups_to_restore.get(&name).cloned().unwrap_or(BackupId::Latest);
+            let hook_failures = self.operation_hook_failures.clone();
+            let backup_id = self
+                .backups_to_restore
+                .get(&name)
+                .cloned()
+                .or_else(|| as_of.clone())
+                .unwrap_or(BackupId::Latest);
             self.operation_steps.push(Command::perform(
                 async move {
                     let mut layout = layout.game_layout(&name);
@@ -390,15 +517,26 @@ impl App {
                         return (None, None, OperationStepDecision::Cancelled, layout);
                     }
 
-                    let scan_info = scan_game_for_restoration(&name, &backup_id, &mut layout, &config.redirects);
+                    let mut scan_info = scan_game_for_restoration(&name, &backup_id, &mut layout, &config.redirects);
+                    scan_info.update_ignored(&config.restore.toggled_paths, &ToggledRegistry::default());
                     if !config.is_game_enabled_for_restore(&name) {
                         return (Some(scan_info), None, OperationStepDecision::Ignored, layout);
                     }
 
-                    let backup_info = if scan_info.backup.is_some() && !preview {
-                        Some(layout.restore(&scan_info))
-                    } else {
-                        None
+                    let backup_name = scan_info.backup.as_ref().map(|x| x.name().to_string());
+                    let (scan_info, backup_info) = match backup_name {
+                        Some(backup_name) if !preview => {
+                            let (scan_info, backup_info, failures) = hooks.around_game(
+                                scan_info,
+                                Some(&backup_name),
+                                |scan_info| scan_info,
+                                |scan_info| layout.restore(scan_info, &|_, _| {}),
+                                || Some(backup_name.clone()),
+                            );
+                            hook_failures.lock().unwrap().extend(failures);
+                            (scan_info, Some(backup_info))
+                        }
+                        _ => (scan_info, None),
                     };
                     (Some(scan_info), backup_info, OperationStepDecision::Processed, layout)
                 },
@@ -412,11 +550,10 @@ impl App {
             ));
         }
 
-        self.operation_steps_active = 100.min(self.operation_steps.len());
-        Command::batch(self.operation_steps.drain(..self.operation_steps_active))
+        self.start_operation_steps(preview, full, HookOperation::Restore)
     }
 
-    fn complete_backup(&mut self, preview: bool, full: bool) {
+    fn complete_backup(&mut self, preview: bool, full: bool) -> Command<Message> {
         log::info!("completed backup");
         let mut failed = false;
 
@@ -441,16 +578,10 @@ impl App {
 
         self.cache.save();
 
-        self.go_idle();
-
-        if failed {
-            self.modal_theme = Some(ModalTheme::Error {
-                variant: Error::SomeEntriesFailed,
-            });
-        }
+        self.complete_operation(HookOperation::Backup, !preview, failed)
     }
 
-    fn complete_restore(&mut self, full: bool) {
+    fn complete_restore(&mut self, full: bool) -> Command<Message> {
         log::info!("completed restore");
         let mut failed = false;
 
@@ -474,13 +605,11 @@ impl App {
 
         self.cache.save();
 
-        self.go_idle();
-
-        if failed {
-            self.modal_theme = Some(ModalTheme::Error {
-                variant: Error::SomeEntriesFailed,
-            });
-        }
+        let ran = matches!(
+            self.operation,
+            Some(OngoingOperation::Restore | OngoingOperation::CancelRestore)
+        );
+        self.complete_operation(HookOperation::Restore, ran, failed)
     }
 
     fn customize_game(&mut self, name: String) -> Command<Message> {
@@ -488,24 +617,50 @@ impl App {
             CustomGame {
                 name: name.clone(),
                 ignore: false,
-                files: standard.files.clone().unwrap_or_default().keys().cloned().collect(),
-                registry: standard.registry.clone().unwrap_or_default().keys().cloned().collect(),
+                integration: Default::default(),
+                files: standard
+                    .files
+                    .clone()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(path, entry)| CustomGameFile {
+                        path,
+                        tags: entry.tags.unwrap_or_default(),
+                        when: entry.when.unwrap_or_default(),
+                    })
+                    .collect(),
+                registry: standard
+                    .registry
+                    .clone()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(path, entry)| CustomGameRegistry {
+                        path,
+                        tags: entry.tags.unwrap_or_default(),
+                        when: entry.when.unwrap_or_default(),
+                    })
+                    .collect(),
+                install_dir: standard.install_dir.clone().unwrap_or_default().into_keys().collect(),
+                steam: standard.steam.clone(),
+                gog: standard.gog.clone(),
             }
         } else {
             CustomGame {
                 name: name.clone(),
                 ignore: false,
-                files: vec![],
-                registry: vec![],
+                ..Default::default()
             }
         };
 
         let mut gui_entry = CustomGamesEditorEntry::new(&name);
         for item in game.files.iter() {
-            gui_entry.files.push(CustomGamesEditorEntryRow::new(item));
+            gui_entry.files.push(CustomGamesEditorEntryRow::new(&item.path));
         }
         for item in game.registry.iter() {
-            gui_entry.registry.push(CustomGamesEditorEntryRow::new(item));
+            gui_entry.registry.push(CustomGamesEditorEntryRow::new(&item.path));
+        }
+        for item in game.install_dir.iter() {
+            gui_entry.install_dir.push(CustomGamesEditorEntryRow::new(item));
         }
         self.custom_games_screen.games_editor.entries.push(gui_entry);
 
@@ -529,6 +684,23 @@ impl App {
         Command::none()
     }
 
+    fn toggle_game_filter_editor(&mut self, name: String) -> Command<Message> {
+        self.backup_screen
+            .log
+            .toggle_game_filter_editor(&name, &self.config, &self.translator);
+        Command::none()
+    }
+
+    fn toggle_game_settings_editor(&mut self, name: String) -> Command<Message> {
+        self.backup_screen.log.toggle_game_settings_editor(&name);
+        Command::none()
+    }
+
+    fn toggle_backup_diff(&mut self, name: String) -> Command<Message> {
+        self.restore_screen.log.toggle_backup_diff(&name);
+        Command::none()
+    }
+
     fn switch_screen(&mut self, screen: Screen) -> Command<Message> {
         self.screen = screen;
         let subject = ScrollSubject::from(screen);
@@ -560,7 +732,7 @@ impl Application for App {
         };
         translator.set_language(config.language);
         let mut cache = Cache::load().unwrap_or_default().migrate_config(&mut config);
-        let manifest = match Manifest::load() {
+        let manifest = match Manifest::load_with_secondary(&config) {
             Ok(y) => y,
             Err(_) => {
                 modal_theme = Some(ModalTheme::UpdatingManifest);
@@ -580,6 +752,13 @@ impl Application for App {
             modal_theme = Some(ModalTheme::ConfirmAddMissingRoots(missing));
         }
 
+        if modal_theme.is_none() {
+            let renamed = find_renamed_games(&config, &cache, &manifest);
+            if !renamed.is_empty() {
+                modal_theme = Some(ModalTheme::ConfirmMigrateRenamedGames(renamed));
+            }
+        }
+
         let manifest_config = config.manifest.clone();
         let manifest_cache = cache.manifests.clone();
 
@@ -655,9 +834,9 @@ impl Application for App {
             Message::ManifestUpdated(updated) => {
                 self.updating_manifest = false;
 
-                let updated = match updated {
-                    Ok(Some(updated)) => updated,
-                    Ok(None) => return Command::none(),
+                let updates = match updated {
+                    Ok(updates) if updates.is_empty() => return Command::none(),
+                    Ok(updates) => updates,
                     Err(e) => {
                         self.show_error(e);
                         return Command::none();
@@ -668,10 +847,41 @@ impl Application for App {
                     self.modal_theme = None;
                 }
 
-                self.cache.update_manifest(updated);
+                let changed: Vec<_> = updates
+                    .iter()
+                    .filter(|x| x.diff.as_ref().map(|diff| !diff.is_empty()).unwrap_or(false))
+                    .cloned()
+                    .collect();
+                for update in updates {
+                    self.cache.update_manifest(update);
+                }
                 self.cache.save();
 
-                match Manifest::load() {
+                if !changed.is_empty() {
+                    let summary = changed
+                        .iter()
+                        .filter_map(|x| x.diff.as_ref())
+                        .map(|diff| self.translator.manifest_update_summary(diff))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    self.timed_notification = Some(Notification::new(summary).expires(10));
+
+                    let layout =
+                        BackupLayout::new(self.config.restore.path.clone(), self.config.backup.retention.clone());
+                    let backed_up: std::collections::BTreeSet<_> = layout.restorable_games().into_iter().collect();
+                    let affects_backups = changed
+                        .iter()
+                        .filter_map(|x| x.diff.as_ref())
+                        .any(|diff| diff.affected_games().iter().any(|x| backed_up.contains(x)));
+                    if affects_backups && self.modal_theme.is_none() {
+                        self.modal_theme = Some(ModalTheme::ManifestUpdated {
+                            updates: changed,
+                            backed_up,
+                        });
+                    }
+                }
+
+                match Manifest::load_with_secondary(&self.config) {
                     Ok(x) => {
                         self.manifest = x;
                     }
@@ -679,6 +889,13 @@ impl Application for App {
                         self.modal_theme = Some(ModalTheme::Error { variant });
                     }
                 }
+
+                if self.modal_theme.is_none() {
+                    let renamed = find_renamed_games(&self.config, &self.cache, &self.manifest);
+                    if !renamed.is_empty() {
+                        self.modal_theme = Some(ModalTheme::ConfirmMigrateRenamedGames(renamed));
+                    }
+                }
                 Command::none()
             }
             Message::ConfirmBackupStart { games } => self.confirm_backup_start(games),
@@ -763,7 +980,7 @@ impl Application for App {
                     None => {
                         self.operation_steps_active -= 1;
                         if self.operation_steps_active == 0 {
-                            self.complete_backup(preview, full);
+                            return self.complete_backup(preview, full);
                         }
                         Command::none()
                     }
@@ -785,6 +1002,10 @@ impl Application for App {
                         self.progress.max,
                         scan_info.game_name
                     );
+                    if scan_info.backup.is_none() && !scan_info.available_backups.is_empty() {
+                        // This only happens when restoring as of a time before the first backup.
+                        self.restore_games_without_backup.push(scan_info.game_name.clone());
+                    }
                     if scan_info.found_anything() {
                         let duplicates = self.restore_screen.duplicate_detector.add_game(&scan_info);
                         self.restore_screen.log.update_game(
@@ -820,12 +1041,46 @@ impl Application for App {
                     None => {
                         self.operation_steps_active -= 1;
                         if self.operation_steps_active == 0 {
-                            self.complete_restore(full);
+                            return self.complete_restore(full);
                         }
                         Command::none()
                     }
                 }
             }
+            Message::OperationHooksStarted {
+                operation,
+                full,
+                failures,
+            } => {
+                let hooks = self.hook_runner(operation);
+                if hooks.blocks(&failures) {
+                    self.go_idle();
+                    self.show_error(Error::HookFailed {
+                        command: failures[0].command.clone(),
+                        why: failures[0].reason.clone(),
+                    });
+                    return Command::none();
+                }
+                *self.operation_hook_failures.lock().unwrap() = failures;
+
+                if self.operation_steps.is_empty() {
+                    // The operation was cancelled while the hooks were running.
+                    return match operation {
+                        HookOperation::Backup => self.complete_backup(false, full),
+                        HookOperation::Restore => self.complete_restore(full),
+                    };
+                }
+                self.operation_steps_active = 100.min(self.operation_steps.len());
+                Command::batch(self.operation_steps.drain(..self.operation_steps_active))
+            }
+            Message::OperationHooksCompleted {
+                operation,
+                failed,
+                failures,
+            } => {
+                self.finish_operation(operation, failed, failures);
+                Command::none()
+            }
             Message::CancelOperation => {
                 self.operation_should_cancel
                     .swap(true, std::sync::atomic::Ordering::Relaxed);
@@ -858,6 +1113,10 @@ impl Application for App {
                 self.config.save();
                 Command::none()
             }
+            Message::EditedRestoreAsOf(text) => {
+                self.restore_screen.as_of = text;
+                Command::none()
+            }
             Message::EditedRestoreSource(text) => {
                 self.restore_screen.restore_source_history.push(&text);
                 self.config.restore.path.reset(text);
@@ -886,6 +1145,19 @@ impl Application for App {
                 self.go_idle();
                 Command::none()
             }
+            Message::MigrateRenamedGames(renamed) => {
+                self.go_idle();
+
+                let mut layout =
+                    BackupLayout::new(self.config.restore.path.clone(), self.config.backup.retention.clone());
+                for (old, new) in renamed {
+                    if let Err(e) = layout.migrate_renamed_game(&old, &new) {
+                        self.show_error(e);
+                        break;
+                    }
+                }
+                Command::none()
+            }
             Message::EditedRoot(action) => {
                 match action {
                     EditAction::Add => {
@@ -912,6 +1184,39 @@ impl Application for App {
                 self.config.save();
                 Command::none()
             }
+            Message::EditedSecondaryManifest(action) => {
+                match action {
+                    EditAction::Add => {
+                        self.other_screen
+                            .secondary_manifest_editor
+                            .rows
+                            .push(SecondaryManifestEditorRow::default());
+                        self.config.manifest.secondary.push(Default::default());
+                    }
+                    EditAction::Change(index, value) => {
+                        self.other_screen.secondary_manifest_editor.rows[index]
+                            .text_history
+                            .push(&value);
+                        self.config.manifest.secondary[index].set_value(value);
+                    }
+                    EditAction::Remove(index) => {
+                        self.other_screen.secondary_manifest_editor.rows.remove(index);
+                        self.config.manifest.secondary.remove(index);
+                    }
+                    EditAction::Move(index, direction) => {
+                        let offset = direction.shift(index);
+                        self.other_screen.secondary_manifest_editor.rows.swap(index, offset);
+                        self.config.manifest.secondary.swap(index, offset);
+                    }
+                }
+                self.config.save();
+                Command::none()
+            }
+            Message::SelectedSecondaryManifestKind(index, kind) => {
+                self.config.manifest.secondary[index].convert(kind);
+                self.config.save();
+                Command::none()
+            }
             Message::SelectedRootStore(index, store) => {
                 self.config.roots[index].store = store;
                 self.config.save();
@@ -1006,13 +1311,13 @@ impl Application for App {
                         self.custom_games_screen.games_editor.entries[game_index]
                             .files
                             .push(CustomGamesEditorEntryRow::default());
-                        self.config.custom_games[game_index].files.push("".to_string());
+                        self.config.custom_games[game_index].files.push(Default::default());
                     }
                     EditAction::Change(index, value) => {
                         self.custom_games_screen.games_editor.entries[game_index].files[index]
                             .text_history
                             .push(&value);
-                        self.config.custom_games[game_index].files[index] = value;
+                        self.config.custom_games[game_index].files[index].path = value;
                     }
                     EditAction::Remove(index) => {
                         self.custom_games_screen.games_editor.entries[game_index]
@@ -1037,13 +1342,13 @@ impl Application for App {
                         self.custom_games_screen.games_editor.entries[game_index]
                             .registry
                             .push(CustomGamesEditorEntryRow::default());
-                        self.config.custom_games[game_index].registry.push("".to_string());
+                        self.config.custom_games[game_index].registry.push(Default::default());
                     }
                     EditAction::Change(index, value) => {
                         self.custom_games_screen.games_editor.entries[game_index].registry[index]
                             .text_history
                             .push(&value);
-                        self.config.custom_games[game_index].registry[index] = value;
+                        self.config.custom_games[game_index].registry[index].path = value;
                     }
                     EditAction::Remove(index) => {
                         self.custom_games_screen.games_editor.entries[game_index]
@@ -1062,6 +1367,95 @@ impl Application for App {
                 self.config.save();
                 Command::none()
             }
+            Message::EditedCustomGameInstallDir(game_index, action) => {
+                match action {
+                    EditAction::Add => {
+                        self.custom_games_screen.games_editor.entries[game_index]
+                            .install_dir
+                            .push(CustomGamesEditorEntryRow::default());
+                        self.config.custom_games[game_index].install_dir.push("".to_string());
+                    }
+                    EditAction::Change(index, value) => {
+                        self.custom_games_screen.games_editor.entries[game_index].install_dir[index]
+                            .text_history
+                            .push(&value);
+                        self.config.custom_games[game_index].install_dir[index] = value;
+                    }
+                    EditAction::Remove(index) => {
+                        self.custom_games_screen.games_editor.entries[game_index]
+                            .install_dir
+                            .remove(index);
+                        self.config.custom_games[game_index].install_dir.remove(index);
+                    }
+                    EditAction::Move(index, direction) => {
+                        let offset = direction.shift(index);
+                        self.custom_games_screen.games_editor.entries[game_index]
+                            .install_dir
+                            .swap(index, offset);
+                        self.config.custom_games[game_index].install_dir.swap(index, offset);
+                    }
+                }
+                self.config.save();
+                Command::none()
+            }
+            Message::SelectedCustomGameIntegration(game_index, integration) => {
+                self.config.custom_games[game_index].integration = integration;
+                self.config.save();
+                Command::none()
+            }
+            Message::EditedCustomGameSteamId(game_index, value) => {
+                if value.is_empty() {
+                    self.config.custom_games[game_index].steam = None;
+                } else if let Ok(id) = value.parse() {
+                    self.config.custom_games[game_index].steam = Some(SteamMetadata { id: Some(id) });
+                }
+                self.config.save();
+                Command::none()
+            }
+            Message::EditedCustomGameGogId(game_index, value) => {
+                if value.is_empty() {
+                    self.config.custom_games[game_index].gog = None;
+                } else if let Ok(id) = value.parse() {
+                    self.config.custom_games[game_index].gog = Some(GogMetadata { id: Some(id) });
+                }
+                self.config.save();
+                Command::none()
+            }
+            Message::ToggledCustomGameFileTag {
+                game,
+                index,
+                tag,
+                enabled,
+            } => {
+                self.config.custom_games[game].files[index].set_tag(tag, enabled);
+                self.config.save();
+                Command::none()
+            }
+            Message::SelectedCustomGameFileOs { game, index, os } => {
+                self.config.custom_games[game].files[index].set_os(os);
+                self.config.save();
+                Command::none()
+            }
+            Message::SelectedCustomGameFileStore { game, index, store } => {
+                self.config.custom_games[game].files[index].set_store(store);
+                self.config.save();
+                Command::none()
+            }
+            Message::ToggledCustomGameRegistryTag {
+                game,
+                index,
+                tag,
+                enabled,
+            } => {
+                self.config.custom_games[game].registry[index].set_tag(tag, enabled);
+                self.config.save();
+                Command::none()
+            }
+            Message::SelectedCustomGameRegistryStore { game, index, store } => {
+                self.config.custom_games[game].registry[index].set_store(store);
+                self.config.save();
+                Command::none()
+            }
             Message::EditedExcludeStoreScreenshots(enabled) => {
                 self.config.backup.filter.exclude_store_screenshots = enabled;
                 self.config.save();
@@ -1233,6 +1627,16 @@ impl Application for App {
                 );
                 Command::none()
             }
+            Message::ToggleSpecificRestorePathIgnored { name, path } => {
+                self.config.restore.toggled_paths.toggle(&name, &path);
+                self.config.save();
+                self.restore_screen.log.update_ignored(
+                    &name,
+                    &self.config.restore.toggled_paths,
+                    &ToggledRegistry::default(),
+                );
+                Command::none()
+            }
             Message::EditedSearchGameName { screen, value } => {
                 match screen {
                     Screen::Backup => {
@@ -1413,6 +1817,17 @@ impl Application for App {
                         &mut self.config.roots[i].path,
                         &mut self.other_screen.root_editor.rows[i].text_history,
                     ),
+                    UndoSubject::SecondaryManifest(i) => {
+                        let history = &mut self.other_screen.secondary_manifest_editor.rows[i].text_history;
+                        match &mut self.config.manifest.secondary[i] {
+                            SecondaryManifestConfig::Local { path } => {
+                                apply_shortcut_to_strict_path_field(&shortcut, path, history)
+                            }
+                            SecondaryManifestConfig::Remote { url } => {
+                                apply_shortcut_to_string_field(&shortcut, url, history)
+                            }
+                        }
+                    }
                     UndoSubject::RedirectSource(i) => apply_shortcut_to_strict_path_field(
                         &shortcut,
                         &mut self.config.redirects[i].source,
@@ -1430,14 +1845,19 @@ impl Application for App {
                     ),
                     UndoSubject::CustomGameFile(i, j) => apply_shortcut_to_string_field(
                         &shortcut,
-                        &mut self.config.custom_games[i].files[j],
+                        &mut self.config.custom_games[i].files[j].path,
                         &mut self.custom_games_screen.games_editor.entries[i].files[j].text_history,
                     ),
                     UndoSubject::CustomGameRegistry(i, j) => apply_shortcut_to_string_field(
                         &shortcut,
-                        &mut self.config.custom_games[i].registry[j],
+                        &mut self.config.custom_games[i].registry[j].path,
                         &mut self.custom_games_screen.games_editor.entries[i].registry[j].text_history,
                     ),
+                    UndoSubject::CustomGameInstallDir(i, j) => apply_shortcut_to_string_field(
+                        &shortcut,
+                        &mut self.config.custom_games[i].install_dir[j],
+                        &mut self.custom_games_screen.games_editor.entries[i].install_dir[j].text_history,
+                    ),
                     UndoSubject::BackupFilterIgnoredPath(i) => apply_shortcut_to_strict_path_field(
                         &shortcut,
                         &mut self.config.backup.filter.ignored_paths[i],
@@ -1516,6 +1936,9 @@ impl Application for App {
                 GameAction::Customize => self.customize_game(game),
                 GameAction::Wiki => Self::open_wiki(game),
                 GameAction::Comment => self.toggle_backup_comment_editor(game),
+                GameAction::Compare => self.toggle_backup_diff(game),
+                GameAction::Filter => self.toggle_game_filter_editor(game),
+                GameAction::Settings => self.toggle_game_settings_editor(game),
             },
             Message::Scroll { subject, position } => {
                 self.scroll_offsets.insert(subject, position);
@@ -1525,6 +1948,115 @@ impl Application for App {
                 self.restore_screen.log.set_comment(&game, comment);
                 Command::none()
             }
+            Message::EditedGameFilterIgnoredPath { game, action } => {
+                let filter = self.config.backup.filter.games.entry(game.clone()).or_default();
+                match action {
+                    EditAction::Add => filter.ignored_paths.push(StrictPath::new("".to_string())),
+                    EditAction::Change(index, value) => filter.ignored_paths[index] = StrictPath::new(value),
+                    EditAction::Remove(index) => {
+                        filter.ignored_paths.remove(index);
+                    }
+                    EditAction::Move(index, direction) => {
+                        let offset = direction.shift(index);
+                        filter.ignored_paths.swap(index, offset);
+                    }
+                }
+                if filter.is_empty() {
+                    self.config.backup.filter.games.remove(&game);
+                }
+                self.config.save();
+                Command::none()
+            }
+            Message::EditedGameFilterSize { game, field, value } => {
+                // Keep the last valid size until the input can be parsed again.
+                let parsed = if value.trim().is_empty() {
+                    Some(None)
+                } else {
+                    crate::gui::game_list::parse_size(&value).map(Some)
+                };
+                self.backup_screen.log.set_game_filter_size(&game, field, value);
+
+                if let Some(parsed) = parsed {
+                    let filter = self.config.backup.filter.games.entry(game.clone()).or_default();
+                    match field {
+                        GameFilterSizeField::MaxFileSize => filter.max_file_size = parsed,
+                        GameFilterSizeField::MaxTotalSize => filter.max_total_size = parsed,
+                    }
+                    if filter.is_empty() {
+                        self.config.backup.filter.games.remove(&game);
+                    }
+                    self.config.save();
+                }
+                Command::none()
+            }
+            Message::EditedGameBackupOverride { game, edit } => {
+                let global = &self.config.backup;
+                let (global_retention, global_format) = (global.retention.clone(), global.format.clone());
+                let overrides = self.config.backup.overrides.entry(game.clone()).or_default();
+                match edit {
+                    GameOverrideEdit::ToggleRetention(enabled) => {
+                        overrides.retention = if enabled {
+                            RetentionOverrides {
+                                full: Some(global_retention.full),
+                                differential: Some(global_retention.differential),
+                            }
+                        } else {
+                            RetentionOverrides::default()
+                        };
+                    }
+                    GameOverrideEdit::FullRetention(value) => {
+                        overrides.retention.full = Some(value);
+                    }
+                    GameOverrideEdit::DiffRetention(value) => {
+                        overrides.retention.differential = Some(value);
+                    }
+                    GameOverrideEdit::ToggleFormat(enabled) => {
+                        overrides.format = if enabled {
+                            BackupFormatOverrides {
+                                chosen: Some(global_format.chosen),
+                                ..Default::default()
+                            }
+                        } else {
+                            BackupFormatOverrides::default()
+                        };
+                    }
+                    GameOverrideEdit::Format(value) => {
+                        overrides.format.chosen = Some(value);
+                    }
+                    GameOverrideEdit::Compression(value) => {
+                        overrides.format.zip.compression = Some(value);
+                        overrides.format.level = None;
+                    }
+                    GameOverrideEdit::CompressionLevel(value) => {
+                        overrides.format.level = Some(value);
+                    }
+                }
+                if overrides.is_empty() {
+                    self.config.backup.overrides.remove(&game);
+                }
+                self.config.save();
+                Command::none()
+            }
+            Message::ToggleFileHistory { name, path } => {
+                self.restore_screen.log.toggle_file_history(&name, &path);
+                Command::none()
+            }
+            Message::RestoreFileVersion { name, path, backup } => {
+                let restored =
+                    self.restore_screen
+                        .log
+                        .restore_file_version(&name, &path, &backup, &self.config.redirects);
+                match restored {
+                    Some(Ok(info)) if info.successful() => {
+                        self.timed_notification =
+                            Some(Notification::new(self.translator.notify_file_version_restored()).expires(3));
+                    }
+                    Some(Ok(_)) => self.show_error(Error::SomeEntriesFailed),
+                    Some(Err(e)) => self.show_error(e),
+                    None => {}
+                }
+                Command::none()
+            }
         }
     }
 
@@ -1590,10 +2122,12 @@ impl Application for App {
                         self.restore_screen
                             .view(&self.config, &self.manifest, &self.translator, &self.operation)
                     }
-                    Screen::CustomGames => {
-                        self.custom_games_screen
-                            .view(&self.config, &self.translator, self.operation.is_some())
-                    }
+                    Screen::CustomGames => self.custom_games_screen.view(
+                        &self.config,
+                        &self.manifest,
+                        &self.translator,
+                        self.operation.is_some(),
+                    ),
                     Screen::Other => {
                         self.other_screen
                             .view(self.updating_manifest, &self.config, &self.cache, &self.translator)
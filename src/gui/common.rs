@@ -1,9 +1,13 @@
 use crate::{
-    config::{BackupFormat, RedirectKind, RootsConfig, SortKey, Theme, ZipCompression},
+    config::{
+        BackupFormat, Integration, RedirectKind, RootsConfig, SecondaryManifestConfigKind, SortKey, Theme,
+        ZipCompression,
+    },
     gui::{badge::Badge, icon::Icon},
+    hooks::{HookFailure, HookOperation},
     lang::{Language, Translator},
     layout::{Backup, GameLayout},
-    manifest::{ManifestUpdate, Store},
+    manifest::{ManifestUpdate, Os, Store, Tag},
     prelude::{BackupInfo, Error, OperationStatus, OperationStepDecision, RegistryItem, ScanInfo, StrictPath},
     shortcuts::{Shortcut, TextHistory},
 };
@@ -19,7 +23,7 @@ pub enum Message {
     CloseModal,
     PruneNotifications,
     UpdateManifest,
-    ManifestUpdated(Result<Option<ManifestUpdate>, Error>),
+    ManifestUpdated(Result<Vec<ManifestUpdate>, Error>),
     ConfirmBackupStart {
         games: Option<Vec<String>>,
     },
@@ -52,19 +56,66 @@ pub enum Message {
         full: bool,
         game_layout: GameLayout,
     },
+    /// The global `before` hooks finished, so the operation's steps can begin.
+    OperationHooksStarted {
+        operation: HookOperation,
+        full: bool,
+        failures: Vec<HookFailure>,
+    },
+    /// The global `after` hooks finished, so the operation can be reported.
+    OperationHooksCompleted {
+        operation: HookOperation,
+        failed: bool,
+        failures: Vec<HookFailure>,
+    },
     CancelOperation,
     EditedBackupTarget(String),
     EditedBackupMerge(bool),
     EditedRestoreSource(String),
+    EditedRestoreAsOf(String),
     FindRoots,
     ConfirmAddMissingRoots(Vec<RootsConfig>),
+    MigrateRenamedGames(std::collections::BTreeMap<String, String>),
     EditedRoot(EditAction),
+    EditedSecondaryManifest(EditAction),
+    SelectedSecondaryManifestKind(usize, SecondaryManifestConfigKind),
     SelectedRootStore(usize, Store),
     SelectedRedirectKind(usize, RedirectKind),
     EditedRedirect(EditAction, Option<RedirectEditActionField>),
     EditedCustomGame(EditAction),
     EditedCustomGameFile(usize, EditAction),
     EditedCustomGameRegistry(usize, EditAction),
+    EditedCustomGameInstallDir(usize, EditAction),
+    SelectedCustomGameIntegration(usize, Integration),
+    EditedCustomGameSteamId(usize, String),
+    EditedCustomGameGogId(usize, String),
+    ToggledCustomGameFileTag {
+        game: usize,
+        index: usize,
+        tag: Tag,
+        enabled: bool,
+    },
+    SelectedCustomGameFileOs {
+        game: usize,
+        index: usize,
+        os: Option<Os>,
+    },
+    SelectedCustomGameFileStore {
+        game: usize,
+        index: usize,
+        store: Option<Store>,
+    },
+    ToggledCustomGameRegistryTag {
+        game: usize,
+        index: usize,
+        tag: Tag,
+        enabled: bool,
+    },
+    SelectedCustomGameRegistryStore {
+        game: usize,
+        index: usize,
+        store: Option<Store>,
+    },
     EditedExcludeStoreScreenshots(bool),
     EditedBackupFilterIgnoredPath(EditAction),
     EditedBackupFilterIgnoredRegistry(EditAction),
@@ -95,6 +146,10 @@ pub enum Message {
         value: Option<String>,
         enabled: bool,
     },
+    ToggleSpecificRestorePathIgnored {
+        name: String,
+        path: StrictPath,
+    },
     ToggleCustomGameEnabled {
         index: usize,
         enabled: bool,
@@ -150,6 +205,28 @@ pub enum Message {
         game: String,
         comment: String,
     },
+    ToggleFileHistory {
+        name: String,
+        path: StrictPath,
+    },
+    RestoreFileVersion {
+        name: String,
+        path: StrictPath,
+        backup: String,
+    },
+    EditedGameFilterIgnoredPath {
+        game: String,
+        action: EditAction,
+    },
+    EditedGameFilterSize {
+        game: String,
+        field: GameFilterSizeField,
+        value: String,
+    },
+    EditedGameBackupOverride {
+        game: String,
+        edit: GameOverrideEdit,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -206,6 +283,23 @@ impl EditAction {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameFilterSizeField {
+    MaxFileSize,
+    MaxTotalSize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOverrideEdit {
+    ToggleRetention(bool),
+    FullRetention(u8),
+    DiffRetention(u8),
+    ToggleFormat(bool),
+    Format(BackupFormat),
+    Compression(ZipCompression),
+    CompressionLevel(i32),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RedirectEditActionField {
     Source,
@@ -230,11 +324,13 @@ pub enum UndoSubject {
     BackupSearchGameName,
     RestoreSearchGameName,
     Root(usize),
+    SecondaryManifest(usize),
     RedirectSource(usize),
     RedirectTarget(usize),
     CustomGameName(usize),
     CustomGameFile(usize, usize),
     CustomGameRegistry(usize, usize),
+    CustomGameInstallDir(usize, usize),
     BackupFilterIgnoredPath(usize),
     BackupFilterIgnoredRegistry(usize),
 }
@@ -302,6 +398,9 @@ pub enum GameAction {
     Restore { confirm: bool },
     Wiki,
     Comment,
+    Compare,
+    Filter,
+    Settings,
 }
 
 impl GameAction {
@@ -322,8 +421,14 @@ impl GameAction {
             options.push(Self::Customize);
         }
 
+        if !restoring {
+            options.push(Self::Filter);
+            options.push(Self::Settings);
+        }
+
         if restoring && has_backups {
             options.push(Self::Comment);
+            options.push(Self::Compare);
         }
 
         if !invented {
@@ -346,6 +451,9 @@ impl GameAction {
             GameAction::Customize => Icon::Edit,
             GameAction::Wiki => Icon::Language,
             GameAction::Comment => Icon::Comment,
+            GameAction::Compare => Icon::CompareArrows,
+            GameAction::Filter => Icon::FilterList,
+            GameAction::Settings => Icon::Settings,
         }
     }
 }
@@ -372,6 +480,9 @@ impl ToString for GameAction {
             Self::Customize => translator.customize_button(),
             Self::Wiki => translator.pcgamingwiki(),
             Self::Comment => translator.comment_button(),
+            Self::Compare => translator.compare_button(),
+            Self::Filter => translator.filter_button(),
+            Self::Settings => translator.game_settings_button(),
         }
     }
 }
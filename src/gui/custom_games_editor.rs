@@ -1,15 +1,18 @@
 use crate::{
-    config::Config,
+    config::{Config, Integration},
     gui::{
         common::{BrowseSubject, EditAction, IcedButtonExt, Message, UndoSubject},
         icon::Icon,
         style,
     },
     lang::Translator,
+    manifest::{Manifest, Os, Store, Tag},
     shortcuts::TextHistory,
 };
 
-use crate::gui::widget::{Button, Checkbox, Column, Container, Row, Space, Text, TextInput, Tooltip, Undoable};
+use crate::gui::widget::{
+    Button, Checkbox, Column, Container, PickList, Row, Space, Text, TextInput, Tooltip, Undoable,
+};
 use iced::{widget::tooltip, Alignment, Length};
 
 use super::common::ScrollSubject;
@@ -32,6 +35,7 @@ pub struct CustomGamesEditorEntry {
     pub text_history: TextHistory,
     pub files: Vec<CustomGamesEditorEntryRow>,
     pub registry: Vec<CustomGamesEditorEntryRow>,
+    pub install_dir: Vec<CustomGamesEditorEntryRow>,
 }
 
 impl CustomGamesEditorEntry {
@@ -43,13 +47,57 @@ impl CustomGamesEditorEntry {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OsChoice(pub Option<Os>);
+
+impl OsChoice {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self(None),
+            Self(Some(Os::Windows)),
+            Self(Some(Os::Linux)),
+            Self(Some(Os::Mac)),
+        ]
+    }
+}
+
+impl std::fmt::Display for OsChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Translator::default().constraint_os(&self.0))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StoreChoice(pub Option<Store>);
+
+impl StoreChoice {
+    pub fn all() -> Vec<Self> {
+        std::iter::once(Self(None))
+            .chain(Store::ALL.iter().map(|x| Self(Some(*x))))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for StoreChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Translator::default().constraint_store(&self.0))
+    }
+}
+
+/// Paths from the manifest that an extending custom game keeps, shown as read-only rows.
+fn inherited<'a>(paths: Vec<String>) -> Column<'a> {
+    paths.into_iter().fold(Column::new().spacing(4), |column, path| {
+        column.push(Container::new(Text::new(path)).padding([5, 0, 5, 5]))
+    })
+}
+
 #[derive(Default)]
 pub struct CustomGamesEditor {
     pub entries: Vec<CustomGamesEditorEntry>,
 }
 
 impl CustomGamesEditor {
-    pub fn view(&self, config: &Config, translator: &Translator, operating: bool) -> Container {
+    pub fn view(&self, config: &Config, manifest: &Manifest, translator: &Translator, operating: bool) -> Container {
         if config.custom_games.is_empty() {
             return Container::new(Space::new(Length::Shrink, Length::Shrink));
         }
@@ -57,6 +105,23 @@ impl CustomGamesEditor {
         let content = self.entries.iter().enumerate().fold(
             Column::new().width(Length::Fill).padding([0, 15, 5, 15]).spacing(10),
             |parent, (i, x)| {
+                let standard = match config.custom_games[i].integration {
+                    Integration::Override => None,
+                    Integration::Extend => manifest.0.get(&config.custom_games[i].name),
+                };
+                let inherited_files = standard
+                    .and_then(|x| x.files.as_ref())
+                    .map(|x| x.keys().cloned().collect())
+                    .unwrap_or_default();
+                let inherited_registry = standard
+                    .and_then(|x| x.registry.as_ref())
+                    .map(|x| x.keys().cloned().collect())
+                    .unwrap_or_default();
+                let inherited_install_dir = standard
+                    .and_then(|x| x.install_dir.as_ref())
+                    .map(|x| x.keys().cloned().collect())
+                    .unwrap_or_default();
+
                 parent.push(
                     Container::new(
                         Column::new()
@@ -98,6 +163,14 @@ impl CustomGamesEditor {
                                         .padding(5),
                                         move |action| Message::UndoRedo(action, UndoSubject::CustomGameName(i)),
                                     ))
+                                    .push(
+                                        PickList::new(
+                                            Integration::ALL,
+                                            Some(config.custom_games[i].integration),
+                                            move |v| Message::SelectedCustomGameIntegration(i, v),
+                                        )
+                                        .style(style::PickList::Primary),
+                                    )
                                     .push(
                                         Tooltip::new(
                                             Button::new(Icon::Refresh.as_text())
@@ -133,7 +206,7 @@ impl CustomGamesEditor {
                                         x.files
                                             .iter()
                                             .enumerate()
-                                            .fold(Column::new().spacing(4), |column, (ii, _)| {
+                                            .fold(inherited(inherited_files), |column, (ii, _)| {
                                                 column.push(
                                                     Row::new()
                                                         .align_items(Alignment::Center)
@@ -159,7 +232,7 @@ impl CustomGamesEditor {
                                                         .push(Undoable::new(
                                                             TextInput::new(
                                                                 "",
-                                                                &config.custom_games[i].files[ii],
+                                                                &config.custom_games[i].files[ii].path,
                                                                 move |v| {
                                                                     Message::EditedCustomGameFile(
                                                                         i,
@@ -176,6 +249,53 @@ impl CustomGamesEditor {
                                                                 )
                                                             },
                                                         ))
+                                                        .push(
+                                                            PickList::new(
+                                                                OsChoice::all(),
+                                                                Some(OsChoice(config.custom_games[i].files[ii].os())),
+                                                                move |v| Message::SelectedCustomGameFileOs {
+                                                                    game: i,
+                                                                    index: ii,
+                                                                    os: v.0,
+                                                                },
+                                                            )
+                                                            .style(style::PickList::Primary),
+                                                        )
+                                                        .push(
+                                                            PickList::new(
+                                                                StoreChoice::all(),
+                                                                Some(StoreChoice(
+                                                                    config.custom_games[i].files[ii].store(),
+                                                                )),
+                                                                move |v| Message::SelectedCustomGameFileStore {
+                                                                    game: i,
+                                                                    index: ii,
+                                                                    store: v.0,
+                                                                },
+                                                            )
+                                                            .style(style::PickList::Primary),
+                                                        )
+                                                        .push([Tag::Save, Tag::Config].into_iter().fold(
+                                                            Row::new().spacing(10),
+                                                            |row, tag| {
+                                                                row.push(
+                                                                    Checkbox::new(
+                                                                        translator.tag(&tag),
+                                                                        config.custom_games[i].files[ii].has_tag(&tag),
+                                                                        move |enabled| {
+                                                                            Message::ToggledCustomGameFileTag {
+                                                                                game: i,
+                                                                                index: ii,
+                                                                                tag: tag.clone(),
+                                                                                enabled,
+                                                                            }
+                                                                        },
+                                                                    )
+                                                                    .spacing(5)
+                                                                    .style(style::Checkbox),
+                                                                )
+                                                            },
+                                                        ))
                                                         .push(
                                                             Button::new(Icon::FolderOpen.as_text())
                                                                 .on_press(Message::BrowseDir(
@@ -211,7 +331,7 @@ impl CustomGamesEditor {
                                         x.registry
                                             .iter()
                                             .enumerate()
-                                            .fold(Column::new().spacing(4), |column, (ii, _)| {
+                                            .fold(inherited(inherited_registry), |column, (ii, _)| {
                                                 column.push(
                                                     Row::new()
                                                         .spacing(20)
@@ -237,7 +357,7 @@ impl CustomGamesEditor {
                                                         .push(Undoable::new(
                                                             TextInput::new(
                                                                 "",
-                                                                &config.custom_games[i].registry[ii],
+                                                                &config.custom_games[i].registry[ii].path,
                                                                 move |v| {
                                                                     Message::EditedCustomGameRegistry(
                                                                         i,
@@ -254,6 +374,42 @@ impl CustomGamesEditor {
                                                                 )
                                                             },
                                                         ))
+                                                        .push(
+                                                            PickList::new(
+                                                                StoreChoice::all(),
+                                                                Some(StoreChoice(
+                                                                    config.custom_games[i].registry[ii].store(),
+                                                                )),
+                                                                move |v| Message::SelectedCustomGameRegistryStore {
+                                                                    game: i,
+                                                                    index: ii,
+                                                                    store: v.0,
+                                                                },
+                                                            )
+                                                            .style(style::PickList::Primary),
+                                                        )
+                                                        .push([Tag::Save, Tag::Config].into_iter().fold(
+                                                            Row::new().spacing(10),
+                                                            |row, tag| {
+                                                                row.push(
+                                                                    Checkbox::new(
+                                                                        translator.tag(&tag),
+                                                                        config.custom_games[i].registry[ii]
+                                                                            .has_tag(&tag),
+                                                                        move |enabled| {
+                                                                            Message::ToggledCustomGameRegistryTag {
+                                                                                game: i,
+                                                                                index: ii,
+                                                                                tag: tag.clone(),
+                                                                                enabled,
+                                                                            }
+                                                                        },
+                                                                    )
+                                                                    .spacing(5)
+                                                                    .style(style::Checkbox),
+                                                                )
+                                                            },
+                                                        ))
                                                         .push(
                                                             Button::new(Icon::RemoveCircle.as_text())
                                                                 .on_press(Message::EditedCustomGameRegistry(
@@ -270,6 +426,122 @@ impl CustomGamesEditor {
                                                     .style(style::Button::Primary),
                                             ),
                                     ),
+                            )
+                            .push(
+                                Row::new()
+                                    .push(
+                                        Column::new()
+                                            .width(130)
+                                            .push(Text::new(translator.custom_install_dir_label())),
+                                    )
+                                    .push(
+                                        x.install_dir
+                                            .iter()
+                                            .enumerate()
+                                            .fold(inherited(inherited_install_dir), |column, (ii, _)| {
+                                                column.push(
+                                                    Row::new()
+                                                        .spacing(20)
+                                                        .align_items(Alignment::Center)
+                                                        .push(Icon::ArrowUpward.as_button_small().on_press_if(
+                                                            || ii > 0,
+                                                            || {
+                                                                Message::EditedCustomGameInstallDir(
+                                                                    i,
+                                                                    EditAction::move_up(ii),
+                                                                )
+                                                            },
+                                                        ))
+                                                        .push(Icon::ArrowDownward.as_button_small().on_press_if(
+                                                            || ii < x.install_dir.len() - 1,
+                                                            || {
+                                                                Message::EditedCustomGameInstallDir(
+                                                                    i,
+                                                                    EditAction::move_down(ii),
+                                                                )
+                                                            },
+                                                        ))
+                                                        .push(Undoable::new(
+                                                            TextInput::new(
+                                                                "",
+                                                                &config.custom_games[i].install_dir[ii],
+                                                                move |v| {
+                                                                    Message::EditedCustomGameInstallDir(
+                                                                        i,
+                                                                        EditAction::Change(ii, v),
+                                                                    )
+                                                                },
+                                                            )
+                                                            .style(style::TextInput)
+                                                            .padding(5),
+                                                            move |action| {
+                                                                Message::UndoRedo(
+                                                                    action,
+                                                                    UndoSubject::CustomGameInstallDir(i, ii),
+                                                                )
+                                                            },
+                                                        ))
+                                                        .push(
+                                                            Button::new(Icon::RemoveCircle.as_text())
+                                                                .on_press(Message::EditedCustomGameInstallDir(
+                                                                    i,
+                                                                    EditAction::Remove(ii),
+                                                                ))
+                                                                .style(style::Button::Negative),
+                                                        ),
+                                                )
+                                            })
+                                            .push(
+                                                Button::new(Icon::AddCircle.as_text())
+                                                    .on_press(Message::EditedCustomGameInstallDir(i, EditAction::Add))
+                                                    .style(style::Button::Primary),
+                                            ),
+                                    ),
+                            )
+                            .push(
+                                Row::new()
+                                    .align_items(Alignment::Center)
+                                    .push(
+                                        Column::new()
+                                            .width(130)
+                                            .push(Text::new(translator.custom_steam_id_label())),
+                                    )
+                                    .push(
+                                        TextInput::new(
+                                            "",
+                                            &config.custom_games[i]
+                                                .steam
+                                                .as_ref()
+                                                .and_then(|x| x.id)
+                                                .map(|x| x.to_string())
+                                                .unwrap_or_default(),
+                                            move |v| Message::EditedCustomGameSteamId(i, v),
+                                        )
+                                        .style(style::TextInput)
+                                        .width(150)
+                                        .padding(5),
+                                    )
+                                    .push(Space::new(20, 0))
+                                    .push(
+                                        Column::new()
+                                            .width(130)
+                                            .push(Text::new(translator.custom_gog_id_label())),
+                                    )
+                                    .push(
+                                        TextInput::new(
+                                            "",
+                                            &config.custom_games[i]
+                                                .gog
+                                                .as_ref()
+                                                .and_then(|x| x.id)
+                                                .map(|x| x.to_string())
+                                                .unwrap_or_default(),
+                                            move |v| Message::EditedCustomGameGogId(i, v),
+                                        )
+                                        .style(style::TextInput)
+                                        .width(150)
+                                        .padding(5),
+                                    ),
                             ),
                     )
                     .style(style::Container::GameListEntry),
@@ -6,6 +6,7 @@ use crate::{
         style,
     },
     lang::Translator,
+    manifest::Manifest,
 };
 
 use crate::gui::widget::{Button, Column, Container, Row, Text};
@@ -22,10 +23,13 @@ impl CustomGamesScreenComponent {
         for custom_game in &config.custom_games {
             let mut row = CustomGamesEditorEntry::new(&custom_game.name.to_string());
             for file in &custom_game.files {
-                row.files.push(CustomGamesEditorEntryRow::new(file))
+                row.files.push(CustomGamesEditorEntryRow::new(&file.path))
             }
             for key in &custom_game.registry {
-                row.registry.push(CustomGamesEditorEntryRow::new(key))
+                row.registry.push(CustomGamesEditorEntryRow::new(&key.path))
+            }
+            for folder in &custom_game.install_dir {
+                row.install_dir.push(CustomGamesEditorEntryRow::new(folder))
             }
             games_editor.entries.push(row);
         }
@@ -33,7 +37,7 @@ impl CustomGamesScreenComponent {
         Self { games_editor }
     }
 
-    pub fn view(&self, config: &Config, translator: &Translator, operating: bool) -> Container {
+    pub fn view(&self, config: &Config, manifest: &Manifest, translator: &Translator, operating: bool) -> Container {
         Container::new(
             Column::new()
                 .spacing(20)
@@ -70,7 +74,7 @@ impl CustomGamesScreenComponent {
                             .style(style::Button::Primary)
                         }),
                 )
-                .push(self.games_editor.view(config, translator, operating)),
+                .push(self.games_editor.view(config, manifest, translator, operating)),
         )
         .height(Length::Fill)
         .width(Length::Fill)
@@ -52,6 +52,19 @@ impl FileTreeNode {
         }
     }
 
+    fn excluded(&self) -> bool {
+        matches!(&self.scanned_file, Some(x) if x.excluded.is_some())
+    }
+
+    /// Restored files are shown at their redirected location,
+    /// but they're toggled by their original path, like when backing up.
+    fn toggled_file_path<'a>(&'a self, path: &'a StrictPath) -> &'a StrictPath {
+        match &self.scanned_file {
+            Some(x) if x.restoring() => x.original_path(),
+            _ => path,
+        }
+    }
+
     pub fn anything_showable(&self) -> bool {
         if self.nodes.is_empty() {
             return true;
@@ -76,15 +89,26 @@ impl FileTreeNode {
         let expanded = self.expanded;
 
         let make_enabler = || {
-            if restoring {
-                return None;
-            }
             if let Some(path) = &self.path {
+                if restoring && !matches!(path, FileTreeNodePath::File(_)) {
+                    return None;
+                }
+                if self.excluded() {
+                    // Game-specific rules can't be overridden here.
+                    return None;
+                }
                 let game_name = game_name.to_string();
-                let path = path.clone();
+                let path = match path {
+                    FileTreeNodePath::File(path) => FileTreeNodePath::File(self.toggled_file_path(path).clone()),
+                    _ => path.clone(),
+                };
                 return Some(
                     Container::new(
                         Checkbox::new("", !self.ignored, move |enabled| match &path {
+                            FileTreeNodePath::File(path) if restoring => Message::ToggleSpecificRestorePathIgnored {
+                                name: game_name.clone(),
+                                path: path.clone(),
+                            },
                             FileTreeNodePath::File(path) => Message::ToggleSpecificBackupPathIgnored {
                                 name: game_name.clone(),
                                 path: path.clone(),
@@ -134,6 +158,21 @@ impl FileTreeNode {
                     })
                     .push_some(make_enabler)
                     .push(Text::new(label))
+                    .push_some(|| {
+                        if !restoring {
+                            return None;
+                        }
+                        let original_path = self.scanned_file.as_ref()?.original_path.as_ref()?;
+                        Some(
+                            Button::new(Icon::History.as_text().width(Length::Shrink).size(15))
+                                .on_press(Message::ToggleFileHistory {
+                                    name: game_name.to_string(),
+                                    path: original_path.clone(),
+                                })
+                                .style(style::Button::Primary)
+                                .height(25),
+                        )
+                    })
                     .push_some(|| {
                         let badge = match self.change {
                             ScanChange::Same | ScanChange::Unknown => return None,
@@ -142,6 +181,10 @@ impl FileTreeNode {
                         };
                         Some(badge.view())
                     })
+                    .push_some(|| {
+                        let excluded = self.scanned_file.as_ref()?.excluded.as_ref()?;
+                        Some(Badge::new(&translator.badge_excluded_by(excluded)).view())
+                    })
                     .push_if(|| self.duplicated, || Badge::new(&translator.badge_duplicated()).view())
                     .push_if(|| !self.successful, || Badge::new(&translator.badge_failed()).view())
                     .push_some(|| {
@@ -331,7 +374,7 @@ impl FileTreeNode {
     pub fn update_ignored(&mut self, game: &str, ignored_paths: &ToggledPaths, ignored_registry: &ToggledRegistry) {
         match &self.path {
             Some(FileTreeNodePath::File(path)) => {
-                self.ignored = ignored_paths.is_ignored(game, path);
+                self.ignored = self.excluded() || ignored_paths.is_ignored(game, self.toggled_file_path(path));
             }
             Some(FileTreeNodePath::RegistryKey(path)) => {
                 self.ignored = ignored_registry.is_ignored(game, path, None);
@@ -416,13 +459,15 @@ impl FileTree {
                 );
         }
 
+        let no_registry = ToggledRegistry::default();
+        let (toggled_paths, toggled_registry) = if scan_info.restoring() {
+            (&config.restore.toggled_paths, &no_registry)
+        } else {
+            (&config.backup.toggled_paths, &config.backup.toggled_registry)
+        };
         for item in nodes.values_mut() {
             item.expand_short();
-            item.update_ignored(
-                &scan_info.game_name,
-                &config.backup.toggled_paths,
-                &config.backup.toggled_registry,
-            );
+            item.update_ignored(&scan_info.game_name, toggled_paths, toggled_registry);
         }
 
         Self { nodes }
@@ -2,18 +2,28 @@ use std::collections::HashSet;
 
 use crate::{
     cache::Cache,
-    config::{Config, Sort, SortKey, ToggledPaths, ToggledRegistry},
+    config::{
+        is_valid_filter_pattern, BackupFormat, Config, RedirectConfig, Sort, SortKey, ToggledPaths, ToggledRegistry,
+        ZipCompression,
+    },
     gui::{
         badge::Badge,
-        common::{GameAction, IcedButtonExt, IcedExtension, Message, Screen},
+        common::{
+            EditAction, GameAction, GameFilterSizeField, GameOverrideEdit, IcedButtonExt, IcedExtension, Message,
+            Screen,
+        },
         file_tree::FileTree,
+        number_input::NumberInput,
         search::SearchComponent,
         style,
     },
     lang::Translator,
-    layout::GameLayout,
+    layout::{FileVersion, GameLayout},
     manifest::Manifest,
-    prelude::{BackupInfo, DuplicateDetector, OperationStatus, ScanInfo},
+    prelude::{
+        scan_game_for_restoration, BackupId, BackupInfo, DuplicateDetector, Error, GameDiff, OperationStatus, ScanInfo,
+        StrictPath,
+    },
 };
 
 use crate::gui::widget::{Button, Checkbox, Column, Container, PickList, Row, Text, Tooltip};
@@ -36,6 +46,52 @@ pub struct GameListEntry {
     pub popup_menu: crate::gui::popup_menu::State<GameAction>,
     pub show_comment_editor: bool,
     pub game_layout: Option<GameLayout>,
+    /// Changes in the selected backup compared to the previous one, if shown.
+    pub diff: Option<(Option<String>, GameDiff)>,
+    /// Stored versions of a single file across all backups, if shown.
+    pub history: Option<(StrictPath, Vec<FileVersion>)>,
+    /// The game's backup rules, if shown.
+    pub filter_editor: Option<GameFilterEditor>,
+    /// The game's backup format and retention, if shown.
+    pub settings_editor: Option<GameSettingsEditor>,
+}
+
+/// Text typed into a game's size limits, which may not be a valid size yet.
+#[derive(Default)]
+pub struct GameFilterEditor {
+    pub max_file_size: String,
+    pub max_total_size: String,
+}
+
+impl GameFilterEditor {
+    fn new(config: &Config, game: &str, translator: &Translator) -> Self {
+        let filter = config.backup.filter.games.get(game);
+        let render = |size: Option<u64>| size.map(|x| translator.adjusted_size(x)).unwrap_or_default();
+        Self {
+            max_file_size: render(filter.and_then(|x| x.max_file_size)),
+            max_total_size: render(filter.and_then(|x| x.max_total_size)),
+        }
+    }
+
+    pub fn set(&mut self, field: GameFilterSizeField, value: String) {
+        match field {
+            GameFilterSizeField::MaxFileSize => self.max_file_size = value,
+            GameFilterSizeField::MaxTotalSize => self.max_total_size = value,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GameSettingsEditor {
+    full_retention_input: NumberInput,
+    diff_retention_input: NumberInput,
+    compression_level_input: NumberInput,
+}
+
+/// Parse a size like `100 MB` or `2 GiB`. A plain number is a count of bytes.
+pub fn parse_size(raw: &str) -> Option<u64> {
+    let bytes = byte_unit::Byte::from_str(raw.trim()).ok()?;
+    u64::try_from(bytes.get_bytes()).ok()
 }
 
 impl GameListEntry {
@@ -147,6 +203,10 @@ impl GameListEntry {
                             || duplicate_detector.is_game_duplicated(&self.scan_info),
                             || Badge::new(&translator.badge_duplicates()).view(),
                         )
+                        .push_if(
+                            || self.scan_info.size_limit_exceeded().is_some(),
+                            || Badge::new(&translator.badge_over_size_limit()).view(),
+                        )
                         .push_if(|| !successful, || Badge::new(&translator.badge_failed()).view())
                         .push_some(|| {
                             self.scan_info
@@ -306,6 +366,77 @@ impl GameListEntry {
                             })),
                     )
                 })
+                .push_some(|| {
+                    let editor = self.filter_editor.as_ref()?;
+                    Some(self.view_filter_editor(editor, translator, config))
+                })
+                .push_some(|| {
+                    let editor = self.settings_editor.as_ref()?;
+                    Some(self.view_settings_editor(editor, translator, config))
+                })
+                .push_some(|| {
+                    let (from, diff) = self.diff.as_ref()?;
+                    let to = self.scan_info.backup.as_ref()?;
+                    let from = from.clone().unwrap_or_else(|| translator.no_backup_label());
+                    let header = Text::new(format!(
+                        "{} -> {} [{}]",
+                        from,
+                        to.label(),
+                        translator.adjusted_size_delta(diff.size_delta())
+                    ));
+                    Some(diff.0.iter().fold(
+                        Column::new().padding([0, 20]).spacing(5).push(header),
+                        |parent, (path, file)| {
+                            parent.push(Text::new(format!(
+                                "[{}] {} [{}]",
+                                translator.diff_symbol(file.kind),
+                                path,
+                                translator.adjusted_size_delta(file.size_delta())
+                            )))
+                        },
+                    ))
+                })
+                .push_some(|| {
+                    let (path, versions) = self.history.as_ref()?;
+                    let name = &self.scan_info.game_name;
+                    let header = Row::new()
+                        .align_items(Alignment::Center)
+                        .spacing(20)
+                        .push(Text::new(path.render()))
+                        .push(Icon::Close.as_button_small().on_press(Message::ToggleFileHistory {
+                            name: name.clone(),
+                            path: path.clone(),
+                        }));
+                    Some(versions.iter().rev().fold(
+                        Column::new().padding([0, 20]).spacing(5).push(header),
+                        |parent, version| {
+                            let mut label = format!(
+                                "{} [{}] {}",
+                                version.label(),
+                                translator.adjusted_size(version.size),
+                                version.hash
+                            );
+                            if let Some(comment) = &version.comment {
+                                label = format!("{label} - {comment}");
+                            }
+                            parent.push(
+                                Row::new()
+                                    .align_items(Alignment::Center)
+                                    .spacing(20)
+                                    .push(
+                                        Button::new(Text::new(translator.restore_button()).size(14))
+                                            .on_press(Message::RestoreFileVersion {
+                                                name: name.clone(),
+                                                path: path.clone(),
+                                                backup: version.backup.clone(),
+                                            })
+                                            .style(style::Button::Primary),
+                                    )
+                                    .push(Text::new(label)),
+                            )
+                        },
+                    ))
+                })
                 .push_if(
                     || expanded,
                     || {
@@ -318,6 +449,224 @@ impl GameListEntry {
         .style(style::Container::GameListEntry)
     }
 
+    fn view_filter_editor(&self, editor: &GameFilterEditor, translator: &Translator, config: &Config) -> Column {
+        let game = &self.scan_info.game_name;
+        let filter = config.backup.filter.games.get(game).cloned().unwrap_or_default();
+
+        let size_row = |label: String, field: GameFilterSizeField, value: &str| {
+            let game = game.clone();
+            Row::new()
+                .align_items(Alignment::Center)
+                .spacing(20)
+                .push(Text::new(label).width(150))
+                .push(TextInput::new(
+                    &translator.size_limit_placeholder(),
+                    value,
+                    move |value| Message::EditedGameFilterSize {
+                        game: game.clone(),
+                        field,
+                        value,
+                    },
+                ))
+                .push_if(
+                    || !value.trim().is_empty() && parse_size(value).is_none(),
+                    || Badge::new(&translator.badge_invalid()).view(),
+                )
+        };
+
+        let paths = filter
+            .ignored_paths
+            .iter()
+            .enumerate()
+            .fold(Column::new().spacing(4), |column, (ii, path)| {
+                let game = game.clone();
+                let game2 = game.clone();
+                column.push(
+                    Row::new()
+                        .align_items(Alignment::Center)
+                        .spacing(20)
+                        .push(TextInput::new(
+                            &translator.ignored_path_placeholder(),
+                            &path.raw(),
+                            move |value| Message::EditedGameFilterIgnoredPath {
+                                game: game.clone(),
+                                action: EditAction::Change(ii, value),
+                            },
+                        ))
+                        .push_if(
+                            || !is_valid_filter_pattern(&path.raw()),
+                            || Badge::new(&translator.badge_invalid()).view(),
+                        )
+                        .push(
+                            Button::new(Icon::RemoveCircle.as_text())
+                                .on_press(Message::EditedGameFilterIgnoredPath {
+                                    game: game2,
+                                    action: EditAction::Remove(ii),
+                                })
+                                .style(style::Button::Negative),
+                        ),
+                )
+            });
+
+        Column::new()
+            .padding([0, 20])
+            .spacing(5)
+            .push(
+                Row::new()
+                    .align_items(Alignment::Center)
+                    .spacing(20)
+                    .push(Text::new(translator.filter_button()))
+                    .push(Icon::Close.as_button_small().on_press(Message::GameAction {
+                        action: GameAction::Filter,
+                        game: game.clone(),
+                    })),
+            )
+            .push(
+                Row::new()
+                    .spacing(20)
+                    .push(Text::new(translator.custom_files_label()).width(150))
+                    .push(
+                        paths.push(
+                            Button::new(Icon::AddCircle.as_text())
+                                .on_press(Message::EditedGameFilterIgnoredPath {
+                                    game: game.clone(),
+                                    action: EditAction::Add,
+                                })
+                                .style(style::Button::Primary),
+                        ),
+                    ),
+            )
+            .push(size_row(
+                translator.game_max_file_size_label(),
+                GameFilterSizeField::MaxFileSize,
+                &editor.max_file_size,
+            ))
+            .push(size_row(
+                translator.game_max_total_size_label(),
+                GameFilterSizeField::MaxTotalSize,
+                &editor.max_total_size,
+            ))
+    }
+
+    fn view_settings_editor<'a>(
+        &'a self,
+        editor: &'a GameSettingsEditor,
+        translator: &Translator,
+        config: &Config,
+    ) -> Column<'a> {
+        let game = &self.scan_info.game_name;
+        let overrides = config.backup.overrides.get(game).cloned().unwrap_or_default();
+        let retention = config.backup.retention_for(game);
+        let format = config.backup.format_for(game);
+        let retention_overridden = !overrides.retention.is_empty();
+        let format_overridden = !overrides.format.is_empty();
+        let edit = |edit: GameOverrideEdit| Message::EditedGameBackupOverride {
+            game: game.clone(),
+            edit,
+        };
+
+        let retention_row = Row::new()
+            .align_items(Alignment::Center)
+            .spacing(20)
+            .height(30)
+            .push(
+                Checkbox::new(
+                    translator.game_override_retention_label(),
+                    retention_overridden,
+                    move |x| edit(GameOverrideEdit::ToggleRetention(x)),
+                )
+                .style(style::Checkbox),
+            )
+            .push_if(
+                || retention_overridden,
+                || {
+                    editor.full_retention_input.view(
+                        retention.full as i32,
+                        translator.full_retention(),
+                        1..=255,
+                        |x| edit(GameOverrideEdit::FullRetention(x as u8)),
+                    )
+                },
+            )
+            .push_if(
+                || retention_overridden,
+                || {
+                    editor.diff_retention_input.view(
+                        retention.differential as i32,
+                        translator.differential_retention(),
+                        0..=255,
+                        |x| edit(GameOverrideEdit::DiffRetention(x as u8)),
+                    )
+                },
+            );
+
+        let format_row = Row::new()
+            .align_items(Alignment::Center)
+            .spacing(20)
+            .push(
+                Checkbox::new(
+                    translator.game_override_format_label(),
+                    format_overridden,
+                    move |x| edit(GameOverrideEdit::ToggleFormat(x)),
+                )
+                .style(style::Checkbox),
+            )
+            .push_if(
+                || format_overridden,
+                || {
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(translator.backup_format_field()))
+                        .push(
+                            PickList::new(BackupFormat::ALL, Some(format.chosen), move |x| {
+                                edit(GameOverrideEdit::Format(x))
+                            })
+                            .style(style::PickList::Primary),
+                        )
+                },
+            )
+            .push_if(
+                || format_overridden && format.chosen == BackupFormat::Zip,
+                || {
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(translator.backup_compression_field()))
+                        .push(
+                            PickList::new(ZipCompression::ALL, Some(format.zip.compression), move |x| {
+                                edit(GameOverrideEdit::Compression(x))
+                            })
+                            .style(style::PickList::Primary),
+                        )
+                },
+            )
+            .push_some(|| {
+                Some(editor.compression_level_input.view(
+                    format.level().filter(|_| format_overridden)?,
+                    translator.backup_compression_level_field(),
+                    format.range()?,
+                    |x| edit(GameOverrideEdit::CompressionLevel(x)),
+                ))
+            });
+
+        Column::new()
+            .padding([0, 20])
+            .spacing(5)
+            .push(
+                Row::new()
+                    .align_items(Alignment::Center)
+                    .spacing(20)
+                    .push(Text::new(translator.game_settings_button()))
+                    .push(Icon::Close.as_button_small().on_press(Message::GameAction {
+                        action: GameAction::Settings,
+                        game: game.clone(),
+                    })),
+            )
+            .push(retention_row)
+            .push(format_row)
+    }
+
     pub fn populate_tree(&mut self, config: &Config, duplicate_detector: &DuplicateDetector) {
         self.tree = FileTree::new(self.scan_info.clone(), config, &self.backup_info, duplicate_detector);
     }
@@ -325,6 +674,19 @@ impl GameListEntry {
     pub fn clear_tree(&mut self) {
         self.tree = Default::default();
     }
+
+    fn compute_diff(&self) -> Option<(Option<String>, GameDiff)> {
+        let layout = self.game_layout.as_ref()?;
+        let backup = self.scan_info.backup.as_ref()?;
+        let previous = layout.previous_backup(&backup.id());
+        let diff = layout.diff(previous.as_ref().map(|x| x.id()).as_ref(), &backup.id());
+        Some((previous.map(|x| x.label()), diff))
+    }
+
+    fn compute_history(&self, path: &StrictPath) -> Option<(StrictPath, Vec<FileVersion>)> {
+        let layout = self.game_layout.as_ref()?;
+        Some((path.clone(), layout.file_history(path)))
+    }
 }
 
 #[derive(Default)]
@@ -535,6 +897,12 @@ impl GameList {
                     self.entries[i].scan_info = scan_info;
                     self.entries[i].backup_info = backup_info;
                     self.entries[i].game_layout = game_layout;
+                    if self.entries[i].diff.is_some() {
+                        self.entries[i].diff = self.entries[i].compute_diff();
+                    }
+                    if let Some((path, _)) = self.entries[i].history.take() {
+                        self.entries[i].history = self.entries[i].compute_history(&path);
+                    }
                     if self.expanded_games.contains(&game_name) {
                         self.entries[i].populate_tree(config, duplicate_detector);
                     }
@@ -604,6 +972,76 @@ impl GameList {
         }
     }
 
+    pub fn toggle_game_filter_editor(&mut self, game: &str, config: &Config, translator: &Translator) {
+        let Some(index) = self.find_game(game) else { return };
+        let entry = &mut self.entries[index];
+
+        entry.filter_editor = if entry.filter_editor.is_some() {
+            None
+        } else {
+            Some(GameFilterEditor::new(config, game, translator))
+        };
+    }
+
+    pub fn toggle_game_settings_editor(&mut self, game: &str) {
+        let Some(index) = self.find_game(game) else { return };
+        let entry = &mut self.entries[index];
+
+        entry.settings_editor = if entry.settings_editor.is_some() {
+            None
+        } else {
+            Some(GameSettingsEditor::default())
+        };
+    }
+
+    pub fn set_game_filter_size(&mut self, game: &str, field: GameFilterSizeField, value: String) {
+        let Some(index) = self.find_game(game) else { return };
+        if let Some(editor) = &mut self.entries[index].filter_editor {
+            editor.set(field, value);
+        }
+    }
+
+    pub fn toggle_backup_diff(&mut self, game: &str) {
+        let Some(index) = self.find_game(game) else { return };
+        let entry = &mut self.entries[index];
+
+        entry.diff = if entry.diff.is_some() {
+            None
+        } else {
+            entry.compute_diff()
+        };
+    }
+
+    pub fn toggle_file_history(&mut self, game: &str, path: &StrictPath) {
+        let Some(index) = self.find_game(game) else { return };
+        let entry = &mut self.entries[index];
+
+        entry.history = match &entry.history {
+            Some((shown, _)) if shown == path => None,
+            _ => entry.compute_history(path),
+        };
+    }
+
+    /// Restore a single file as it was in the given backup.
+    pub fn restore_file_version(
+        &mut self,
+        game: &str,
+        path: &StrictPath,
+        backup: &str,
+        redirects: &[RedirectConfig],
+    ) -> Option<Result<BackupInfo, Error>> {
+        let index = self.find_game(game)?;
+        let mut layout = self.entries[index].game_layout.clone()?;
+
+        let id = BackupId::Named(backup.to_string());
+        if layout.verify_id(&id) != id {
+            return Some(Err(Error::CliInvalidBackupId));
+        }
+
+        let mut scan_info = scan_game_for_restoration(game, &id, &mut layout, redirects);
+        Some(layout.restore_file_version(&mut scan_info, path))
+    }
+
     pub fn set_comment(&mut self, game: &str, comment: String) {
         let Some(index) = self.find_game(game) else { return };
         let entry = &mut self.entries[index];
@@ -31,6 +31,9 @@ pub enum Icon {
     ArrowDownward,
     Comment,
     Close,
+    CompareArrows,
+    History,
+    FilterList,
 }
 
 impl Icon {
@@ -58,6 +61,9 @@ impl Icon {
             Self::ArrowDownward => '\u{E5DB}',
             Self::Comment => '\u{E0B9}',
             Self::Close => '\u{E5CD}',
+            Self::CompareArrows => '\u{E915}',
+            Self::History => '\u{E889}',
+            Self::FilterList => '\u{E152}',
         }
     }
 
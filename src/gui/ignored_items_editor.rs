@@ -1,7 +1,8 @@
 use crate::{
-    config::Config,
+    config::{is_valid_filter_pattern, Config},
     gui::{
-        common::{BrowseSubject, EditAction, Message, UndoSubject},
+        badge::Badge,
+        common::{BrowseSubject, EditAction, IcedExtension, Message, UndoSubject},
         icon::Icon,
         style,
     },
@@ -102,7 +103,7 @@ impl IgnoredItemsEditor {
                                                     ))
                                                     .push(Undoable::new(
                                                         TextInput::new(
-                                                            "",
+                                                            &translator.ignored_path_placeholder(),
                                                             &config.backup.filter.ignored_paths[ii].raw(),
                                                             move |v| {
                                                                 Message::EditedBackupFilterIgnoredPath(
@@ -119,6 +120,14 @@ impl IgnoredItemsEditor {
                                                             )
                                                         },
                                                     ))
+                                                    .push_if(
+                                                        || {
+                                                            !is_valid_filter_pattern(
+                                                                &config.backup.filter.ignored_paths[ii].raw(),
+                                                            )
+                                                        },
+                                                        || Badge::new(&translator.badge_invalid()).view(),
+                                                    )
                                                     .push(
                                                         Button::new(Icon::FolderOpen.as_text())
                                                             .on_press(Message::BrowseDir(
@@ -176,7 +185,7 @@ impl IgnoredItemsEditor {
                                                     ))
                                                     .push(Undoable::new(
                                                         TextInput::new(
-                                                            "",
+                                                            &translator.ignored_registry_placeholder(),
                                                             &config.backup.filter.ignored_registry[ii].raw(),
                                                             move |v| {
                                                                 Message::EditedBackupFilterIgnoredRegistry(
@@ -193,6 +202,14 @@ impl IgnoredItemsEditor {
                                                             )
                                                         },
                                                     ))
+                                                    .push_if(
+                                                        || {
+                                                            !is_valid_filter_pattern(
+                                                                &config.backup.filter.ignored_registry[ii].raw(),
+                                                            )
+                                                        },
+                                                        || Badge::new(&translator.badge_invalid()).view(),
+                                                    )
                                                     .push(
                                                         Button::new(Icon::RemoveCircle.as_text())
                                                             .on_press(Message::EditedBackupFilterIgnoredRegistry(
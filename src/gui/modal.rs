@@ -2,8 +2,10 @@ use crate::{
     config::{Config, RootsConfig},
     gui::{common::Message, style},
     lang::Translator,
+    manifest::ManifestUpdate,
     prelude::Error,
 };
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::gui::widget::{Button, Column, Container, Row, Space, Text};
 use iced::{alignment::Horizontal as HorizontalAlignment, Alignment, Length};
@@ -18,23 +20,37 @@ pub enum ModalVariant {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ModalTheme {
-    Error { variant: Error },
-    ConfirmBackup { games: Option<Vec<String>> },
-    ConfirmRestore { games: Option<Vec<String>> },
+    Error {
+        variant: Error,
+    },
+    ConfirmBackup {
+        games: Option<Vec<String>>,
+    },
+    ConfirmRestore {
+        games: Option<Vec<String>>,
+    },
     NoMissingRoots,
     ConfirmAddMissingRoots(Vec<RootsConfig>),
+    /// Backed up games that were renamed in the manifest (old name -> new name).
+    ConfirmMigrateRenamedGames(BTreeMap<String, String>),
     PreparingBackupDir,
     UpdatingManifest,
+    /// Manifest updates that affected games with existing backups.
+    ManifestUpdated {
+        updates: Vec<ManifestUpdate>,
+        backed_up: BTreeSet<String>,
+    },
 }
 
 impl ModalTheme {
     pub fn variant(&self) -> ModalVariant {
         match self {
             Self::PreparingBackupDir | Self::UpdatingManifest => ModalVariant::Loading,
-            Self::Error { .. } | Self::NoMissingRoots => ModalVariant::Info,
-            Self::ConfirmBackup { .. } | Self::ConfirmRestore { .. } | Self::ConfirmAddMissingRoots(..) => {
-                ModalVariant::Confirm
-            }
+            Self::Error { .. } | Self::NoMissingRoots | Self::ManifestUpdated { .. } => ModalVariant::Info,
+            Self::ConfirmBackup { .. }
+            | Self::ConfirmRestore { .. }
+            | Self::ConfirmAddMissingRoots(..)
+            | Self::ConfirmMigrateRenamedGames(..) => ModalVariant::Confirm,
         }
     }
 
@@ -50,14 +66,20 @@ impl ModalTheme {
             Self::ConfirmRestore { .. } => translator.confirm_restore(&config.restore.path, true),
             Self::NoMissingRoots => translator.no_missing_roots(),
             Self::ConfirmAddMissingRoots(missing) => translator.confirm_add_missing_roots(missing),
+            Self::ConfirmMigrateRenamedGames(renamed) => translator.confirm_migrate_renamed_games(renamed),
             Self::PreparingBackupDir => translator.preparing_backup_dir(),
             Self::UpdatingManifest => translator.updating_manifest(),
+            Self::ManifestUpdated { updates, backed_up } => updates
+                .iter()
+                .map(|update| translator.manifest_update_report(update, backed_up))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
         }
     }
 
     pub fn message(&self) -> Option<Message> {
         match self {
-            Self::Error { .. } | Self::NoMissingRoots => Some(Message::CloseModal),
+            Self::Error { .. } | Self::NoMissingRoots | Self::ManifestUpdated { .. } => Some(Message::CloseModal),
             Self::ConfirmBackup { games } => Some(Message::BackupPrep {
                 preview: false,
                 games: games.clone(),
@@ -67,6 +89,7 @@ impl ModalTheme {
                 games: games.clone(),
             }),
             Self::ConfirmAddMissingRoots(missing) => Some(Message::ConfirmAddMissingRoots(missing.clone())),
+            Self::ConfirmMigrateRenamedGames(renamed) => Some(Message::MigrateRenamedGames(renamed.clone())),
             Self::PreparingBackupDir | Self::UpdatingManifest => None,
         }
     }
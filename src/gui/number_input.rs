@@ -13,7 +13,13 @@ use super::common::IcedButtonExt;
 pub struct NumberInput {}
 
 impl NumberInput {
-    pub fn view(&self, value: i32, label: String, range: RangeInclusive<i32>, change: fn(i32) -> Message) -> Container {
+    pub fn view(
+        &self,
+        value: i32,
+        label: String,
+        range: RangeInclusive<i32>,
+        change: impl Fn(i32) -> Message,
+    ) -> Container {
         Container::new(
             Row::new()
                 .spacing(5)
@@ -7,6 +7,7 @@ use crate::{
         ignored_items_editor::IgnoredItemsEditor,
         redirect_editor::{RedirectEditor, RedirectEditorRow},
         root_editor::{RootEditor, RootEditorRow},
+        secondary_manifest_editor::SecondaryManifestEditor,
         style,
     },
     lang::{Language, Translator},
@@ -22,6 +23,7 @@ pub struct OtherScreenComponent {
     pub ignored_items_editor: IgnoredItemsEditor,
     pub root_editor: RootEditor,
     pub redirect_editor: RedirectEditor,
+    pub secondary_manifest_editor: SecondaryManifestEditor,
 }
 
 impl OtherScreenComponent {
@@ -42,6 +44,7 @@ impl OtherScreenComponent {
             ignored_items_editor: IgnoredItemsEditor::new(config),
             root_editor,
             redirect_editor,
+            secondary_manifest_editor: SecondaryManifestEditor::new(config),
         }
     }
 
@@ -156,6 +159,20 @@ impl OtherScreenComponent {
                                     )
                                 }),
                         )
+                        .push(
+                            Column::new()
+                                .spacing(5)
+                                .push(Text::new(translator.secondary_manifests_label()))
+                                .push(
+                                    Container::new(
+                                        Column::new()
+                                            .padding(5)
+                                            .spacing(4)
+                                            .push(self.secondary_manifest_editor.view(config)),
+                                    )
+                                    .style(style::Container::GameListEntry),
+                                ),
+                        )
                         .push(
                             Column::new().spacing(5).push(Text::new(translator.roots_label())).push(
                                 Container::new(
@@ -2,14 +2,18 @@ use crate::{
     cache::Cache,
     config::Config,
     gui::{
-        common::{make_status_row, BrowseSubject, IcedButtonExt, Message, OngoingOperation, Screen, UndoSubject},
+        badge::Badge,
+        common::{
+            make_status_row, BrowseSubject, IcedButtonExt, IcedExtension, Message, OngoingOperation, Screen,
+            UndoSubject,
+        },
         game_list::GameList,
         icon::Icon,
         style,
     },
     lang::Translator,
     manifest::Manifest,
-    prelude::DuplicateDetector,
+    prelude::{parse_as_of, DuplicateDetector},
     shortcuts::TextHistory,
 };
 
@@ -21,6 +25,8 @@ pub struct RestoreScreenComponent {
     pub log: GameList,
     pub restore_source_history: TextHistory,
     pub duplicate_detector: DuplicateDetector,
+    /// Raw text for restoring each game as of some point in time.
+    pub as_of: String,
 }
 
 impl RestoreScreenComponent {
@@ -148,6 +154,31 @@ impl RestoreScreenComponent {
                                 .style(style::Button::Primary),
                         ),
                 )
+                .push(
+                    Row::new()
+                        .padding([0, 20, 0, 20])
+                        .spacing(20)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(translator.restore_as_of_label()))
+                        .push(
+                            TextInput::new(
+                                &translator.restore_as_of_placeholder(),
+                                &self.as_of,
+                                Message::EditedRestoreAsOf,
+                            )
+                            .style(style::TextInput)
+                            .padding(5),
+                        )
+                        .push_if(
+                            || !self.as_of.trim().is_empty() && parse_as_of(&self.as_of).is_none(),
+                            || Badge::new(&translator.badge_invalid()).view(),
+                        )
+                        .push(
+                            Icon::Close
+                                .as_button_small()
+                                .on_press(Message::EditedRestoreAsOf(String::new())),
+                        ),
+                )
                 .push(
                     self.log
                         .view(true, translator, config, manifest, &self.duplicate_detector, operation),
@@ -0,0 +1,97 @@
+use crate::{
+    config::{Config, SecondaryManifestConfigKind},
+    gui::{
+        common::{EditAction, Message, UndoSubject},
+        icon::Icon,
+        style,
+    },
+    shortcuts::TextHistory,
+};
+
+use crate::gui::widget::{Button, Column, Container, PickList, Row, TextInput, Undoable};
+use iced::{Alignment, Length};
+
+use super::common::IcedButtonExt;
+
+#[derive(Default)]
+pub struct SecondaryManifestEditorRow {
+    pub text_history: TextHistory,
+}
+
+impl SecondaryManifestEditorRow {
+    pub fn new(initial_text: &str) -> Self {
+        Self {
+            text_history: TextHistory::new(initial_text, 100),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SecondaryManifestEditor {
+    pub rows: Vec<SecondaryManifestEditorRow>,
+}
+
+impl SecondaryManifestEditor {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            rows: config
+                .manifest
+                .secondary
+                .iter()
+                .map(|x| SecondaryManifestEditorRow::new(&x.value()))
+                .collect(),
+        }
+    }
+
+    pub fn view(&self, config: &Config) -> Container {
+        let secondary = &config.manifest.secondary;
+        let content =
+            self.rows
+                .iter()
+                .enumerate()
+                .fold(Column::new().width(Length::Fill).spacing(5), |parent, (i, _)| {
+                    parent.push(
+                        Row::new()
+                            .spacing(20)
+                            .align_items(Alignment::Center)
+                            .push(
+                                Icon::ArrowUpward
+                                    .as_button_small()
+                                    .on_press_if(|| i > 0, || Message::EditedSecondaryManifest(EditAction::move_up(i))),
+                            )
+                            .push(Icon::ArrowDownward.as_button_small().on_press_if(
+                                || i < self.rows.len() - 1,
+                                || Message::EditedSecondaryManifest(EditAction::move_down(i)),
+                            ))
+                            .push(
+                                PickList::new(SecondaryManifestConfigKind::ALL, Some(secondary[i].kind()), move |v| {
+                                    Message::SelectedSecondaryManifestKind(i, v)
+                                })
+                                .style(style::PickList::Primary),
+                            )
+                            .push(Undoable::new(
+                                TextInput::new("", &secondary[i].value(), move |v| {
+                                    Message::EditedSecondaryManifest(EditAction::Change(i, v))
+                                })
+                                .style(style::TextInput)
+                                .width(Length::Fill)
+                                .padding(5),
+                                move |action| Message::UndoRedo(action, UndoSubject::SecondaryManifest(i)),
+                            ))
+                            .push(
+                                Button::new(Icon::RemoveCircle.as_text())
+                                    .on_press(Message::EditedSecondaryManifest(EditAction::Remove(i)))
+                                    .style(style::Button::Negative),
+                            ),
+                    )
+                });
+
+        Container::new(
+            content.push(
+                Button::new(Icon::AddCircle.as_text())
+                    .on_press(Message::EditedSecondaryManifest(EditAction::Add))
+                    .style(style::Button::Primary),
+            ),
+        )
+    }
+}
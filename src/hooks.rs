@@ -0,0 +1,377 @@
+use crate::{
+    config::{HookFailureMode, HooksConfig, OperationHooks},
+    prelude::{BackupInfo, ScanInfo, StrictPath},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+pub enum HookOperation {
+    #[serde(rename = "backup")]
+    Backup,
+    #[serde(rename = "restore")]
+    Restore,
+}
+
+impl HookOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Backup => "backup",
+            Self::Restore => "restore",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+pub enum HookEvent {
+    #[serde(rename = "before")]
+    Before,
+    #[serde(rename = "after")]
+    After,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Before => "before",
+            Self::After => "after",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct HookFailure {
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game: Option<String>,
+    pub operation: HookOperation,
+    pub event: HookEvent,
+    pub reason: String,
+}
+
+/// Runs the configured hooks for one backup or restore operation.
+#[derive(Clone, Debug)]
+pub struct HookRunner {
+    hooks: HooksConfig,
+    operation: HookOperation,
+    backup_dir: StrictPath,
+}
+
+impl HookRunner {
+    pub fn new(hooks: &HooksConfig, operation: HookOperation, backup_dir: &StrictPath) -> Self {
+        Self {
+            hooks: hooks.clone(),
+            operation,
+            backup_dir: backup_dir.clone(),
+        }
+    }
+
+    /// Whether these failures should stop what the hooks were guarding.
+    pub fn blocks(&self, failures: &[HookFailure]) -> bool {
+        self.hooks.on_failure == HookFailureMode::Error && !failures.is_empty()
+    }
+
+    pub fn failure_mode(&self) -> HookFailureMode {
+        self.hooks.on_failure
+    }
+
+    pub fn before_all(&self) -> Vec<HookFailure> {
+        self.run(self.global(), HookEvent::Before, None, None, None)
+    }
+
+    pub fn after_all(&self, successful: bool) -> Vec<HookFailure> {
+        self.run(self.global(), HookEvent::After, None, None, Some(successful))
+    }
+
+    pub fn before_game(&self, game: &str, backup: Option<&str>) -> Vec<HookFailure> {
+        match self.game(game) {
+            Some(hooks) => self.run(hooks, HookEvent::Before, Some(game), backup, None),
+            None => vec![],
+        }
+    }
+
+    pub fn after_game(&self, game: &str, backup: Option<&str>, successful: bool) -> Vec<HookFailure> {
+        match self.game(game) {
+            Some(hooks) => self.run(hooks, HookEvent::After, Some(game), backup, Some(successful)),
+            None => vec![],
+        }
+    }
+
+    /// Run `operation` for one game between its hooks.
+    /// Hooks only run for games that were found, and then `rescan` gets the earlier scan
+    /// so that it can pick up any changes that the hooks made to the game's files.
+    /// If a hook before it fails in error mode, then the operation is skipped and treated as failed.
+    /// `backup_after` is only called when the game has hooks.
+    pub fn around_game(
+        &self,
+        scan_info: ScanInfo,
+        backup_before: Option<&str>,
+        rescan: impl FnOnce(ScanInfo) -> ScanInfo,
+        operation: impl FnOnce(&ScanInfo) -> BackupInfo,
+        backup_after: impl FnOnce() -> Option<String>,
+    ) -> (ScanInfo, BackupInfo, Vec<HookFailure>) {
+        let game = scan_info.game_name.clone();
+        if !scan_info.found_anything() || !matches!(self.game(&game), Some(hooks) if !hooks.is_empty()) {
+            let backup_info = operation(&scan_info);
+            return (scan_info, backup_info, vec![]);
+        }
+
+        let mut failures = self.before_game(&game, backup_before);
+        if self.blocks(&failures) {
+            let backup_info = BackupInfo::all_failed(&scan_info);
+            return (scan_info, backup_info, failures);
+        }
+
+        let scan_info = rescan(scan_info);
+        let backup_info = operation(&scan_info);
+        failures.extend(self.after_game(&game, backup_after().as_deref(), backup_info.successful()));
+        (scan_info, backup_info, failures)
+    }
+
+    fn global(&self) -> &OperationHooks {
+        match self.operation {
+            HookOperation::Backup => &self.hooks.backup,
+            HookOperation::Restore => &self.hooks.restore,
+        }
+    }
+
+    fn game(&self, game: &str) -> Option<&OperationHooks> {
+        let hooks = self.hooks.games.get(game)?;
+        Some(match self.operation {
+            HookOperation::Backup => &hooks.backup,
+            HookOperation::Restore => &hooks.restore,
+        })
+    }
+
+    fn run(
+        &self,
+        hooks: &OperationHooks,
+        event: HookEvent,
+        game: Option<&str>,
+        backup: Option<&str>,
+        successful: Option<bool>,
+    ) -> Vec<HookFailure> {
+        let commands = match event {
+            HookEvent::Before => &hooks.before,
+            HookEvent::After => &hooks.after,
+        };
+
+        let mut failures = vec![];
+        for command in commands.iter().filter(|x| !x.trim().is_empty()) {
+            log::info!(
+                "running {} {} hook{}: {}",
+                event.as_str(),
+                self.operation.as_str(),
+                game.map(|x| format!(" for {x}")).unwrap_or_default(),
+                command
+            );
+            let reason = match self.execute(command, event, game, backup, successful) {
+                Ok(_) => continue,
+                Err(reason) => reason,
+            };
+            log::warn!("hook failed: {command} | {reason}");
+
+            failures.push(HookFailure {
+                command: command.clone(),
+                game: game.map(|x| x.to_string()),
+                operation: self.operation,
+                event,
+                reason,
+            });
+            if self.hooks.on_failure == HookFailureMode::Error {
+                break;
+            }
+        }
+        failures
+    }
+
+    /// Returns the hook's stdout, which is captured so that it can't mix with the `--api` output.
+    fn execute(
+        &self,
+        command: &str,
+        event: HookEvent,
+        game: Option<&str>,
+        backup: Option<&str>,
+        successful: Option<bool>,
+    ) -> Result<String, String> {
+        let mut process = shell(command);
+        process
+            .env("LUDUSAVI_OPERATION", self.operation.as_str())
+            .env("LUDUSAVI_EVENT", event.as_str())
+            .env("LUDUSAVI_GAME", game.unwrap_or_default())
+            .env("LUDUSAVI_BACKUP_DIR", self.backup_dir.render())
+            .env("LUDUSAVI_BACKUP_NAME", backup.unwrap_or_default())
+            .env(
+                "LUDUSAVI_STATUS",
+                match successful {
+                    None => "",
+                    Some(true) => "success",
+                    Some(false) => "failure",
+                },
+            )
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::inherit());
+
+        let output = process.output().map_err(|e| e.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if !stdout.trim().is_empty() {
+            log::info!("hook output: {command} | {}", stdout.trim_end());
+        }
+
+        if output.status.success() {
+            Ok(stdout)
+        } else {
+            Err(output.status.to_string())
+        }
+    }
+}
+
+/// Hooks are run through the system shell so that they can use pipes, `&&`, and so on.
+fn shell(command: &str) -> std::process::Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut process = std::process::Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut process = std::process::Command::new("sh");
+        process.args(["-c", command]);
+        process
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::GameHooks, prelude::ScannedFile, testing::s};
+    use maplit::{btreemap, hashset};
+    use pretty_assertions::assert_eq;
+
+    fn runner(hooks: HooksConfig) -> HookRunner {
+        HookRunner::new(&hooks, HookOperation::Backup, &StrictPath::new(s("/backups")))
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn can_pass_context_to_hooks() {
+        let runner = runner(HooksConfig {
+            games: btreemap! {
+                s("foo") => GameHooks {
+                    backup: OperationHooks {
+                        before: vec![s(r#"test "$LUDUSAVI_OPERATION $LUDUSAVI_EVENT $LUDUSAVI_GAME $LUDUSAVI_BACKUP_NAME" = "backup before foo bar""#)],
+                        after: vec![s(r#"test "$LUDUSAVI_EVENT $LUDUSAVI_BACKUP_DIR $LUDUSAVI_STATUS" = "after /backups success""#)],
+                    },
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        });
+
+        assert_eq!(Vec::<HookFailure>::new(), runner.before_game("foo", Some("bar")));
+        assert_eq!(Vec::<HookFailure>::new(), runner.after_game("foo", Some("bar"), true));
+        assert_eq!(Vec::<HookFailure>::new(), runner.before_game("other", None));
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn keeps_hook_output_out_of_stdout() {
+        let output = runner(HooksConfig::default()).execute("echo hi", HookEvent::Before, None, None, None);
+        assert_eq!(Ok(s("hi\n")), output);
+
+        let runner = runner(HooksConfig {
+            backup: OperationHooks {
+                before: vec![s("echo hi")],
+                after: vec![],
+            },
+            ..Default::default()
+        });
+        assert_eq!(Vec::<HookFailure>::new(), runner.before_all());
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn runs_game_hooks_before_scanning_again() {
+        let runner = runner(HooksConfig {
+            games: btreemap! {
+                s("foo") => GameHooks {
+                    backup: OperationHooks {
+                        before: vec![s("exit 1")],
+                        after: vec![],
+                    },
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        });
+        let found = ScanInfo {
+            game_name: s("foo"),
+            found_files: hashset! { ScannedFile::new("/old", 1, "1") },
+            ..Default::default()
+        };
+        let rescanned = ScanInfo {
+            game_name: s("foo"),
+            found_files: hashset! { ScannedFile::new("/new", 2, "2") },
+            ..Default::default()
+        };
+
+        let (scan_info, _, failures) = runner.around_game(
+            found,
+            None,
+            |_| rescanned.clone(),
+            |scan_info| {
+                assert_eq!(&rescanned, scan_info);
+                BackupInfo::default()
+            },
+            || None,
+        );
+        assert_eq!(rescanned, scan_info);
+        assert_eq!(1, failures.len());
+
+        let missing = ScanInfo {
+            game_name: s("foo"),
+            ..Default::default()
+        };
+        let (_, _, failures) = runner.around_game(
+            missing,
+            None,
+            |_| panic!("should not scan again"),
+            |_| BackupInfo::default(),
+            || None,
+        );
+        assert_eq!(Vec::<HookFailure>::new(), failures);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn can_report_failed_hooks() {
+        let hooks = HooksConfig {
+            backup: OperationHooks {
+                before: vec![s("exit 3"), s("true")],
+                after: vec![],
+            },
+            ..Default::default()
+        };
+
+        let warning = runner(hooks.clone());
+        let failures = warning.before_all();
+        assert_eq!(
+            vec![HookFailure {
+                command: s("exit 3"),
+                game: None,
+                operation: HookOperation::Backup,
+                event: HookEvent::Before,
+                reason: s("exit status: 3"),
+            }],
+            failures
+        );
+        assert!(!warning.blocks(&failures));
+
+        let error = runner(HooksConfig {
+            on_failure: HookFailureMode::Error,
+            ..hooks
+        });
+        assert!(error.blocks(&error.before_all()));
+        assert!(!error.blocks(&error.after_all(true)));
+    }
+}
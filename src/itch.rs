@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::{
+    config::RootsConfig,
+    manifest::Store,
+    prelude::{StrictPath, TitleFinder},
+};
+
+//
+/// Row of itch db/butler.db `caves` table, joined with `games` and `install_locations`
+//
+#[derive(Debug)]
+struct DatabaseCave {
+    title: String,
+    install_folder_name: String,
+    install_location: String,
+}
+
+//
+/// Main structure where games installed with the itch app are collected
+//
+#[derive(Clone, Default, Debug)]
+pub struct ItchGames {
+    games: HashMap<(RootsConfig, String), StrictPath>,
+}
+
+impl ItchGames {
+    pub fn get_install_dir(&self, root: &RootsConfig, game: &str) -> Option<&StrictPath> {
+        self.games.get(&(root.clone(), game.to_string()))
+    }
+
+    pub fn scan(roots: &[RootsConfig], title_finder: &TitleFinder) -> Self {
+        let mut instance = ItchGames::default();
+
+        for root in roots {
+            if root.store == Store::Itch {
+                instance.detect_games(root, title_finder);
+                log::trace!("scan found: {:#?}", instance.games);
+            }
+        }
+
+        instance
+    }
+
+    fn detect_games(&mut self, root: &RootsConfig, title_finder: &TitleFinder) {
+        let database_path = root.path.joined("db").joined("butler.db");
+        if !database_path.is_file() {
+            log::trace!(
+                "detect_games no such file '{}', itch probably not used yet... skipping",
+                database_path.interpret()
+            );
+            return;
+        }
+
+        let caves = match Self::read_database(&database_path) {
+            Ok(caves) => caves,
+            Err(e) => {
+                log::warn!(
+                    "detect_games aborting since it could not read {}: {}",
+                    database_path.interpret(),
+                    e
+                );
+                return;
+            }
+        };
+        log::trace!(
+            "detect_games found {} caves in {}",
+            caves.len(),
+            database_path.interpret()
+        );
+
+        for cave in caves {
+            let install_dir = StrictPath::new(cave.install_location).joined(&cave.install_folder_name);
+            let official_title = title_finder.find_one(&[cave.title.to_owned()], &None, &None, true, true, false);
+            self.memorize_game(root, &cave.title, official_title, install_dir);
+        }
+    }
+
+    fn read_database(path: &StrictPath) -> Result<Vec<DatabaseCave>, rusqlite::Error> {
+        let connection =
+            rusqlite::Connection::open_with_flags(path.interpret(), rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut statement = connection.prepare(
+            "SELECT games.title, caves.install_folder_name, install_locations.path FROM caves
+            JOIN games ON games.id = caves.game_id
+            JOIN install_locations ON install_locations.id = caves.install_location_id",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(DatabaseCave {
+                title: row.get(0)?,
+                install_folder_name: row.get(1)?,
+                install_location: row.get(2)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    fn memorize_game(
+        &mut self,
+        root: &RootsConfig,
+        itch_title: &str,
+        official_title: Option<String>,
+        install_dir: StrictPath,
+    ) {
+        if let Some(official) = official_title {
+            log::trace!(
+                "memorize_game memorizing info for '{}' (from: '{}'): install_dir={:?}",
+                official,
+                itch_title,
+                &install_dir
+            );
+            self.games.insert((root.clone(), official), install_dir);
+        } else {
+            let log_message = format!("Ignoring unrecognized itch game: '{}'", itch_title);
+            if std::env::var("LUDUSAVI_DEBUG").is_ok() {
+                eprintln!("{}", &log_message);
+            }
+            log::info!("{}", &log_message);
+
+            log::trace!(
+                "memorize_game memorizing info for '{}': install_dir={:?}",
+                itch_title,
+                &install_dir
+            );
+            self.games.insert((root.clone(), itch_title.to_string()), install_dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manifest::Manifest, serialization::ResourceFile, testing::repo};
+    use maplit::hashmap;
+    use pretty_assertions::assert_eq;
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            windows-game:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn title_finder() -> TitleFinder {
+        TitleFinder::new(&manifest(), &Default::default())
+    }
+
+    #[test]
+    fn scan_finds_nothing_when_folder_does_not_exist() {
+        let roots = vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/nonexistent", repo())),
+            store: Store::Itch,
+        }];
+        let games = ItchGames::scan(&roots, &title_finder());
+        assert_eq!(HashMap::new(), games.games);
+    }
+
+    #[test]
+    fn scan_finds_all_installed_games() {
+        let roots = vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/itch", repo())),
+            store: Store::Itch,
+        }];
+        let games = ItchGames::scan(&roots, &title_finder());
+        assert_eq!(
+            hashmap! {
+                (roots[0].clone(), "windows-game".to_string()) => StrictPath::new("/home/root/.config/itch/apps/windows-game".to_string()),
+                (roots[0].clone(), "Unknown Game".to_string()) => StrictPath::new("/mnt/games/itch/unknown-game".to_string()),
+            },
+            games.games,
+        );
+    }
+}
@@ -3,13 +3,21 @@ use fluent::{bundle::FluentBundle, FluentArgs, FluentResource};
 use intl_memoizer::concurrent::IntlLangMemoizer;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::sync::Mutex;
+use std::{collections::BTreeSet, sync::Mutex};
 use unic_langid::LanguageIdentifier;
 
 use crate::{
-    config::{BackupFormat, RedirectKind, SortKey, Theme, ZipCompression},
-    manifest::Store,
-    prelude::{Error, OperationStatus, OperationStepDecision, ScanChange, ScanChangeCount, StrictPath},
+    config::{
+        BackupFormat, GameBackupOverrides, Integration, RedirectKind, SecondaryManifestConfigKind, SortKey, Theme,
+        ZipCompression,
+    },
+    hooks::HookFailure,
+    layout::FileVersion,
+    manifest::{ManifestDiff, ManifestUpdate, Os, Store, Tag},
+    prelude::{
+        BackupExclusion, Error, FileDiff, FileDiffKind, OperationStatus, OperationStepDecision, PathCandidate,
+        PathCandidateCheck, PathCandidateSource, ScanChange, ScanChangeCount, StrictPath,
+    },
 };
 
 const PATH: &str = "path";
@@ -18,9 +26,20 @@ const PROCESSED_GAMES: &str = "processed-games";
 const PROCESSED_SIZE: &str = "processed-size";
 const TOTAL_GAMES: &str = "total-games";
 const TOTAL_SIZE: &str = "total-size";
+const URL: &str = "url";
+const USER: &str = "user";
+const PATTERN: &str = "pattern";
+const ADDED: &str = "added";
+const REMOVED: &str = "removed";
+const RENAMED: &str = "renamed";
+const CHANGED: &str = "changed";
+const FILES: &str = "files";
+const RULE: &str = "rule";
+const SIZE_LIMIT: &str = "size-limit";
 
 pub const ADD_SYMBOL: &str = "+";
 pub const CHANGE_SYMBOL: &str = "Δ";
+pub const REMOVE_SYMBOL: &str = "-";
 
 // TODO: Some are blocked by https://github.com/mtkennerly/ludusavi/issues/9.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -247,6 +266,18 @@ impl Translator {
         translate("button-comment")
     }
 
+    pub fn compare_button(&self) -> String {
+        translate("button-compare")
+    }
+
+    pub fn filter_button(&self) -> String {
+        translate("button-filter")
+    }
+
+    pub fn game_settings_button(&self) -> String {
+        translate("button-game-settings")
+    }
+
     pub fn handle_error(&self, error: &Error) -> String {
         match error {
             Error::ConfigInvalid { why } => self.config_is_invalid(why),
@@ -256,6 +287,12 @@ impl Translator {
             Error::CliUnableToRequestConfirmation => self.cli_unable_to_request_confirmation(),
             Error::CliBackupIdWithMultipleGames => self.cli_backup_id_with_multiple_games(),
             Error::CliInvalidBackupId => self.cli_invalid_backup_id(),
+            Error::CliFileNotInBackup { path } => self.cli_file_not_in_backup(path),
+            Error::CliAmbiguousGame { games } => self.cli_ambiguous_game(games),
+            Error::CliUnrecognizedSteamUser { user } => self.cli_unrecognized_steam_user(user),
+            Error::CliInvalidPattern { pattern, why } => self.cli_invalid_pattern(pattern, why),
+            Error::GameDidNotLaunch { why } => self.game_did_not_launch(why),
+            Error::ServerDidNotStart { why } => self.server_did_not_start(why),
             Error::SomeEntriesFailed => self.some_entries_failed(),
             Error::CannotPrepareBackupTarget { path } => self.cannot_prepare_backup_target(path),
             Error::RestorationSourceInvalid { path } => self.restoration_source_is_invalid(path),
@@ -263,6 +300,8 @@ impl Translator {
             Error::UnableToBrowseFileSystem => self.unable_to_browse_file_system(),
             Error::UnableToOpenDir(path) => self.unable_to_open_dir(path),
             Error::UnableToOpenUrl(url) => self.unable_to_open_url(url),
+            Error::UnableToMigrateRenamedGame { old, new } => self.unable_to_migrate_renamed_game(old, new),
+            Error::HookFailed { command, why } => self.hook_failed(command, why),
         }
     }
 
@@ -272,6 +311,32 @@ impl Translator {
         format!("{}\n{}", prefix, lines.join("\n"))
     }
 
+    pub fn cli_ambiguous_game(&self, games: &[String]) -> String {
+        let prefix = translate("cli-ambiguous-game");
+        let lines: Vec<_> = games.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_unrecognized_steam_user(&self, user: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(USER, user);
+        translate_args("cli-unrecognized-steam-user", &args)
+    }
+
+    pub fn cli_invalid_pattern(&self, pattern: &str, why: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATTERN, pattern);
+        format!("{}\n{}", translate_args("cli-invalid-pattern", &args), why)
+    }
+
+    pub fn game_did_not_launch(&self, why: &str) -> String {
+        format!("{}\n\n{}", translate("game-did-not-launch"), why)
+    }
+
+    pub fn server_did_not_start(&self, why: &str) -> String {
+        format!("{}\n\n{}", translate("server-did-not-start"), why)
+    }
+
     pub fn cli_unable_to_request_confirmation(&self) -> String {
         #[cfg(target_os = "windows")]
         let extra_note = translate("cli-unable-to-request-confirmation.winpty-workaround");
@@ -290,6 +355,12 @@ impl Translator {
         translate("cli-invalid-backup-id")
     }
 
+    pub fn cli_file_not_in_backup(&self, path: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, path.render());
+        translate_args("cli-file-not-in-backup", &args)
+    }
+
     pub fn some_entries_failed(&self) -> String {
         translate("some-entries-failed")
     }
@@ -342,12 +413,26 @@ impl Translator {
         translate("badge-ignored")
     }
 
+    pub fn badge_invalid(&self) -> String {
+        translate("badge-invalid")
+    }
+
     pub fn badge_redirected_from(&self, original: &StrictPath) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, original.render());
         translate_args("badge-redirected-from", &args)
     }
 
+    pub fn badge_excluded_by(&self, exclusion: &BackupExclusion) -> String {
+        let mut args = FluentArgs::new();
+        args.set(RULE, self.exclusion_rule(exclusion));
+        translate_args("badge-excluded-by", &args)
+    }
+
+    pub fn badge_over_size_limit(&self) -> String {
+        translate("badge-over-size-limit")
+    }
+
     pub fn badge_redirecting_to(&self, path: &StrictPath) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, path.render());
@@ -421,12 +506,220 @@ impl Translator {
         format!("    - {}", translate_args("cli-game-line-item-redirected", &args),)
     }
 
+    pub fn cli_game_line_item_excluded(&self, exclusion: &BackupExclusion) -> String {
+        let mut args = FluentArgs::new();
+        args.set(RULE, self.exclusion_rule(exclusion));
+        format!("    - {}", translate_args("cli-game-line-item-excluded", &args))
+    }
+
+    pub fn cli_game_size_limit_exceeded(&self, limit: u64) -> String {
+        let mut args = FluentArgs::new();
+        args.set(SIZE_LIMIT, self.adjusted_size(limit));
+        format!("  - {}", translate_args("cli-game-size-limit-exceeded", &args))
+    }
+
+    /// The settings that a game uses instead of the global ones, shown after its name.
+    pub fn cli_game_overrides(&self, overrides: &GameBackupOverrides) -> String {
+        let mut out = String::new();
+
+        let format = &overrides.format;
+        let mut parts = vec![];
+        if let Some(chosen) = &format.chosen {
+            parts.push(format!("{} {}", self.backup_format_field(), self.backup_format(chosen)));
+        }
+        if let Some(compression) = &format.zip.compression {
+            parts.push(format!(
+                "{} {}",
+                self.backup_compression_field(),
+                self.backup_compression(compression)
+            ));
+        }
+        if let Some(level) = format.level {
+            parts.push(format!("{} {}", self.backup_compression_level_field(), level));
+        }
+        if !parts.is_empty() {
+            out.push_str(&format!(" [{}]", parts.join(", ")));
+        }
+
+        let retention = &overrides.retention;
+        let mut parts = vec![];
+        if let Some(full) = retention.full {
+            parts.push(format!("{} {}", self.full_retention(), full));
+        }
+        if let Some(differential) = retention.differential {
+            parts.push(format!("{} {}", self.differential_retention(), differential));
+        }
+        if !parts.is_empty() {
+            out.push_str(&format!(" [{}]", parts.join(", ")));
+        }
+
+        out
+    }
+
+    /// A short description of the rule behind an exclusion.
+    fn exclusion_rule(&self, exclusion: &BackupExclusion) -> String {
+        match exclusion {
+            BackupExclusion::IgnoredPath(path) | BackupExclusion::GameIgnoredPath(path) => path.raw(),
+            BackupExclusion::SteamUser(user) => user.clone(),
+            BackupExclusion::Toggled => translate("badge-ignored"),
+            BackupExclusion::MaxFileSize(max) => format!("> {}", self.adjusted_size(*max)),
+        }
+    }
+
     pub fn cli_game_line_item_redirecting(&self, item: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, item);
         format!("    - {}", translate_args("cli-game-line-item-redirecting", &args),)
     }
 
+    pub fn cli_diff_header(&self, name: &str, from: &str, to: &str, bytes: i64) -> String {
+        format!("{} ({} -> {}) [{}]:", name, from, to, self.adjusted_size_delta(bytes))
+    }
+
+    pub fn cli_diff_line_item(&self, item: &str, diff: &FileDiff) -> String {
+        format!(
+            "  - [{}] {} [{}]",
+            self.diff_symbol(diff.kind),
+            item,
+            self.adjusted_size_delta(diff.size_delta())
+        )
+    }
+
+    pub fn cli_diff_no_changes(&self) -> String {
+        format!("  {}", translate("cli-diff-no-changes"))
+    }
+
+    pub fn cli_games_without_backup(&self) -> String {
+        format!("{}:", translate("games-without-backup"))
+    }
+
+    pub fn cli_hook_failures(&self) -> String {
+        format!("{}:", translate("hook-failures"))
+    }
+
+    pub fn cli_hook_failure(&self, failure: &HookFailure) -> String {
+        let mut line = format!("  - [{} {}] ", failure.event.as_str(), failure.operation.as_str());
+        if let Some(game) = &failure.game {
+            line.push_str(&format!("{game}: "));
+        }
+        format!("{line}{} ({})", failure.command, failure.reason)
+    }
+
+    pub fn cli_history_header(&self, name: &str, file: &str) -> String {
+        format!("{} ({}):", name, file)
+    }
+
+    pub fn cli_history_line_item(&self, version: &FileVersion) -> String {
+        format!(
+            "  - {} ({}) [{}] {}",
+            version.backup,
+            version.label(),
+            self.adjusted_size(version.size),
+            version.hash
+        )
+    }
+
+    pub fn cli_history_line_item_comment(&self, comment: &str) -> String {
+        format!("    - {}: {}", self.comment_label(), comment)
+    }
+
+    pub fn cli_manifest_info_entry(&self, entry: &str) -> String {
+        let lines: Vec<_> = entry.lines().map(|x| format!("    {}", x)).collect();
+        format!("  {}:\n{}", translate("cli-manifest-info-entry"), lines.join("\n"))
+    }
+
+    fn cli_manifest_info_files(&self, files: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(FILES, files);
+        translate_args("cli-manifest-info-files", &args)
+    }
+
+    pub fn cli_manifest_info_candidates(&self, candidates: &[(PathCandidate, PathCandidateCheck)]) -> String {
+        use std::fmt::Write;
+
+        if candidates.is_empty() {
+            return format!("  {}", translate("cli-manifest-info-no-candidates"));
+        }
+
+        let mut msg = format!("  {}:", translate("cli-manifest-info-candidates"));
+        for (candidate, check) in candidates {
+            let status = if check.exists {
+                self.cli_manifest_info_files(check.files)
+            } else {
+                translate("cli-manifest-info-not-found")
+            };
+            let _ = write!(msg, "\n    - {} [{}]", candidate.path.render(), status);
+
+            let source = match &candidate.source {
+                PathCandidateSource::Manifest(path) => path.clone(),
+                PathCandidateSource::SteamCloud => translate("cli-manifest-info-from-steam-cloud"),
+                PathCandidateSource::SteamScreenshots => translate("cli-manifest-info-from-steam-screenshots"),
+                PathCandidateSource::WineRegistry => translate("cli-manifest-info-from-wine-registry"),
+            };
+            let _ = write!(msg, "\n      - {}: {}", translate("cli-manifest-info-from"), source);
+
+            if let Some(root) = &candidate.root {
+                let _ = write!(
+                    msg,
+                    "\n      - {}: [{}] {}",
+                    translate("cli-manifest-info-root"),
+                    self.store(&root.store),
+                    root.path.render()
+                );
+            }
+
+            for (exclusion, files) in &check.excluded {
+                let reason = match exclusion {
+                    BackupExclusion::IgnoredPath(path) => format!(
+                        "{} ({})",
+                        translate("cli-manifest-info-excluded-ignored-path"),
+                        path.render()
+                    ),
+                    BackupExclusion::SteamUser(user) => {
+                        format!("{} ({})", translate("cli-manifest-info-excluded-steam-user"), user)
+                    }
+                    BackupExclusion::Toggled => translate("cli-manifest-info-excluded-toggled"),
+                    BackupExclusion::GameIgnoredPath(_) | BackupExclusion::MaxFileSize(_) => format!(
+                        "{} ({})",
+                        translate("cli-manifest-info-excluded-game-rule"),
+                        self.exclusion_rule(exclusion)
+                    ),
+                };
+                let _ = write!(msg, "\n      - {}: {}", reason, self.cli_manifest_info_files(*files));
+            }
+        }
+
+        msg
+    }
+
+    pub fn cli_history_no_versions(&self) -> String {
+        format!("  {}", translate("cli-history-no-versions"))
+    }
+
+    pub fn cli_watch_started(&self, total_games: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL_GAMES, total_games);
+        translate_args("cli-watch-started", &args)
+    }
+
+    pub fn cli_serve_started(&self, url: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(URL, url);
+        translate_args("cli-serve-started", &args)
+    }
+
+    pub fn cli_serve_without_token(&self) -> String {
+        translate("cli-serve-without-token")
+    }
+
+    pub fn diff_symbol(&self, kind: FileDiffKind) -> &'static str {
+        match kind {
+            FileDiffKind::Added => ADD_SYMBOL,
+            FileDiffKind::Removed => REMOVE_SYMBOL,
+            FileDiffKind::Modified => CHANGE_SYMBOL,
+        }
+    }
+
     pub fn cli_summary(&self, status: &OperationStatus, location: &StrictPath) -> String {
         let new_games = if status.changed_games.new > 0 {
             format!(" [{}{}]", crate::lang::ADD_SYMBOL, status.changed_games.new)
@@ -521,6 +814,83 @@ impl Translator {
         translate("updating-manifest")
     }
 
+    pub fn manifest_update_summary(&self, diff: &ManifestDiff) -> String {
+        let mut args = FluentArgs::new();
+        args.set(ADDED, diff.added.len());
+        args.set(REMOVED, diff.removed.len());
+        args.set(RENAMED, diff.renamed.len());
+        args.set(CHANGED, diff.changed.len());
+        translate_args("manifest-diff-summary", &args)
+    }
+
+    /// Describe a manifest update, marking any games that have existing backups.
+    pub fn manifest_update_report(&self, update: &ManifestUpdate, backed_up: &BTreeSet<String>) -> String {
+        use std::fmt::Write;
+
+        let mut args = FluentArgs::new();
+        args.set(URL, update.url.as_str());
+        let mut msg = if update.modified {
+            translate_args("manifest-updated", &args)
+        } else {
+            translate_args("manifest-up-to-date", &args)
+        };
+
+        let Some(diff) = &update.diff else {
+            return msg;
+        };
+        if diff.is_empty() {
+            let _ = write!(msg, "\n  {}", translate("manifest-diff-no-changes"));
+            return msg;
+        }
+
+        let mark = |name: &str| {
+            if backed_up.contains(name) {
+                format!(" [{}]", translate("manifest-diff-backed-up"))
+            } else {
+                "".to_string()
+            }
+        };
+
+        if !diff.added.is_empty() {
+            let _ = write!(msg, "\n  {}", translate("manifest-diff-added"));
+            for name in &diff.added {
+                let _ = write!(msg, "\n    - {}", name);
+            }
+        }
+        if !diff.removed.is_empty() {
+            let _ = write!(msg, "\n  {}", translate("manifest-diff-removed"));
+            for name in &diff.removed {
+                let _ = write!(msg, "\n    - {}{}", name, mark(name));
+            }
+        }
+        if !diff.renamed.is_empty() {
+            let _ = write!(msg, "\n  {}", translate("manifest-diff-renamed"));
+            for (old, new) in &diff.renamed {
+                let _ = write!(msg, "\n    - {} -> {}{}", old, new, mark(old));
+            }
+        }
+        if !diff.changed.is_empty() {
+            let _ = write!(msg, "\n  {}", translate("manifest-diff-changed"));
+            for (name, paths) in &diff.changed {
+                let old_name = diff
+                    .renamed
+                    .iter()
+                    .find(|(_, new)| *new == name)
+                    .map(|(old, _)| old)
+                    .unwrap_or(name);
+                let _ = write!(msg, "\n    - {}{}", name, mark(old_name));
+                for path in paths.added_files.iter().chain(&paths.added_registry) {
+                    let _ = write!(msg, "\n      {} {}", ADD_SYMBOL, path);
+                }
+                for path in paths.removed_files.iter().chain(&paths.removed_registry) {
+                    let _ = write!(msg, "\n      {} {}", REMOVE_SYMBOL, path);
+                }
+            }
+        }
+
+        msg
+    }
+
     pub fn confirm_add_missing_roots(&self, roots: &[crate::config::RootsConfig]) -> String {
         use std::fmt::Write;
         let mut msg = translate("confirm-add-missing-roots") + "\n";
@@ -532,6 +902,21 @@ impl Translator {
         msg
     }
 
+    pub fn confirm_migrate_renamed_games(&self, renamed: &std::collections::BTreeMap<String, String>) -> String {
+        use std::fmt::Write;
+        let mut msg = translate("confirm-migrate-renamed-games") + "\n";
+
+        for (old, new) in renamed {
+            let _ = &write!(msg, "\n{} -> {}", old, new);
+        }
+
+        msg
+    }
+
+    pub fn no_renamed_games(&self) -> String {
+        translate("no-renamed-games")
+    }
+
     pub fn add_game_button(&self) -> String {
         translate("button-add-game")
     }
@@ -616,12 +1001,25 @@ impl Translator {
         format!("{}\n\n{}", translate("unable-to-open-url"), url)
     }
 
+    pub fn unable_to_migrate_renamed_game(&self, old: &str, new: &str) -> String {
+        format!("{}\n\n{} -> {}", translate("unable-to-migrate-renamed-game"), old, new)
+    }
+
+    pub fn hook_failed(&self, command: &str, why: &str) -> String {
+        format!("{}\n\n{}\n{}", translate("hook-failed"), command, why)
+    }
+
     pub fn adjusted_size(&self, bytes: u64) -> String {
         let byte = Byte::from_bytes(bytes.into());
         let adjusted_byte = byte.get_appropriate_unit(true);
         adjusted_byte.to_string()
     }
 
+    pub fn adjusted_size_delta(&self, bytes: i64) -> String {
+        let sign = if bytes < 0 { "-" } else { "+" };
+        format!("{}{}", sign, self.adjusted_size(bytes.unsigned_abs()))
+    }
+
     pub fn processed_games(&self, status: &OperationStatus) -> String {
         let mut args = FluentArgs::new();
         args.set(TOTAL_GAMES, status.total_games);
@@ -664,6 +1062,14 @@ impl Translator {
         translate("field-restore-source")
     }
 
+    pub fn restore_as_of_label(&self) -> String {
+        translate("field-restore-as-of")
+    }
+
+    pub fn restore_as_of_placeholder(&self) -> String {
+        translate("field-restore-as-of.placeholder")
+    }
+
     pub fn custom_files_label(&self) -> String {
         translate("field-custom-files")
     }
@@ -672,6 +1078,18 @@ impl Translator {
         translate("field-custom-registry")
     }
 
+    pub fn custom_install_dir_label(&self) -> String {
+        translate("field-custom-install-dir")
+    }
+
+    pub fn custom_steam_id_label(&self) -> String {
+        translate("field-custom-steam-id")
+    }
+
+    pub fn custom_gog_id_label(&self) -> String {
+        translate("field-custom-gog-id")
+    }
+
     pub fn search_label(&self) -> String {
         translate("field-search")
     }
@@ -680,12 +1098,54 @@ impl Translator {
         translate("field-sort")
     }
 
+    pub fn secondary_manifest_kind(&self, kind: &SecondaryManifestConfigKind) -> String {
+        translate(match kind {
+            SecondaryManifestConfigKind::Local => "secondary-manifest-local",
+            SecondaryManifestConfigKind::Remote => "secondary-manifest-remote",
+        })
+    }
+
+    pub fn integration(&self, integration: &Integration) -> String {
+        translate(match integration {
+            Integration::Override => "integration-override",
+            Integration::Extend => "integration-extend",
+        })
+    }
+
+    pub fn constraint_os(&self, os: &Option<Os>) -> String {
+        translate(match os {
+            None => "constraint-any-os",
+            Some(Os::Windows) => "os-windows",
+            Some(Os::Linux) => "os-linux",
+            Some(Os::Mac) => "os-mac",
+            Some(Os::Other) => "os-other",
+        })
+    }
+
+    pub fn constraint_store(&self, store: &Option<Store>) -> String {
+        match store {
+            None => translate("constraint-any-store"),
+            Some(store) => self.store(store),
+        }
+    }
+
+    pub fn tag(&self, tag: &Tag) -> String {
+        translate(match tag {
+            Tag::Save => "tag-save",
+            Tag::Config => "tag-config",
+            Tag::Other => "tag-other",
+        })
+    }
+
     pub fn store(&self, store: &Store) -> String {
         translate(match store {
+            Store::Bottles => "store-bottles",
             Store::Epic => "store-epic",
             Store::Gog => "store-gog",
             Store::GogGalaxy => "store-gog-galaxy",
             Store::Heroic => "store-heroic",
+            Store::Itch => "store-itch",
+            Store::Lutris => "store-lutris",
             Store::Microsoft => "store-microsoft",
             Store::Origin => "store-origin",
             Store::Prime => "store-prime",
@@ -747,6 +1207,14 @@ impl Translator {
         translate("field-redirect-target.placeholder")
     }
 
+    pub fn ignored_path_placeholder(&self) -> String {
+        translate("field-backup-excluded-path.placeholder")
+    }
+
+    pub fn ignored_registry_placeholder(&self) -> String {
+        translate("field-backup-excluded-registry.placeholder")
+    }
+
     pub fn custom_game_name_placeholder(&self) -> String {
         translate("game-name")
     }
@@ -763,6 +1231,10 @@ impl Translator {
         translate("field-roots")
     }
 
+    pub fn secondary_manifests_label(&self) -> String {
+        translate("field-secondary-manifests")
+    }
+
     pub fn ignored_items_label(&self) -> String {
         translate("field-backup-excluded-items")
     }
@@ -807,6 +1279,34 @@ impl Translator {
         translate("label-comment")
     }
 
+    pub fn game_max_file_size_label(&self) -> String {
+        translate("field-game-max-file-size")
+    }
+
+    pub fn game_max_total_size_label(&self) -> String {
+        translate("field-game-max-total-size")
+    }
+
+    pub fn size_limit_placeholder(&self) -> String {
+        translate("field-game-max-total-size.placeholder")
+    }
+
+    pub fn game_override_retention_label(&self) -> String {
+        translate("field-game-override-retention")
+    }
+
+    pub fn game_override_format_label(&self) -> String {
+        translate("field-game-override-format")
+    }
+
+    pub fn live_files_label(&self) -> String {
+        translate("label-live-files")
+    }
+
+    pub fn no_backup_label(&self) -> String {
+        translate("label-no-backup")
+    }
+
     pub fn new_tooltip(&self) -> String {
         translate("label-new")
     }
@@ -866,6 +1366,19 @@ impl Translator {
         }
     }
 
+    pub fn notify_games_without_backup(&self, games: &[String]) -> String {
+        format!("{}: {}", translate("games-without-backup"), games.join(", "))
+    }
+
+    pub fn notify_hook_failures(&self, failures: &[HookFailure]) -> String {
+        let commands: Vec<_> = failures.iter().map(|x| x.command.as_str()).collect();
+        format!("{}: {}", translate("hook-failures"), commands.join(", "))
+    }
+
+    pub fn notify_file_version_restored(&self) -> String {
+        translate("file-version-restored")
+    }
+
     pub fn suffix_no_confirmation(&self) -> String {
         translate("suffix-no-confirmation")
     }
@@ -0,0 +1,50 @@
+use crate::{
+    bottles::BottlesGames,
+    config::RootsConfig,
+    heroic::HeroicGames,
+    itch::ItchGames,
+    lutris::LutrisGames,
+    manifest::Store,
+    prelude::{StrictPath, TitleFinder},
+};
+
+//
+/// Games found through launchers that track their own install folders (and sometimes Wine prefixes)
+//
+#[derive(Clone, Default, Debug)]
+pub struct Launchers {
+    heroic: HeroicGames,
+    lutris: LutrisGames,
+    bottles: BottlesGames,
+    itch: ItchGames,
+}
+
+impl Launchers {
+    pub fn get_prefix(&self, root: &RootsConfig, game: &str) -> Option<&StrictPath> {
+        match root.store {
+            Store::Heroic => self.heroic.get_prefix(root, game),
+            Store::Lutris => self.lutris.get_prefix(root, game),
+            Store::Bottles => self.bottles.get_prefix(root, game),
+            _ => None,
+        }
+    }
+
+    pub fn get_install_dir(&self, root: &RootsConfig, game: &str) -> Option<&StrictPath> {
+        match root.store {
+            Store::Heroic => self.heroic.get_install_dir(root, game),
+            Store::Lutris => self.lutris.get_install_dir(root, game),
+            Store::Bottles => self.bottles.get_install_dir(root, game),
+            Store::Itch => self.itch.get_install_dir(root, game),
+            _ => None,
+        }
+    }
+
+    pub fn scan(roots: &[RootsConfig], title_finder: &TitleFinder, legendary: Option<StrictPath>) -> Self {
+        Self {
+            heroic: HeroicGames::scan(roots, title_finder, legendary),
+            lutris: LutrisGames::scan(roots, title_finder),
+            bottles: BottlesGames::scan(roots, title_finder),
+            itch: ItchGames::scan(roots, title_finder),
+        }
+    }
+}
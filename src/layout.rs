@@ -8,7 +8,9 @@ use chrono::{Datelike, Timelike};
 use crate::{
     config::{BackupFormat, BackupFormats, RedirectConfig, Retention, ZipCompression},
     path::StrictPath,
-    prelude::{game_file_target, BackupId, BackupInfo, ScanChange, ScanInfo, ScannedFile, ScannedRegistry},
+    prelude::{
+        game_file_target, BackupId, BackupInfo, Error, GameDiff, ScanChange, ScanInfo, ScannedFile, ScannedRegistry,
+    },
 };
 
 const SAFE: &str = "_";
@@ -266,6 +268,36 @@ impl DifferentialBackup {
     }
 }
 
+/// A distinct version of a file, as stored in one of the game's backups.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileVersion {
+    pub backup: String,
+    pub when: chrono::DateTime<chrono::Utc>,
+    pub comment: Option<String>,
+    pub size: u64,
+    pub hash: String,
+}
+
+impl FileVersion {
+    pub fn label(&self) -> String {
+        chrono::DateTime::<chrono::Local>::from(self.when)
+            .format("%Y-%m-%dT%H:%M:%S")
+            .to_string()
+    }
+}
+
+/// Only the contents matter when deciding whether to make a new backup.
+/// Other fields, like the Steam user, may be missing from backups made by older versions.
+fn same_file_contents(
+    a: &BTreeMap<String, IndividualMappingFile>,
+    b: &BTreeMap<String, IndividualMappingFile>,
+) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|((k1, v1), (k2, v2))| k1 == k2 && v1.hash == v2.hash && v1.size == v2.size)
+}
+
 fn default_backup_list() -> VecDeque<FullBackup> {
     VecDeque::from(vec![FullBackup {
         name: ".".to_string(),
@@ -277,6 +309,9 @@ fn default_backup_list() -> VecDeque<FullBackup> {
 pub struct IndividualMappingFile {
     pub hash: String,
     pub size: u64,
+    /// For files in Steam's `userdata` folder, the account ID of the user they belong to.
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "steamUserId")]
+    pub steam_user_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -369,6 +404,23 @@ impl IndividualMapping {
         full.map(|x| (x, x.children.last()))
     }
 
+    fn backup_as_of(&self, time: &chrono::DateTime<chrono::Utc>) -> Option<(&FullBackup, Option<&DifferentialBackup>)> {
+        let mut found = None;
+
+        for full in &self.backups {
+            if full.when <= *time {
+                found = Some((full, None));
+            }
+            for diff in &full.children {
+                if diff.when <= *time {
+                    found = Some((full, Some(diff)));
+                }
+            }
+        }
+
+        found
+    }
+
     pub fn save(&self, file: &StrictPath) {
         let new_content = serde_yaml::to_string(&self).unwrap();
 
@@ -497,7 +549,7 @@ impl GameLayout {
 
     pub fn verify_id(&self, id: &BackupId) -> BackupId {
         match id {
-            BackupId::Latest => id.clone(),
+            BackupId::Latest | BackupId::AsOf(_) => id.clone(),
             BackupId::Named(name) => {
                 for full in &self.mapping.backups {
                     for diff in &full.children {
@@ -517,6 +569,7 @@ impl GameLayout {
     pub fn find_by_id(&self, id: &BackupId) -> Option<(&FullBackup, Option<&DifferentialBackup>)> {
         match id {
             BackupId::Latest => self.mapping.latest_backup(),
+            BackupId::AsOf(time) => self.mapping.backup_as_of(time),
             BackupId::Named(id) => {
                 let mut full = None;
                 let mut diff = None;
@@ -565,6 +618,7 @@ impl GameLayout {
                 found_registry_keys: Default::default(),
                 available_backups: vec![],
                 backup: None,
+                max_total_size: None,
             })
         }
     }
@@ -582,6 +636,72 @@ impl GameLayout {
         backups
     }
 
+    /// Find the backup that was created just before the specified one.
+    pub fn previous_backup(&self, id: &BackupId) -> Option<Backup> {
+        let current = self.find_by_id_flattened(id)?;
+        let backups = self.restorable_backups_flattened();
+        let index = backups.iter().position(|x| x.name() == current.name())?;
+        if index == 0 {
+            None
+        } else {
+            backups.get(index - 1).cloned()
+        }
+    }
+
+    /// Compare the files of two backups.
+    /// When `from` is not set, every file in the `to` backup is considered new.
+    pub fn diff(&self, from: Option<&BackupId>, to: &BackupId) -> GameDiff {
+        let old = match from {
+            Some(from) => self.restorable_files(from, false, &[]),
+            None => Default::default(),
+        };
+        let new = self.restorable_files(to, false, &[]);
+        GameDiff::new(&old, &new)
+    }
+
+    /// List each version of a file across all backups, from oldest to newest.
+    /// A version is listed for the backup where it first appears,
+    /// and consecutive backups that contain identical content are collapsed into the first one.
+    /// If the file is missing from a backup, then the next backup to contain it starts a new version.
+    pub fn file_history(&self, original_path: &StrictPath) -> Vec<FileVersion> {
+        let key = original_path.render();
+        let mut versions: Vec<FileVersion> = vec![];
+        let mut previous: Option<String> = None;
+
+        let mut add = |backup: &str,
+                       when: &chrono::DateTime<chrono::Utc>,
+                       comment: &Option<String>,
+                       file: Option<&IndividualMappingFile>| {
+            let hash = file.map(|x| x.hash.clone());
+            if let Some(file) = file {
+                if previous != hash {
+                    versions.push(FileVersion {
+                        backup: backup.to_string(),
+                        when: *when,
+                        comment: comment.clone(),
+                        size: file.size,
+                        hash: file.hash.clone(),
+                    });
+                }
+            }
+            previous = hash;
+        };
+
+        for full in &self.mapping.backups {
+            let full_file = full.files.get(&key);
+            add(&full.name, &full.when, &full.comment, full_file);
+            for diff in &full.children {
+                let file = match diff.files.get(&key) {
+                    None => full_file,
+                    Some(file) => file.as_ref(),
+                };
+                add(&diff.name, &diff.when, &diff.comment, file);
+            }
+        }
+
+        versions
+    }
+
     pub fn restorable_files(
         &self,
         id: &BackupId,
@@ -637,6 +757,7 @@ impl GameLayout {
                         redirected,
                         original_path: Some(original_path),
                         ignored: false,
+                        excluded: None,
                         container: None,
                     });
                 }
@@ -653,6 +774,7 @@ impl GameLayout {
                         redirected,
                         original_path: Some(original_path),
                         ignored: false,
+                        excluded: None,
                         container: Some(self.path.joined(&backup.name)),
                     });
                 }
@@ -690,6 +812,7 @@ impl GameLayout {
                         redirected,
                         original_path: Some(original_path),
                         ignored: false,
+                        excluded: None,
                         container: None,
                     });
                 }
@@ -706,6 +829,7 @@ impl GameLayout {
                         redirected,
                         original_path: Some(original_path),
                         ignored: false,
+                        excluded: None,
                         container: Some(self.path.joined(&backup.name)),
                     });
                 }
@@ -746,6 +870,7 @@ impl GameLayout {
                     path,
                     original_path,
                     ignored: false,
+                    excluded: None,
                     container: None,
                     redirected: None,
                 });
@@ -871,7 +996,7 @@ impl GameLayout {
             }
         };
 
-        prior_files != current_files || prior_registry != current_registry
+        !same_file_contents(&prior_files, &current_files) || prior_registry != current_registry
     }
 
     fn generate_file_friendly_timestamp(now: &chrono::DateTime<chrono::Utc>) -> String {
@@ -944,6 +1069,7 @@ impl GameLayout {
                 IndividualMappingFile {
                     hash: file.hash.clone(),
                     size: file.size,
+                    steam_user_id: crate::steam::user_id_in_path(file.effective()),
                 },
             );
         }
@@ -983,6 +1109,7 @@ impl GameLayout {
                 Some(IndividualMappingFile {
                     hash: file.hash.clone(),
                     size: file.size,
+                    steam_user_id: crate::steam::user_id_in_path(file.effective()),
                 }),
             );
         }
@@ -1024,7 +1151,12 @@ impl GameLayout {
         }
     }
 
-    fn execute_backup_as_simple(&mut self, backup: &Backup, scan: &ScanInfo) -> BackupInfo {
+    fn execute_backup_as_simple(
+        &mut self,
+        backup: &Backup,
+        scan: &ScanInfo,
+        on_file: &dyn Fn(&ScannedFile, bool),
+    ) -> BackupInfo {
         let mut backup_info = BackupInfo::default();
 
         let mut relevant_files = vec![];
@@ -1043,10 +1175,12 @@ impl GameLayout {
                     target_file.raw()
                 );
                 relevant_files.push(target_file);
+                on_file(file, false);
                 continue;
             }
             if let Err(_e) = file.path.copy_to_path(&self.mapping.name, 0, &target_file) {
                 backup_info.failed_files.insert(file.clone());
+                on_file(file, true);
                 continue;
             }
             log::info!(
@@ -1056,6 +1190,7 @@ impl GameLayout {
                 target_file.raw()
             );
             relevant_files.push(target_file);
+            on_file(file, false);
         }
 
         #[cfg(target_os = "windows")]
@@ -1078,11 +1213,19 @@ impl GameLayout {
         backup_info
     }
 
-    fn execute_backup_as_zip(&mut self, backup: &Backup, scan: &ScanInfo, format: &BackupFormats) -> BackupInfo {
+    fn execute_backup_as_zip(
+        &mut self,
+        backup: &Backup,
+        scan: &ScanInfo,
+        format: &BackupFormats,
+        on_file: &dyn Fn(&ScannedFile, bool),
+    ) -> BackupInfo {
         let mut backup_info = BackupInfo::default();
 
-        let fail_file =
-            |file: &ScannedFile, backup_info: &mut BackupInfo| backup_info.failed_files.insert(file.clone());
+        let fail_file = |file: &ScannedFile, backup_info: &mut BackupInfo| {
+            backup_info.failed_files.insert(file.clone());
+            on_file(file, true);
+        };
         let fail_all = |backup_info: &mut BackupInfo| {
             for file in &scan.found_files {
                 backup_info.failed_files.insert(file.clone());
@@ -1195,6 +1338,7 @@ impl GameLayout {
                         file.path.raw(),
                         &target_file_id
                     );
+                    on_file(file, false);
                     break;
                 }
                 if let Err(e) = zip.write_all(&buffer[0..read]) {
@@ -1245,13 +1389,19 @@ impl GameLayout {
         }
     }
 
-    fn execute_backup(&mut self, backup: &Backup, scan: &ScanInfo, format: &BackupFormats) -> BackupInfo {
+    fn execute_backup(
+        &mut self,
+        backup: &Backup,
+        scan: &ScanInfo,
+        format: &BackupFormats,
+        on_file: &dyn Fn(&ScannedFile, bool),
+    ) -> BackupInfo {
         if backup.only_inherits_and_overrides() {
             BackupInfo::default()
         } else {
             match format.chosen {
-                BackupFormat::Simple => self.execute_backup_as_simple(backup, scan),
-                BackupFormat::Zip => self.execute_backup_as_zip(backup, scan, format),
+                BackupFormat::Simple => self.execute_backup_as_simple(backup, scan, on_file),
+                BackupFormat::Zip => self.execute_backup_as_zip(backup, scan, format, on_file),
             }
         }
     }
@@ -1279,11 +1429,13 @@ impl GameLayout {
         let mut registry = IndividualMappingRegistry::default();
 
         for file in self.restorable_files_in_simple(&backup.name) {
+            let original_path = file.original_path.unwrap();
             files.insert(
-                file.original_path.unwrap().render(),
+                original_path.render(),
                 IndividualMappingFile {
                     hash: file.path.sha1(),
                     size: file.path.size(),
+                    steam_user_id: crate::steam::user_id_in_path(&original_path),
                 },
             );
         }
@@ -1304,11 +1456,14 @@ impl GameLayout {
         }
     }
 
+    /// `on_file` is called as soon as each file has been backed up,
+    /// along with whether that failed.
     pub fn back_up(
         &mut self,
         scan: &ScanInfo,
         now: &chrono::DateTime<chrono::Utc>,
         format: &BackupFormats,
+        on_file: &dyn Fn(&ScannedFile, bool),
     ) -> BackupInfo {
         self.migrate_legacy_backup();
         match self.plan_backup(scan, now, format) {
@@ -1323,7 +1478,7 @@ impl GameLayout {
                     backup.kind(),
                     backup.name()
                 );
-                let backup_info = self.execute_backup(&backup, scan, format);
+                let backup_info = self.execute_backup(&backup, scan, format, on_file);
                 backup.prune_failures(&backup_info);
                 if self.need_backup(&backup) {
                     self.insert_backup(backup.clone());
@@ -1335,30 +1490,14 @@ impl GameLayout {
         }
     }
 
-    pub fn restore(&self, scan: &ScanInfo) -> BackupInfo {
+    /// `on_file` is called as soon as each file has been restored,
+    /// along with whether that failed.
+    pub fn restore(&self, scan: &ScanInfo, on_file: &dyn Fn(&ScannedFile, bool)) -> BackupInfo {
         log::trace!("[{}] beginning restore", &scan.game_name);
 
-        let mut failed_files = std::collections::HashSet::new();
+        let failed_files = self.restore_files(scan, on_file);
         let failed_registry = std::collections::HashSet::new();
 
-        for file in &scan.found_files {
-            let original_path = some_or_continue!(&file.original_path);
-            let target = file.effective();
-
-            if let Err(e) = match &file.container {
-                None => self.restore_file_from_simple(target, file),
-                Some(container) => self.restore_file_from_zip(target, file, container),
-            } {
-                log::error!(
-                    "[{}] failed to restore: {} -> {} | {e}",
-                    self.mapping.name,
-                    original_path.raw(),
-                    target.raw()
-                );
-                failed_files.insert(file.clone());
-            }
-        }
-
         #[cfg(target_os = "windows")]
         {
             use crate::registry::Hives;
@@ -1381,6 +1520,145 @@ impl GameLayout {
         }
     }
 
+    /// Restore only the file with this original path from a restoration scan,
+    /// without touching any other files or the registry.
+    /// Afterwards, the scan only contains that file.
+    /// Fails if the scanned backup does not contain the file.
+    pub fn restore_file_version(&self, scan: &mut ScanInfo, original_path: &StrictPath) -> Result<BackupInfo, Error> {
+        let rendered = original_path.render();
+        scan.found_files
+            .retain(|x| x.original_path.as_ref().map(|x| x.render()) == Some(rendered.clone()));
+        scan.found_registry_keys.clear();
+
+        if scan.found_files.is_empty() {
+            return Err(Error::CliFileNotInBackup {
+                path: original_path.clone(),
+            });
+        }
+
+        Ok(BackupInfo {
+            failed_files: self.restore_files(scan, &|_, _| {}),
+            failed_registry: std::collections::HashSet::new(),
+        })
+    }
+
+    /// Point each file at a location inside of `target`,
+    /// organized by its original path, so that `extract` can write it there.
+    pub fn redirect_for_extraction(&self, scan: &mut ScanInfo, target: &StrictPath) {
+        scan.found_files = scan
+            .found_files
+            .drain()
+            .map(|mut file| {
+                if let Some(original_path) = &file.original_path {
+                    file.redirected = Some(target.joined(&self.mapping.game_file_for_zip_immutable(original_path)));
+                }
+                file
+            })
+            .collect();
+    }
+
+    /// Ignore any files from other Steam users' `userdata` folders than `user`,
+    /// and optionally restore that user's files to the `target` user's folder instead.
+    /// Files that don't belong to a Steam user are left alone.
+    pub fn retarget_steam_user(&self, scan: &mut ScanInfo, user: &str, target: Option<&str>) {
+        let backup = scan.backup.as_ref().and_then(|x| self.find_by_id(&x.id()));
+
+        scan.found_files = scan
+            .found_files
+            .drain()
+            .map(|mut file| {
+                let key = file.original_path().render();
+                let recorded = backup.and_then(|(full, diff)| {
+                    match diff.and_then(|diff| diff.files.get(&key)) {
+                        Some(file) => file.as_ref(),
+                        None => full.files.get(&key),
+                    }
+                    .and_then(|x| x.steam_user_id.clone())
+                });
+                // Backups from before the user was recorded can still be handled by their path.
+                let Some(owner) = recorded.or_else(|| crate::steam::user_id_in_path(file.original_path())) else {
+                    return file;
+                };
+
+                if owner != user {
+                    file.ignored = true;
+                } else if let Some(target) = target {
+                    let redirected = crate::steam::retarget_user(file.effective(), &owner, target);
+                    file.change = ScanChange::evaluate_restore(&redirected, &file.hash);
+                    file.redirected = Some(redirected);
+                }
+                file
+            })
+            .collect();
+    }
+
+    /// Copy the files of a backup to wherever `redirect_for_extraction` pointed them,
+    /// and put the backed up registry content (if any) in `target`.
+    pub fn extract(&self, scan: &ScanInfo, target: &StrictPath) -> BackupInfo {
+        log::trace!("[{}] beginning extraction", &scan.game_name);
+
+        let failed_files = self.restore_files(scan, &|_, _| {});
+        let failed_registry = std::collections::HashSet::new();
+
+        if let Some(backup) = scan.backup.as_ref() {
+            if let Some(registry_content) = self.registry_content(&backup.id()) {
+                let registry_file = target.joined("registry.yaml");
+                if let Err(e) = registry_file
+                    .create_parent_dir()
+                    .and_then(|_| std::fs::write(registry_file.interpret(), registry_content.as_bytes()))
+                {
+                    log::error!(
+                        "[{}] failed to extract registry: {} | {e}",
+                        self.mapping.name,
+                        registry_file.raw()
+                    );
+                }
+            }
+        }
+
+        log::trace!("[{}] completed extraction", &scan.game_name);
+
+        BackupInfo {
+            failed_files,
+            failed_registry,
+        }
+    }
+
+    fn restore_files(
+        &self,
+        scan: &ScanInfo,
+        on_file: &dyn Fn(&ScannedFile, bool),
+    ) -> std::collections::HashSet<ScannedFile> {
+        let mut failed_files = std::collections::HashSet::new();
+
+        for file in &scan.found_files {
+            let original_path = some_or_continue!(&file.original_path);
+            if file.ignored {
+                log::debug!("[{}] skipped: {}", self.mapping.name, original_path.raw());
+                continue;
+            }
+            let target = file.effective();
+
+            if let Err(e) = match &file.container {
+                None => self.restore_file_from_simple(target, file),
+                Some(container) => self.restore_file_from_zip(target, file, container),
+            } {
+                log::error!(
+                    "[{}] failed to restore: {} -> {} | {e}",
+                    self.mapping.name,
+                    original_path.raw(),
+                    target.raw()
+                );
+                failed_files.insert(file.clone());
+                on_file(file, true);
+            } else {
+                on_file(file, false);
+            }
+        }
+
+        failed_files
+    }
+
     fn restore_file_from_simple(
         &self,
         target: &StrictPath,
@@ -1592,6 +1870,8 @@ pub struct BackupLayout {
     games: std::collections::HashMap<String, StrictPath>,
     games_lowercase: std::collections::HashMap<String, StrictPath>,
     retention: Retention,
+    /// Games that don't use the default `retention`.
+    game_retention: std::collections::BTreeMap<String, Retention>,
 }
 
 impl BackupLayout {
@@ -1603,9 +1883,19 @@ impl BackupLayout {
             games,
             games_lowercase,
             retention,
+            game_retention: Default::default(),
         }
     }
 
+    pub fn with_game_retention(mut self, game_retention: std::collections::BTreeMap<String, Retention>) -> Self {
+        self.game_retention = game_retention;
+        self
+    }
+
+    fn retention_for(&self, name: &str) -> Retention {
+        self.game_retention.get(name).unwrap_or(&self.retention).clone()
+    }
+
     pub fn load(base: &StrictPath) -> std::collections::HashMap<String, StrictPath> {
         let mut overall = std::collections::HashMap::new();
 
@@ -1632,7 +1922,7 @@ impl BackupLayout {
     pub fn game_layout(&self, name: &str) -> GameLayout {
         let path = self.game_folder(name);
 
-        match GameLayout::load(path.clone(), self.retention.clone()) {
+        match GameLayout::load(path.clone(), self.retention_for(name)) {
             Ok(mut x) => {
                 if x.mapping.name != name {
                     // This can happen if the game name changed in the manifest,
@@ -1646,11 +1936,17 @@ impl BackupLayout {
             Err(_) => GameLayout {
                 path,
                 mapping: IndividualMapping::new(name.to_string()),
-                retention: self.retention.clone(),
+                retention: self.retention_for(name),
             },
         }
     }
 
+    pub fn latest_backup_name(&self, name: &str) -> Option<String> {
+        self.game_layout(name)
+            .find_by_id_flattened(&BackupId::Latest)
+            .map(|x| x.name().to_string())
+    }
+
     fn contains_game(&self, name: &str) -> bool {
         self.games.contains_key(name)
             || (crate::prelude::CASE_INSENSITIVE_OS && self.games_lowercase.contains_key(&name.to_lowercase()))
@@ -1696,12 +1992,76 @@ impl BackupLayout {
     pub fn restorable_games(&self) -> Vec<String> {
         self.games.keys().cloned().collect()
     }
+
+    /// Find backed up games that no longer exist in the manifest,
+    /// but which have an alias pointing to a game that does.
+    /// Games whose new name already has its own backups are left alone.
+    pub fn find_renamed_games(
+        &self,
+        manifest: &crate::manifest::Manifest,
+        aliases: &BTreeMap<String, String>,
+    ) -> BTreeMap<String, String> {
+        let mut renamed = BTreeMap::new();
+
+        for old in self.games.keys() {
+            if manifest.0.contains_key(old) {
+                continue;
+            }
+            if let Some(new) = crate::prelude::resolve_game_alias(aliases, old) {
+                if manifest.0.contains_key(&new) && !self.contains_game(&new) {
+                    renamed.insert(old.clone(), new);
+                }
+            }
+        }
+
+        renamed
+    }
+
+    /// Move a renamed game's backups to the folder for its new name
+    /// and update the name recorded in its mapping file.
+    pub fn migrate_renamed_game(&mut self, old: &str, new: &str) -> Result<(), Error> {
+        let error = || Error::UnableToMigrateRenamedGame {
+            old: old.to_string(),
+            new: new.to_string(),
+        };
+
+        let source = self.games.get(old).cloned().ok_or_else(error)?;
+        if self.contains_game(new) {
+            return Err(error());
+        }
+
+        let target = self.game_folder(new);
+        if source.interpret() != target.interpret() {
+            if target.exists() {
+                return Err(error());
+            }
+            std::fs::rename(source.interpret(), target.interpret()).map_err(|e| {
+                log::error!("Unable to move backup folder {:?} -> {:?}: {e}", &source, &target);
+                error()
+            })?;
+        }
+
+        let mut game_layout = GameLayout::load(target.clone(), self.retention_for(new)).map_err(|_| error())?;
+        game_layout.mapping.name = new.to_string();
+        game_layout.save();
+        log::info!("Migrated renamed game: {} -> {}", old, new);
+
+        self.games.remove(old);
+        self.games_lowercase.remove(&old.to_lowercase());
+        self.games.insert(new.to_string(), target.clone());
+        self.games_lowercase.insert(new.to_lowercase(), target);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::testing::{repo_raw, *};
+    use crate::{
+        prelude::{FileDiff, FileDiffKind},
+        testing::{repo_raw, *},
+    };
     use maplit::*;
 
     mod individual_mapping {
@@ -1740,6 +2100,29 @@ mod tests {
             }
         }
 
+        #[test]
+        fn can_find_renamed_games() {
+            use crate::serialization::ResourceFile;
+
+            let manifest = crate::manifest::Manifest::load_from_string(
+                r#"
+                game1: {}
+                game3-new: {}
+                "#,
+            )
+            .unwrap();
+            let aliases = btreemap! {
+                s("game1") => s("game1-new"),
+                s("game3") => s("game3-middle"),
+                s("game3-middle") => s("game3-new"),
+            };
+
+            assert_eq!(
+                btreemap! { s("game3") => s("game3-new") },
+                layout().find_renamed_games(&manifest, &aliases),
+            );
+        }
+
         #[test]
         fn can_find_existing_game_folder_with_matching_name() {
             assert_eq!(
@@ -1812,6 +2195,22 @@ mod tests {
             );
         }
 
+        #[test]
+        fn can_use_game_specific_retention() {
+            let retention = Retention {
+                full: 2,
+                differential: 5,
+            };
+            let layout = layout().with_game_retention(btreemap! {
+                s("game1") => retention.clone(),
+                s("new game") => retention.clone(),
+            });
+
+            assert_eq!(retention, layout.game_layout("game1").retention);
+            assert_eq!(retention, layout.game_layout("new game").retention);
+            assert_eq!(Retention::default(), layout.game_layout("game2").retention);
+        }
+
         #[test]
         fn can_find_irrelevant_backup_files() {
             assert_eq!(
@@ -1924,7 +2323,7 @@ mod tests {
                     name: ".".to_string(),
                     when: now(),
                     files: btreemap! {
-                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 1 },
+                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 1, ..Default::default() },
                     },
                     ..Default::default()
                 })),
@@ -1951,7 +2350,40 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
+                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                        },
+                        ..Default::default()
+                    }]),
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 0,
+                },
+            };
+            assert_eq!(None, layout.plan_backup(&scan, &now(), &BackupFormats::default()));
+        }
+
+        #[test]
+        fn can_plan_backup_when_unchanged_since_last_full_without_steam_user() {
+            let file = format!("{}/tests/root/steam/userdata/12345/104/remote/file1.txt", repo_raw());
+            let scan = ScanInfo {
+                game_name: "game1".to_string(),
+                found_files: hashset! {
+                    ScannedFile::new(&file, 1, "old"),
+                },
+                found_registry_keys: hashset! {},
+                ..Default::default()
+            };
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives(),
+                    backups: VecDeque::from_iter(vec![FullBackup {
+                        name: ".".to_string(),
+                        when: past(),
+                        files: btreemap! {
+                            StrictPath::new(file.clone()).render() => IndividualMappingFile { hash: "old".into(), size: 1, steam_user_id: None },
                         },
                         ..Default::default()
                     }]),
@@ -1984,8 +2416,8 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -2000,8 +2432,8 @@ mod tests {
                     name: ".".to_string(),
                     when: now(),
                     files: btreemap! {
-                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 1 },
-                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 1, ..Default::default() },
+                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                     },
                     ..Default::default()
                 })),
@@ -2029,8 +2461,8 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -2045,8 +2477,8 @@ mod tests {
                     name: format!("backup-{}", now_str()),
                     when: now(),
                     files: btreemap! {
-                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 1 },
-                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 1, ..Default::default() },
+                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                     },
                     ..Default::default()
                 })),
@@ -2067,6 +2499,7 @@ mod tests {
                         hash: "old".into(),
                         original_path: None,
                         ignored: true,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -2085,10 +2518,10 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/unchanged.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
-                            StrictPath::new(format!("{}/tests/root/game1/delete.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 3 },
-                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4 },
+                            StrictPath::new(format!("{}/tests/root/game1/unchanged.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/delete.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 3, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -2103,10 +2536,10 @@ mod tests {
                     name: format!("backup-{}", now_str()),
                     when: now(),
                     files: btreemap! {
-                        StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                        StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
                         StrictPath::new(format!("{}/tests/root/game1/delete.txt", repo_raw())).render() => None,
                         StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => None,
-                        StrictPath::new(format!("{}/tests/root/game1/added.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                        StrictPath::new(format!("{}/tests/root/game1/added.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 5, ..Default::default() }),
                     },
                     ..Default::default()
                 })),
@@ -2133,19 +2566,19 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/unchanged.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
-                            StrictPath::new(format!("{}/tests/root/game1/delete.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 3 },
-                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4 },
+                            StrictPath::new(format!("{}/tests/root/game1/unchanged.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/delete.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 3, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: format!("backup-{}", now_str()),
                             when: now(),
                             files: btreemap! {
-                                StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
                                 StrictPath::new(format!("{}/tests/root/game1/delete.txt", repo_raw())).render() => None,
                                 StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => None,
-                                StrictPath::new(format!("{}/tests/root/game1/added.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                StrictPath::new(format!("{}/tests/root/game1/added.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 5, ..Default::default() }),
                             },
                             ..Default::default()
                         }],
@@ -2163,7 +2596,7 @@ mod tests {
                     when: now(),
                     files: btreemap! {
                         StrictPath::new(format!("{}/tests/root/game1/unchanged.txt", repo_raw())).render() => None,
-                        StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "newer".into(), size: 2 }),
+                        StrictPath::new(format!("{}/tests/root/game1/changed.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "newer".into(), size: 2, ..Default::default() }),
                         StrictPath::new(format!("{}/tests/root/game1/delete.txt", repo_raw())).render() => None,
                         StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => None,
                     },
@@ -2193,15 +2626,15 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: format!("backup-{}", past2_str()),
                             when: past2(),
                             files: btreemap! {
-                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1 }),
-                                StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "old".into(), size: 2 }),
+                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1, ..Default::default() }),
+                                StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() }),
                             },
                             ..Default::default()
                         }],
@@ -2235,13 +2668,13 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
+                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: format!("backup-{}", past2_str()),
                             when: past2(),
                             files: btreemap! {
-                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1 }),
+                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1, ..Default::default() }),
                             },
                             ..Default::default()
                         }],
@@ -2282,7 +2715,7 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4 },
+                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -2324,7 +2757,7 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4 },
+                            StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 4, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: format!("backup-{}", now_str()),
@@ -2347,7 +2780,7 @@ mod tests {
                     name: format!("backup-{}", now_str()),
                     when: now(),
                     files: btreemap! {
-                        StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                        StrictPath::new(format!("{}/tests/root/game1/ignore.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
                     },
                     ..Default::default()
                 })),
@@ -2375,15 +2808,15 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: format!("backup-{}", past2_str()),
                             when: past2(),
                             files: btreemap! {
-                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1 }),
-                                StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "old".into(), size: 2 }),
+                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1, ..Default::default() }),
+                                StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() }),
                             },
                             ..Default::default()
                         }],
@@ -2400,8 +2833,8 @@ mod tests {
                     name: format!("backup-{}", now_str()),
                     when: now(),
                     files: btreemap! {
-                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "newer".into(), size: 1 },
-                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "newer".into(), size: 1, ..Default::default() },
+                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                     },
                     ..Default::default()
                 })),
@@ -2429,15 +2862,15 @@ mod tests {
                         name: format!("backup-{}", past_str()),
                         when: past(),
                         files: btreemap! {
-                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2 },
+                            StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: format!("backup-{}", past2_str()),
                             when: past2(),
                             files: btreemap! {
-                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1 }),
-                                StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "old".into(), size: 2 }),
+                                StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "new".into(), size: 1, ..Default::default() }),
+                                StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => Some(IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() }),
                             },
                             ..Default::default()
                         }],
@@ -2454,8 +2887,8 @@ mod tests {
                     name: ".".to_string(),
                     when: now(),
                     files: btreemap! {
-                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1 },
-                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 2 },
+                        StrictPath::new(format!("{}/tests/root/game1/file1.txt", repo_raw())).render() => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                        StrictPath::new(format!("{}/tests/root/game1/file2.txt", repo_raw())).render() => IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() },
                     },
                     ..Default::default()
                 })),
@@ -2507,8 +2940,8 @@ mod tests {
                         name: "backup-1".into(),
                         when: past(),
                         files: btreemap! {
-                            mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt") => IndividualMappingFile { hash: "old".into(), size: 2 },
+                            mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt") => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -2526,6 +2959,7 @@ mod tests {
                         hash: "old".into(),
                         original_path: Some(make_original_path("/file1.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -2536,6 +2970,7 @@ mod tests {
                         hash: "old".into(),
                         original_path: Some(make_original_path("/file2.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -2556,8 +2991,8 @@ mod tests {
                         name: "backup-1.zip".into(),
                         when: past(),
                         files: btreemap! {
-                            mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt") => IndividualMappingFile { hash: "old".into(), size: 2 },
+                            mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt") => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -2575,6 +3010,7 @@ mod tests {
                         hash: "old".into(),
                         original_path: Some(make_original_path("/file1.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: Some(make_path("backup-1.zip")),
                         redirected: None,
@@ -2585,6 +3021,7 @@ mod tests {
                         hash: "old".into(),
                         original_path: Some(make_original_path("/file2.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: Some(make_path("backup-1.zip")),
                         redirected: None,
@@ -2605,17 +3042,17 @@ mod tests {
                         name: "backup-1".into(),
                         when: past(),
                         files: btreemap! {
-                            mapping_file_key("/unchanged.txt") => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt") => IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt") => IndividualMappingFile { hash: "old".into(), size: 3 },
+                            mapping_file_key("/unchanged.txt") => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/changed.txt") => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
+                            mapping_file_key("/delete.txt") => IndividualMappingFile { hash: "old".into(), size: 3, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: "backup-2".into(),
                             when: past2(),
                             files: btreemap! {
-                                mapping_file_key("/changed.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                mapping_file_key("/changed.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
                                 mapping_file_key("/delete.txt") => None,
-                                mapping_file_key("/added.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                mapping_file_key("/added.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 5, ..Default::default() }),
                             },
                             ..Default::default()
                         }],
@@ -2635,6 +3072,7 @@ mod tests {
                         hash: "old".into(),
                         original_path: Some(make_original_path("/unchanged.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -2645,6 +3083,7 @@ mod tests {
                         hash: "new".into(),
                         original_path: Some(make_original_path("/changed.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -2655,6 +3094,7 @@ mod tests {
                         hash: "new".into(),
                         original_path: Some(make_original_path("/added.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: None,
                         redirected: None,
@@ -2675,17 +3115,17 @@ mod tests {
                         name: "backup-1.zip".into(),
                         when: past(),
                         files: btreemap! {
-                            mapping_file_key("/unchanged.txt") => IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt") => IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt") => IndividualMappingFile { hash: "old".into(), size: 3 },
+                            mapping_file_key("/unchanged.txt") => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/changed.txt") => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
+                            mapping_file_key("/delete.txt") => IndividualMappingFile { hash: "old".into(), size: 3, ..Default::default() },
                         },
                         children: vec![DifferentialBackup {
                             name: "backup-2.zip".into(),
                             when: past2(),
                             files: btreemap! {
-                                mapping_file_key("/changed.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                mapping_file_key("/changed.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
                                 mapping_file_key("/delete.txt") => None,
-                                mapping_file_key("/added.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                mapping_file_key("/added.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 5, ..Default::default() }),
                             },
                             ..Default::default()
                         }],
@@ -2705,6 +3145,7 @@ mod tests {
                         hash: "old".into(),
                         original_path: Some(make_original_path("/unchanged.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: Some(make_path("backup-1.zip")),
                         redirected: None,
@@ -2715,6 +3156,7 @@ mod tests {
                         hash: "new".into(),
                         original_path: Some(make_original_path("/changed.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: Some(make_path("backup-2.zip")),
                         redirected: None,
@@ -2725,6 +3167,7 @@ mod tests {
                         hash: "new".into(),
                         original_path: Some(make_original_path("/added.txt")),
                         ignored: false,
+                        excluded: None,
                         change: Default::default(),
                         container: Some(make_path("backup-2.zip")),
                         redirected: None,
@@ -2733,5 +3176,405 @@ mod tests {
                 layout.restorable_files(&BackupId::Latest, false, &[]),
             );
         }
+
+        #[test]
+        fn can_diff_differential_backup_against_previous_backup() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "backup-1".into(),
+                        when: past(),
+                        files: btreemap! {
+                            mapping_file_key("/unchanged.txt") => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/changed.txt") => IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
+                            mapping_file_key("/delete.txt") => IndividualMappingFile { hash: "old".into(), size: 3, ..Default::default() },
+                        },
+                        children: vec![DifferentialBackup {
+                            name: "backup-2".into(),
+                            when: past2(),
+                            files: btreemap! {
+                                mapping_file_key("/changed.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 4, ..Default::default() }),
+                                mapping_file_key("/delete.txt") => None,
+                                mapping_file_key("/added.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 5, ..Default::default() }),
+                            },
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }]),
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 1,
+                },
+            };
+
+            let previous = layout.previous_backup(&BackupId::Latest);
+            assert_eq!(Some("backup-1"), previous.as_ref().map(|x| x.name()));
+            assert_eq!(None, layout.previous_backup(&BackupId::Named("backup-1".into())));
+
+            assert_eq!(
+                GameDiff(btreemap! {
+                    make_original_path("/added.txt").render() => FileDiff {
+                        kind: FileDiffKind::Added,
+                        old_size: None,
+                        new_size: Some(5),
+                    },
+                    make_original_path("/changed.txt").render() => FileDiff {
+                        kind: FileDiffKind::Modified,
+                        old_size: Some(2),
+                        new_size: Some(4),
+                    },
+                    make_original_path("/delete.txt").render() => FileDiff {
+                        kind: FileDiffKind::Removed,
+                        old_size: Some(3),
+                        new_size: None,
+                    },
+                }),
+                layout.diff(previous.map(|x| x.id()).as_ref(), &BackupId::Latest),
+            );
+        }
+
+        #[test]
+        fn can_find_backup_as_of_time() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "backup-1".into(),
+                        when: past(),
+                        children: vec![DifferentialBackup {
+                            name: "backup-2".into(),
+                            when: past2(),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }]),
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 1,
+                },
+            };
+
+            let as_of = |time: chrono::DateTime<chrono::Utc>| {
+                layout
+                    .find_by_id_flattened(&BackupId::AsOf(time))
+                    .map(|x| x.name().to_string())
+            };
+            assert_eq!(None, as_of(past() - chrono::Duration::seconds(1)));
+            assert_eq!(Some(s("backup-1")), as_of(past()));
+            assert_eq!(Some(s("backup-2")), as_of(past2()));
+            assert_eq!(Some(s("backup-2")), as_of(past2() + chrono::Duration::days(1)));
+        }
+
+        #[test]
+        fn can_list_file_history() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![
+                        FullBackup {
+                            name: "backup-1".into(),
+                            when: past(),
+                            comment: Some("first".into()),
+                            files: btreemap! {
+                                mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            },
+                            children: vec![
+                                DifferentialBackup {
+                                    name: "backup-2".into(),
+                                    when: past2(),
+                                    files: btreemap! {
+                                        mapping_file_key("/file1.txt") => Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
+                                    },
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "backup-3".into(),
+                                    when: past2(),
+                                    files: btreemap! {
+                                        mapping_file_key("/file1.txt") => None,
+                                    },
+                                    ..Default::default()
+                                },
+                            ],
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "backup-4".into(),
+                            when: past2(),
+                            files: btreemap! {
+                                mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() },
+                            },
+                            ..Default::default()
+                        },
+                    ]),
+                },
+                retention: Retention {
+                    full: 2,
+                    differential: 2,
+                },
+            };
+            assert_eq!(
+                vec![
+                    FileVersion {
+                        backup: "backup-1".into(),
+                        when: past(),
+                        comment: Some("first".into()),
+                        size: 1,
+                        hash: "old".into(),
+                    },
+                    FileVersion {
+                        backup: "backup-2".into(),
+                        when: past2(),
+                        comment: None,
+                        size: 2,
+                        hash: "new".into(),
+                    },
+                    FileVersion {
+                        backup: "backup-4".into(),
+                        when: past2(),
+                        comment: None,
+                        size: 2,
+                        hash: "new".into(),
+                    },
+                ],
+                layout.file_history(&make_original_path("/file1.txt")),
+            );
+            assert_eq!(
+                Vec::<FileVersion>::new(),
+                layout.file_history(&make_original_path("/file2.txt")),
+            );
+        }
+
+        #[test]
+        fn restoring_file_version_fails_when_file_is_not_in_backup() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    ..Default::default()
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 1,
+                },
+            };
+            let mut scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hashset! {
+                    ScannedFile::new("backup-1/drive-X/file1.txt", 1, "old").original(make_original_path("/file1.txt").raw()),
+                },
+                ..Default::default()
+            };
+
+            assert_eq!(
+                Some(Error::CliFileNotInBackup {
+                    path: make_original_path("/file2.txt")
+                }),
+                layout
+                    .restore_file_version(&mut scan, &make_original_path("/file2.txt"))
+                    .err(),
+            );
+            assert!(scan.found_files.is_empty());
+        }
+
+        #[test]
+        fn can_redirect_files_for_extraction() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    ..Default::default()
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 1,
+                },
+            };
+            let mut scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hashset! {
+                    ScannedFile::new("backup-1/drive-X/file1.txt", 1, "old").original(make_original_path("/file1.txt").raw()),
+                },
+                ..Default::default()
+            };
+
+            layout.redirect_for_extraction(&mut scan, &StrictPath::new(s("/extract")));
+
+            assert_eq!(
+                hashset! {
+                    ScannedFile {
+                        redirected: Some(StrictPath::new(format!(
+                            "/extract/drive-{}/file1.txt",
+                            if cfg!(target_os = "windows") { "X" } else { "0" }
+                        ))),
+                        ..ScannedFile::new("backup-1/drive-X/file1.txt", 1, "old").original(make_original_path("/file1.txt").raw())
+                    },
+                },
+                scan.found_files,
+            );
+        }
+
+        #[test]
+        fn can_extract_simple_and_zip_backups() {
+            use std::io::Write;
+
+            let root = StrictPath::from_std_path_buf(
+                &std::env::temp_dir().join(format!("ludusavi-test-extract-{}", std::process::id())),
+            );
+            let _ = root.remove();
+            let game_dir = root.joined("backups/game1");
+            let drive = if cfg!(target_os = "windows") {
+                "drive-X"
+            } else {
+                "drive-0"
+            };
+            let simple_time = past();
+            let zip_time = past2();
+
+            let simple_file = game_dir.joined(&format!("backup-1/{drive}/file1.txt"));
+            simple_file.create_parent_dir().unwrap();
+            std::fs::write(simple_file.interpret(), "a").unwrap();
+            simple_file.set_mtime(simple_time.into()).unwrap();
+            std::fs::write(game_dir.joined("backup-1/registry.yaml").interpret(), "simple registry").unwrap();
+
+            let mut zip =
+                zip::ZipWriter::new(std::fs::File::create(game_dir.joined("backup-2.zip").interpret()).unwrap());
+            let options = zip::write::FileOptions::default().last_modified_time(
+                zip::DateTime::from_date_and_time(
+                    zip_time.year() as u16,
+                    zip_time.month() as u8,
+                    zip_time.day() as u8,
+                    zip_time.hour() as u8,
+                    zip_time.minute() as u8,
+                    zip_time.second() as u8,
+                )
+                .unwrap(),
+            );
+            zip.start_file(format!("{drive}/file1.txt"), options).unwrap();
+            zip.write_all(b"bb").unwrap();
+            zip.start_file("registry.yaml", options).unwrap();
+            zip.write_all(b"zip registry").unwrap();
+            zip.finish().unwrap();
+
+            let mut layout = GameLayout {
+                path: game_dir,
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![
+                        FullBackup {
+                            name: "backup-1".into(),
+                            when: simple_time,
+                            files: btreemap! {
+                                mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "a".into(), size: 1, ..Default::default() },
+                            },
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "backup-2.zip".into(),
+                            when: zip_time,
+                            files: btreemap! {
+                                mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "b".into(), size: 2, ..Default::default() },
+                            },
+                            ..Default::default()
+                        },
+                    ]),
+                },
+                retention: Retention {
+                    full: 2,
+                    differential: 0,
+                },
+            };
+
+            for (backup, content, registry, mtime) in [
+                ("backup-1", "a", "simple registry", simple_time),
+                ("backup-2.zip", "bb", "zip registry", zip_time),
+            ] {
+                let target = root.joined(&format!("extract-{backup}"));
+                let mut scan =
+                    crate::prelude::scan_game_for_restoration("game1", &BackupId::Named(s(backup)), &mut layout, &[]);
+                layout.redirect_for_extraction(&mut scan, &target);
+
+                let info = layout.extract(&scan, &target);
+                assert!(info.successful());
+
+                let extracted = target.joined(&format!("{drive}/file1.txt"));
+                assert_eq!(Some(s(content)), extracted.read());
+                assert_eq!(Some(s(registry)), target.joined("registry.yaml").read());
+                assert_eq!(
+                    mtime,
+                    chrono::DateTime::<chrono::Utc>::from(extracted.get_mtime().unwrap())
+                );
+            }
+
+            let _ = root.remove();
+        }
+
+        #[test]
+        fn can_retarget_files_to_another_steam_user() {
+            let backup = FullBackup {
+                name: "backup-1".into(),
+                when: past(),
+                files: btreemap! {
+                    make_original_path("/steam/userdata/12345/104/remote/a.sav").render() => IndividualMappingFile { hash: "a".into(), size: 1, steam_user_id: Some(s("12345")) },
+                    make_original_path("/steam/userdata/67890/104/remote/a.sav").render() => IndividualMappingFile { hash: "b".into(), size: 1, steam_user_id: Some(s("67890")) },
+                    make_original_path("/steam/userdata/12345/104/remote/legacy.sav").render() => IndividualMappingFile { hash: "c".into(), size: 1, ..Default::default() },
+                    make_original_path("/saves/other.sav").render() => IndividualMappingFile { hash: "d".into(), size: 1, ..Default::default() },
+                },
+                ..Default::default()
+            };
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![backup.clone()]),
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 1,
+                },
+            };
+            let mut scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hashset! {
+                    ScannedFile::new("a", 1, "a").original(make_original_path("/steam/userdata/12345/104/remote/a.sav").raw()),
+                    ScannedFile::new("b", 1, "b").original(make_original_path("/steam/userdata/67890/104/remote/a.sav").raw()),
+                    ScannedFile::new("c", 1, "c").original(make_original_path("/steam/userdata/12345/104/remote/legacy.sav").raw()),
+                    ScannedFile::new("d", 1, "d").original(make_original_path("/saves/other.sav").raw()),
+                },
+                backup: Some(Backup::Full(backup)),
+                ..Default::default()
+            };
+
+            layout.retarget_steam_user(&mut scan, "12345", Some("24680"));
+
+            assert_eq!(
+                hashset! {
+                    ScannedFile {
+                        redirected: Some(StrictPath::new(make_original_path("/steam/userdata/24680/104/remote/a.sav").render())),
+                        ..ScannedFile::new("a", 1, "a").original(make_original_path("/steam/userdata/12345/104/remote/a.sav").raw()).change_new()
+                    },
+                    ScannedFile::new("b", 1, "b").original(make_original_path("/steam/userdata/67890/104/remote/a.sav").raw()).ignored(),
+                    ScannedFile {
+                        redirected: Some(StrictPath::new(make_original_path("/steam/userdata/24680/104/remote/legacy.sav").render())),
+                        ..ScannedFile::new("c", 1, "c").original(make_original_path("/steam/userdata/12345/104/remote/legacy.sav").raw()).change_new()
+                    },
+                    ScannedFile::new("d", 1, "d").original(make_original_path("/saves/other.sav").raw()),
+                },
+                scan.found_files,
+            );
+        }
     }
 }
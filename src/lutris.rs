@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use crate::{
+    config::RootsConfig,
+    manifest::Store,
+    prelude::{StrictPath, TitleFinder},
+};
+
+//
+/// Row of Lutris pga.db `games` table
+//
+#[derive(Debug)]
+struct DatabaseGame {
+    name: String,
+    runner: Option<String>,
+    directory: Option<String>,
+    configpath: Option<String>,
+}
+
+//
+/// Deserialization of Lutris games/*.yml
+//
+#[derive(serde::Deserialize, Debug, Default)]
+struct GameConfig {
+    #[serde(default)]
+    game: GameConfigGame,
+}
+#[derive(serde::Deserialize, Debug, Default)]
+struct GameConfigGame {
+    exe: Option<String>,
+    prefix: Option<String>,
+    working_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct MemorizedGame {
+    install_dir: StrictPath,
+    prefix: Option<StrictPath>,
+}
+
+//
+/// Main structure where games installed with Lutris are collected
+//
+#[derive(Clone, Default, Debug)]
+pub struct LutrisGames {
+    games: HashMap<(RootsConfig, String), MemorizedGame>,
+}
+
+impl LutrisGames {
+    pub fn get_prefix(&self, root: &RootsConfig, game: &str) -> Option<&StrictPath> {
+        self.games
+            .get(&(root.clone(), game.to_string()))
+            .and_then(|x| x.prefix.as_ref())
+    }
+
+    pub fn get_install_dir(&self, root: &RootsConfig, game: &str) -> Option<&StrictPath> {
+        self.games
+            .get(&(root.clone(), game.to_string()))
+            .map(|x| &x.install_dir)
+    }
+
+    pub fn scan(roots: &[RootsConfig], title_finder: &TitleFinder) -> Self {
+        let mut instance = LutrisGames::default();
+
+        for root in roots {
+            if root.store == Store::Lutris {
+                instance.detect_games(root, title_finder);
+                log::trace!("scan found: {:#?}", instance.games);
+            }
+        }
+
+        instance
+    }
+
+    fn detect_games(&mut self, root: &RootsConfig, title_finder: &TitleFinder) {
+        let database_path = root.path.joined("pga.db");
+        if !database_path.is_file() {
+            log::trace!(
+                "detect_games no such file '{}', Lutris probably not used yet... skipping",
+                database_path.interpret()
+            );
+            return;
+        }
+
+        let games = match Self::read_database(&database_path) {
+            Ok(games) => games,
+            Err(e) => {
+                log::warn!(
+                    "detect_games aborting since it could not read {}: {}",
+                    database_path.interpret(),
+                    e
+                );
+                return;
+            }
+        };
+        log::trace!(
+            "detect_games found {} games in {}",
+            games.len(),
+            database_path.interpret()
+        );
+
+        for game in games {
+            let config = Self::read_config(root, &game).unwrap_or_default();
+
+            let Some(install_dir) = Self::find_install_dir(&game, &config) else {
+                log::trace!(
+                    "detect_games could not determine install dir for {}, ignoring",
+                    game.name
+                );
+                continue;
+            };
+            let prefix = match game.runner.as_deref() {
+                Some("wine") => config.game.prefix.map(StrictPath::new),
+                _ => None,
+            };
+
+            let official_title = title_finder.find_one(&[game.name.to_owned()], &None, &None, true, true, false);
+            self.memorize_game(root, &game.name, official_title, install_dir, prefix);
+        }
+    }
+
+    fn read_database(path: &StrictPath) -> Result<Vec<DatabaseGame>, rusqlite::Error> {
+        let connection =
+            rusqlite::Connection::open_with_flags(path.interpret(), rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut statement =
+            connection.prepare("SELECT name, runner, directory, configpath FROM games WHERE installed = 1")?;
+        let rows = statement.query_map([], |row| {
+            Ok(DatabaseGame {
+                name: row.get(0)?,
+                runner: row.get(1)?,
+                directory: row.get(2)?,
+                configpath: row.get(3)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Newer versions of Lutris keep the game configs next to the database,
+    /// while older versions keep them in the config folder.
+    fn read_config(root: &RootsConfig, game: &DatabaseGame) -> Option<GameConfig> {
+        let configpath = game.configpath.as_ref().filter(|x| !x.is_empty())?;
+        let file = format!("{configpath}.yml");
+
+        let candidates = [
+            root.path.joined("games").joined(&file),
+            // Flatpak: ~/.var/app/net.lutris.Lutris/data/lutris -> ~/.var/app/net.lutris.Lutris/config/lutris
+            StrictPath::relative(format!("../../config/lutris/games/{file}"), Some(root.path.interpret())),
+            StrictPath::new(format!("~/.config/lutris/games/{file}")),
+        ];
+
+        for candidate in candidates {
+            if !candidate.is_file() {
+                continue;
+            }
+            log::trace!("read_config reading {}", candidate.interpret());
+            match serde_yaml::from_str::<GameConfig>(&candidate.read().unwrap_or_default()) {
+                Ok(config) => return Some(config),
+                Err(e) => {
+                    log::warn!("read_config could not parse {}: {}", candidate.interpret(), e);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn find_install_dir(game: &DatabaseGame, config: &GameConfig) -> Option<StrictPath> {
+        let directory = game.directory.as_ref().filter(|x| !x.is_empty());
+
+        if let Some(working_dir) = config.game.working_dir.as_ref().filter(|x| !x.is_empty()) {
+            return Some(StrictPath::new(working_dir.clone()));
+        }
+
+        if let Some(exe) = config.game.exe.as_ref().filter(|x| !x.is_empty()) {
+            // The executable may be relative to the game folder.
+            let exe = match directory {
+                Some(directory) => StrictPath::relative(exe.clone(), Some(directory.clone())),
+                None => StrictPath::new(exe.clone()),
+            };
+            if let Some(parent) = std::path::Path::new(&exe.interpret()).parent() {
+                return Some(StrictPath::from_std_path_buf(parent));
+            }
+        }
+
+        directory.map(|x| StrictPath::new(x.clone()))
+    }
+
+    fn memorize_game(
+        &mut self,
+        root: &RootsConfig,
+        lutris_title: &str,
+        official_title: Option<String>,
+        install_dir: StrictPath,
+        prefix: Option<StrictPath>,
+    ) {
+        if let Some(official) = official_title {
+            log::trace!(
+                "memorize_game memorizing info for '{}' (from: '{}'): install_dir={:?}, prefix={:?}",
+                official,
+                lutris_title,
+                &install_dir,
+                &prefix
+            );
+            self.games
+                .insert((root.clone(), official), MemorizedGame { install_dir, prefix });
+        } else {
+            let log_message = format!("Ignoring unrecognized Lutris game: '{}'", lutris_title);
+            if std::env::var("LUDUSAVI_DEBUG").is_ok() {
+                eprintln!("{}", &log_message);
+            }
+            log::info!("{}", &log_message);
+
+            log::trace!(
+                "memorize_game memorizing info for '{}': install_dir={:?}, prefix={:?}",
+                lutris_title,
+                &install_dir,
+                &prefix
+            );
+            self.games.insert(
+                (root.clone(), lutris_title.to_string()),
+                MemorizedGame { install_dir, prefix },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manifest::Manifest, serialization::ResourceFile, testing::repo};
+    use maplit::hashmap;
+    use pretty_assertions::assert_eq;
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            windows-game:
+              files:
+                <base>/file1.txt: {}
+            linux-game:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn title_finder() -> TitleFinder {
+        TitleFinder::new(&manifest(), &Default::default())
+    }
+
+    #[test]
+    fn scan_finds_nothing_when_folder_does_not_exist() {
+        let roots = vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/nonexistent", repo())),
+            store: Store::Lutris,
+        }];
+        let games = LutrisGames::scan(&roots, &title_finder());
+        assert_eq!(HashMap::new(), games.games);
+    }
+
+    #[test]
+    fn scan_finds_all_installed_games() {
+        let roots = vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/lutris", repo())),
+            store: Store::Lutris,
+        }];
+        let games = LutrisGames::scan(&roots, &title_finder());
+        assert_eq!(
+            hashmap! {
+                (roots[0].clone(), "windows-game".to_string()) => MemorizedGame {
+                    install_dir: StrictPath::new("/home/root/Games/windows-game/drive_c/Program Files/Windows Game".to_string()),
+                    prefix: Some(StrictPath::new("/home/root/Games/windows-game".to_string())),
+                },
+                (roots[0].clone(), "linux-game".to_string()) => MemorizedGame {
+                    install_dir: StrictPath::new("/home/root/Games/linux-game".to_string()),
+                    prefix: None,
+                },
+            },
+            games.games,
+        );
+    }
+}
@@ -2,19 +2,25 @@
 
 use crate::prelude::CONFIG_DIR;
 
+mod bottles;
 mod cache;
 mod cli;
 mod config;
 mod gui;
 mod heroic;
+mod hooks;
+mod itch;
 mod lang;
+mod launchers;
 mod layout;
+mod lutris;
 mod manifest;
 mod path;
 mod prelude;
 mod registry_compat;
 mod serialization;
 mod shortcuts;
+mod steam;
 
 #[cfg(target_os = "windows")]
 mod registry;
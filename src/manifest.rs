@@ -1,8 +1,8 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use crate::{
     cache::{self, Cache},
-    config::{Config, CustomGame, ManifestConfig},
+    config::{Config, CustomGame, Integration, ManifestConfig, SecondaryManifestConfig},
     prelude::{app_dir, Error, StrictPath},
     serialization::{ResourceFile, SaveableResourceFile},
 };
@@ -22,6 +22,8 @@ pub enum Os {
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Store {
+    #[serde(rename = "bottles")]
+    Bottles,
     #[serde(rename = "epic")]
     Epic,
     #[serde(rename = "gog")]
@@ -30,6 +32,10 @@ pub enum Store {
     GogGalaxy,
     #[serde(rename = "heroic")]
     Heroic,
+    #[serde(rename = "itch")]
+    Itch,
+    #[serde(rename = "lutris")]
+    Lutris,
     #[serde(rename = "microsoft")]
     Microsoft,
     #[serde(rename = "origin")]
@@ -51,10 +57,13 @@ pub enum Store {
 
 impl Store {
     pub const ALL: &'static [Self] = &[
+        Store::Bottles,
         Store::Epic,
         Store::Gog,
         Store::GogGalaxy,
         Store::Heroic,
+        Store::Itch,
+        Store::Lutris,
         Store::Microsoft,
         Store::Origin,
         Store::Prime,
@@ -100,6 +109,30 @@ pub struct Game {
     pub gog: Option<GogMetadata>,
 }
 
+impl Game {
+    /// Add another entry's paths to this one, preferring the other entry's
+    /// tags and constraints for any paths that both of them have.
+    pub fn extend(&mut self, other: Self) {
+        if let Some(files) = other.files {
+            self.files.get_or_insert_with(Default::default).extend(files);
+        }
+        if let Some(registry) = other.registry {
+            self.registry.get_or_insert_with(Default::default).extend(registry);
+        }
+        if let Some(install_dir) = other.install_dir {
+            self.install_dir
+                .get_or_insert_with(Default::default)
+                .extend(install_dir);
+        }
+        if other.steam.is_some() {
+            self.steam = other.steam;
+        }
+        if other.gog.is_some() {
+            self.gog = other.gog;
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct GameFileEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -147,31 +180,195 @@ pub struct GogMetadata {
 
 impl From<CustomGame> for Game {
     fn from(item: CustomGame) -> Self {
-        let file_tuples = item.files.iter().map(|x| (x.to_string(), GameFileEntry::default()));
+        let file_tuples = item.files.into_iter().map(|x| {
+            (
+                x.path,
+                GameFileEntry {
+                    tags: (!x.tags.is_empty()).then_some(x.tags),
+                    when: (!x.when.is_empty()).then_some(x.when),
+                },
+            )
+        });
         let files: BTreeMap<_, _> = file_tuples.collect();
 
-        let registry_tuples = item
-            .registry
-            .iter()
-            .map(|x| (x.to_string(), GameRegistryEntry::default()));
+        let registry_tuples = item.registry.into_iter().map(|x| {
+            (
+                x.path,
+                GameRegistryEntry {
+                    tags: (!x.tags.is_empty()).then_some(x.tags),
+                    when: (!x.when.is_empty()).then_some(x.when),
+                },
+            )
+        });
         let registry: BTreeMap<_, _> = registry_tuples.collect();
 
+        let install_dir_tuples = item
+            .install_dir
+            .into_iter()
+            .map(|x| (x, GameInstallDirEntry::default()));
+        let install_dir: BTreeMap<_, _> = install_dir_tuples.collect();
+
         Self {
             files: Some(files),
-            install_dir: None,
+            install_dir: (!install_dir.is_empty()).then_some(install_dir),
             registry: Some(registry),
-            steam: None,
-            gog: None,
+            steam: item.steam,
+            gog: item.gog,
         }
     }
 }
 
+/// Where a game's entry came from after layering the manifests and custom games.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub enum ManifestSource {
+    #[serde(rename = "primary")]
+    Primary,
+    #[serde(rename = "secondary")]
+    Secondary(SecondaryManifestConfig),
+    #[serde(rename = "custom")]
+    Custom,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct ManifestUpdate {
     pub url: String,
     pub etag: Option<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub modified: bool,
+    /// What changed compared to the previous copy, if there was one.
+    #[serde(default)]
+    pub diff: Option<ManifestDiff>,
+}
+
+/// Differences between two versions of a manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ManifestDiff {
+    pub added: BTreeSet<String>,
+    pub removed: BTreeSet<String>,
+    /// Old name -> new name, for games with the same Steam or GOG ID.
+    pub renamed: BTreeMap<String, String>,
+    /// Games whose paths changed, by their new name.
+    pub changed: BTreeMap<String, GamePathsDiff>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GamePathsDiff {
+    #[serde(rename = "addedFiles", skip_serializing_if = "BTreeSet::is_empty", default)]
+    pub added_files: BTreeSet<String>,
+    #[serde(rename = "removedFiles", skip_serializing_if = "BTreeSet::is_empty", default)]
+    pub removed_files: BTreeSet<String>,
+    #[serde(rename = "addedRegistry", skip_serializing_if = "BTreeSet::is_empty", default)]
+    pub added_registry: BTreeSet<String>,
+    #[serde(rename = "removedRegistry", skip_serializing_if = "BTreeSet::is_empty", default)]
+    pub removed_registry: BTreeSet<String>,
+}
+
+impl GamePathsDiff {
+    pub fn new(old: &Game, new: &Game) -> Self {
+        fn keys<T>(map: &Option<BTreeMap<String, T>>) -> BTreeSet<String> {
+            map.as_ref().map(|x| x.keys().cloned().collect()).unwrap_or_default()
+        }
+
+        let (old_files, new_files) = (keys(&old.files), keys(&new.files));
+        let (old_registry, new_registry) = (keys(&old.registry), keys(&new.registry));
+
+        Self {
+            added_files: new_files.difference(&old_files).cloned().collect(),
+            removed_files: old_files.difference(&new_files).cloned().collect(),
+            added_registry: new_registry.difference(&old_registry).cloned().collect(),
+            removed_registry: old_registry.difference(&new_registry).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_files.is_empty()
+            && self.removed_files.is_empty()
+            && self.added_registry.is_empty()
+            && self.removed_registry.is_empty()
+    }
+}
+
+impl ManifestDiff {
+    pub fn new(old: &Manifest, new: &Manifest) -> Self {
+        let mut diff = Self {
+            added: new.0.keys().filter(|x| !old.0.contains_key(*x)).cloned().collect(),
+            removed: old.0.keys().filter(|x| !new.0.contains_key(*x)).cloned().collect(),
+            ..Default::default()
+        };
+
+        let new_steam_ids = new.map_steam_ids_to_names();
+        let new_gog_ids = new.map_gog_ids_to_names();
+        for old_name in diff.removed.clone() {
+            let old_game = &old.0[&old_name];
+            let steam = old_game
+                .steam
+                .as_ref()
+                .and_then(|x| x.id)
+                .and_then(|id| new_steam_ids.get(&id));
+            let gog = old_game
+                .gog
+                .as_ref()
+                .and_then(|x| x.id)
+                .and_then(|id| new_gog_ids.get(&id));
+            if let Some(new_name) = steam.or(gog).filter(|x| diff.added.contains(*x)).cloned() {
+                diff.removed.remove(&old_name);
+                diff.added.remove(&new_name);
+                diff.renamed.insert(old_name, new_name);
+            }
+        }
+
+        let renamed: HashMap<_, _> = diff.renamed.iter().map(|(old, new)| (new, old)).collect();
+        for (name, new_game) in &new.0 {
+            let old_name = renamed.get(name).copied().unwrap_or(name);
+            let Some(old_game) = old.0.get(old_name) else {
+                continue;
+            };
+            let paths = GamePathsDiff::new(old_game, new_game);
+            if !paths.is_empty() {
+                diff.changed.insert(name.clone(), paths);
+            }
+        }
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.renamed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Games that may have existing backups under their old name and whose entry was removed or changed.
+    pub fn affected_games(&self) -> BTreeSet<String> {
+        let renamed: HashMap<_, _> = self.renamed.iter().map(|(old, new)| (new, old)).collect();
+        self.removed
+            .iter()
+            .chain(self.renamed.keys())
+            .chain(self.changed.keys().map(|x| renamed.get(x).copied().unwrap_or(x)))
+            .cloned()
+            .collect()
+    }
+}
+
+const BINARY_CACHE_FILE_NAME: &str = "manifest.cache";
+
+/// Information about `manifest.yaml` at the time that the binary cache was made.
+#[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct BinaryCacheStamp {
+    version: String,
+    modified: chrono::DateTime<chrono::Utc>,
+    size: u64,
+}
+
+/// Pre-parsed copy of `manifest.yaml`, which is much faster to load than the YAML itself.
+#[derive(serde::Deserialize)]
+struct BinaryCache {
+    stamp: BinaryCacheStamp,
+    manifest: Manifest,
+}
+
+#[derive(serde::Serialize)]
+struct BinaryCacheRef<'a> {
+    stamp: BinaryCacheStamp,
+    manifest: &'a Manifest,
 }
 
 impl ResourceFile for Manifest {
@@ -180,17 +377,128 @@ impl ResourceFile for Manifest {
 
 impl Manifest {
     pub fn load() -> Result<Self, Error> {
-        ResourceFile::load().map_err(|e| Error::ManifestInvalid { why: format!("{}", e) })
+        if let Some(manifest) = Self::load_binary_cache() {
+            return Ok(manifest);
+        }
+
+        let manifest: Self = ResourceFile::load().map_err(|e| Error::ManifestInvalid { why: format!("{}", e) })?;
+        manifest.save_binary_cache();
+        Ok(manifest)
+    }
+
+    fn binary_cache_path() -> std::path::PathBuf {
+        app_dir().join(BINARY_CACHE_FILE_NAME)
+    }
+
+    /// Identify the current `manifest.yaml` so that we can tell when the binary cache is stale.
+    fn binary_cache_stamp() -> Option<BinaryCacheStamp> {
+        let metadata = std::fs::metadata(Self::path()).ok()?;
+        Some(BinaryCacheStamp {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            modified: chrono::DateTime::<chrono::Utc>::from(metadata.modified().ok()?),
+            size: metadata.len(),
+        })
     }
 
-    pub fn should_update(config: &ManifestConfig, cache: &cache::Manifests, force: bool) -> bool {
+    fn load_binary_cache() -> Option<Self> {
+        let stamp = Self::binary_cache_stamp()?;
+        let content = std::fs::read(Self::binary_cache_path()).ok()?;
+        match rmp_serde::from_slice::<BinaryCache>(&content) {
+            Ok(cache) if cache.stamp == stamp => Some(cache.manifest),
+            Ok(_) => {
+                log::debug!("Binary manifest cache is stale");
+                None
+            }
+            Err(e) => {
+                log::warn!("Unable to read binary manifest cache: {}", e);
+                None
+            }
+        }
+    }
+
+    fn save_binary_cache(&self) {
+        let Some(stamp) = Self::binary_cache_stamp() else {
+            return;
+        };
+        let cache = BinaryCacheRef { stamp, manifest: self };
+        match rmp_serde::to_vec_named(&cache) {
+            Ok(content) => {
+                // Write to a temporary file first so that another instance never sees a partial cache.
+                let path = Self::binary_cache_path();
+                let temp = path.with_extension(format!("{}.tmp", std::process::id()));
+                if let Err(e) = std::fs::write(&temp, content).and_then(|_| std::fs::rename(&temp, &path)) {
+                    log::warn!("Unable to save binary manifest cache: {}", e);
+                    let _ = std::fs::remove_file(&temp);
+                }
+            }
+            Err(e) => {
+                log::warn!("Unable to serialize binary manifest cache: {}", e);
+            }
+        }
+    }
+
+    /// Load the primary manifest with any secondary manifests layered on top of it.
+    pub fn load_with_secondary(config: &Config) -> Result<Self, Error> {
+        Ok(Self::load_with_sources(config)?.0)
+    }
+
+    /// Like `load_with_secondary`, but also report which manifest provided each game.
+    pub fn load_with_sources(config: &Config) -> Result<(Self, HashMap<String, ManifestSource>), Error> {
+        Ok(Self::load()?.with_secondary(&config.manifest.secondary))
+    }
+
+    /// Layer secondary manifests on top of this one, in order.
+    /// A secondary manifest's entry replaces any earlier entry for the same game.
+    fn with_secondary(mut self, secondary: &[SecondaryManifestConfig]) -> (Self, HashMap<String, ManifestSource>) {
+        let mut sources: HashMap<_, _> = self
+            .0
+            .keys()
+            .map(|name| (name.clone(), ManifestSource::Primary))
+            .collect();
+
+        for config in secondary {
+            match Self::load_secondary(config) {
+                Ok(layer) => {
+                    for (name, game) in layer.0 {
+                        sources.insert(name.clone(), ManifestSource::Secondary(config.clone()));
+                        self.0.insert(name, game);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Unable to load secondary manifest {:?}: {:?}", config, e);
+                }
+            }
+        }
+
+        (self, sources)
+    }
+
+    fn load_secondary(config: &SecondaryManifestConfig) -> Result<Self, Error> {
+        let path = match config {
+            SecondaryManifestConfig::Local { path } => path.clone(),
+            SecondaryManifestConfig::Remote { url } => Self::secondary_path(url),
+        };
+        let content = path.read().ok_or_else(|| Error::ManifestInvalid {
+            why: format!("Unable to read {}", path.render()),
+        })?;
+        Self::load_from_string(&content).map_err(|e| Error::ManifestInvalid { why: format!("{}", e) })
+    }
+
+    /// Where a remote secondary manifest is kept after downloading it.
+    pub fn secondary_path(url: &str) -> StrictPath {
+        StrictPath::from(app_dir())
+            .joined("manifests")
+            .joined(&format!("{}.yaml", crate::prelude::sha1(url.to_string())))
+    }
+
+    fn should_update(url: &str, path: &StrictPath, cache: &cache::Manifests, force: bool) -> bool {
         if force {
             return true;
         }
-        if !Self::path().exists() {
+        if !path.exists() {
             return true;
         }
-        match cache.get(&config.url) {
+        match cache.get(url) {
             None => true,
             Some(cached) => {
                 let now = chrono::offset::Utc::now();
@@ -201,28 +509,67 @@ impl Manifest {
         }
     }
 
-    pub fn update(
-        config: ManifestConfig,
-        cache: cache::Manifests,
+    /// Download the primary manifest and any remote secondary manifests.
+    /// A secondary manifest that can't be updated is skipped,
+    /// and its last downloaded copy stays in use.
+    pub fn update(config: ManifestConfig, cache: cache::Manifests, force: bool) -> Result<Vec<ManifestUpdate>, Error> {
+        let mut updates = vec![];
+
+        if let Some(update) = Self::fetch(&config.url, &StrictPath::from(Self::path()), &cache, force, || {
+            Self::load().ok()
+        })? {
+            updates.push(update);
+        }
+
+        for secondary in &config.secondary {
+            if let SecondaryManifestConfig::Remote { url } = secondary {
+                match Self::fetch(url, &Self::secondary_path(url), &cache, force, || {
+                    Self::load_secondary(secondary).ok()
+                }) {
+                    Ok(Some(update)) => {
+                        updates.push(update);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        log::warn!("Unable to update secondary manifest {}: {:?}", url, e);
+                    }
+                }
+            }
+        }
+
+        Ok(updates)
+    }
+
+    /// Download a manifest if it's due for an update.
+    /// The previous copy is only loaded (via `load`) when a new version is about to replace it,
+    /// so that we can report what changed.
+    fn fetch(
+        url: &str,
+        path: &StrictPath,
+        cache: &cache::Manifests,
         force: bool,
+        load: impl Fn() -> Option<Self>,
     ) -> Result<Option<ManifestUpdate>, Error> {
-        if !Self::should_update(&config, &cache, force) {
+        if !Self::should_update(url, path, cache, force) {
             return Ok(None);
         }
 
-        let mut req = reqwest::blocking::Client::new().get(&config.url);
-        let old_etag = cache.get(&config.url).and_then(|x| x.etag.clone());
+        let mut req = reqwest::blocking::Client::new().get(url);
+        let old_etag = cache.get(url).and_then(|x| x.etag.clone());
         if let Some(etag) = old_etag.as_ref() {
-            if StrictPath::from_std_path_buf(&Self::path()).exists() {
+            if path.exists() {
                 req = req.header(reqwest::header::IF_NONE_MATCH, etag);
             }
         }
         let mut res = req.send().map_err(|_e| Error::ManifestCannotBeUpdated)?;
         match res.status() {
             reqwest::StatusCode::OK => {
-                std::fs::create_dir_all(app_dir()).map_err(|_| Error::ManifestCannotBeUpdated)?;
-                let mut file = std::fs::File::create(Self::path()).map_err(|_| Error::ManifestCannotBeUpdated)?;
+                let old = if path.exists() { load() } else { None };
+
+                path.create_parent_dir().map_err(|_| Error::ManifestCannotBeUpdated)?;
+                let mut file = std::fs::File::create(path.interpret()).map_err(|_| Error::ManifestCannotBeUpdated)?;
                 res.copy_to(&mut file).map_err(|_| Error::ManifestCannotBeUpdated)?;
+                drop(file);
 
                 let new_etag = res
                     .headers()
@@ -230,26 +577,30 @@ impl Manifest {
                     .map(|etag| String::from_utf8_lossy(etag.as_bytes()).to_string());
 
                 Ok(Some(ManifestUpdate {
-                    url: config.url,
+                    url: url.to_string(),
                     etag: new_etag,
                     timestamp: chrono::offset::Utc::now(),
                     modified: true,
+                    diff: old.zip(load()).map(|(old, new)| ManifestDiff::new(&old, &new)),
                 }))
             }
             reqwest::StatusCode::NOT_MODIFIED => Ok(Some(ManifestUpdate {
-                url: config.url,
+                url: url.to_string(),
                 etag: old_etag,
                 timestamp: chrono::offset::Utc::now(),
                 modified: false,
+                diff: None,
             })),
             _ => Err(Error::ManifestCannotBeUpdated),
         }
     }
 
     pub fn update_mut(config: &Config, cache: &mut Cache, force: bool) -> Result<(), Error> {
-        let updated = Self::update(config.manifest.clone(), cache.manifests.clone(), force)?;
-        if let Some(updated) = updated {
-            cache.update_manifest(updated);
+        let updates = Self::update(config.manifest.clone(), cache.manifests.clone(), force)?;
+        if !updates.is_empty() {
+            for update in updates {
+                cache.update_manifest(update);
+            }
             cache.save();
         }
         Ok(())
@@ -277,10 +628,24 @@ impl Manifest {
 
     pub fn add_custom_game(&mut self, custom: CustomGame) {
         let name = custom.name.clone();
+        let integration = custom.integration;
         let mut game: Game = custom.into();
         if let Some(existing) = self.0.get(&name) {
-            game.steam = existing.steam.clone();
-            game.install_dir = existing.install_dir.clone();
+            if integration == Integration::Extend {
+                let mut extended = existing.clone();
+                extended.extend(game);
+                game = extended;
+            }
+
+            if game.steam.is_none() {
+                game.steam = existing.steam.clone();
+            }
+            if game.gog.is_none() {
+                game.gog = existing.gog.clone();
+            }
+            if game.install_dir.is_none() {
+                game.install_dir = existing.install_dir.clone();
+            }
         }
         self.0.insert(name, game);
     }
@@ -304,8 +669,11 @@ impl Manifest {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::testing::s;
-    use maplit::btreemap;
+    use crate::{
+        config::{CustomGameFile, CustomGameRegistry},
+        testing::{repo, s},
+    };
+    use maplit::{btreemap, btreeset, hashmap};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -571,4 +939,317 @@ mod tests {
 
         assert_eq!(&GogMetadata { id: None }, manifest.0["game"].gog.as_ref().unwrap());
     }
+
+    #[test]
+    fn can_add_custom_game_with_all_fields() {
+        let mut manifest = Manifest::default();
+        manifest.add_custom_game(CustomGame {
+            name: s("game"),
+            files: vec![CustomGameFile {
+                path: s("<base>/saves"),
+                tags: vec![Tag::Save],
+                when: vec![GameFileConstraint {
+                    os: Some(Os::Windows),
+                    store: Some(Store::Steam),
+                }],
+            }],
+            registry: vec![CustomGameRegistry::new("HKEY_CURRENT_USER/Software/Game")],
+            install_dir: vec![s("Game Folder")],
+            steam: Some(SteamMetadata { id: Some(101) }),
+            gog: Some(GogMetadata { id: Some(102) }),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            Game {
+                files: Some(btreemap! {
+                    s("<base>/saves") => GameFileEntry {
+                        tags: Some(vec![Tag::Save]),
+                        when: Some(vec![GameFileConstraint {
+                            os: Some(Os::Windows),
+                            store: Some(Store::Steam),
+                        }]),
+                    },
+                }),
+                install_dir: Some(btreemap! {
+                    s("Game Folder") => GameInstallDirEntry {},
+                }),
+                registry: Some(btreemap! {
+                    s("HKEY_CURRENT_USER/Software/Game") => GameRegistryEntry::default(),
+                }),
+                steam: Some(SteamMetadata { id: Some(101) }),
+                gog: Some(GogMetadata { id: Some(102) }),
+            },
+            manifest.0["game"],
+        );
+    }
+
+    #[test]
+    fn custom_game_inherits_ids_and_install_dir_from_manifest_unless_set() {
+        let mut manifest = Manifest::load_from_string(
+            r#"
+            game:
+              files:
+                <base>/original: {}
+              installDir:
+                Original Folder: {}
+              steam:
+                id: 101
+              gog:
+                id: 102
+            "#,
+        )
+        .unwrap();
+
+        manifest.add_custom_game(CustomGame {
+            name: s("game"),
+            files: vec![CustomGameFile::new("<base>/custom")],
+            steam: Some(SteamMetadata { id: Some(201) }),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            Game {
+                files: Some(btreemap! {
+                    s("<base>/custom") => GameFileEntry::default(),
+                }),
+                install_dir: Some(btreemap! {
+                    s("Original Folder") => GameInstallDirEntry {},
+                }),
+                registry: Some(btreemap! {}),
+                steam: Some(SteamMetadata { id: Some(201) }),
+                gog: Some(GogMetadata { id: Some(102) }),
+            },
+            manifest.0["game"],
+        );
+    }
+
+    #[test]
+    fn can_extend_manifest_entry_with_custom_game() {
+        let mut manifest = Manifest::load_from_string(
+            r#"
+            game:
+              files:
+                <base>/original: {}
+                <base>/shared:
+                  tags: [save]
+              registry:
+                HKEY_CURRENT_USER/Software/Original: {}
+              installDir:
+                Original Folder: {}
+              steam:
+                id: 101
+            "#,
+        )
+        .unwrap();
+
+        manifest.add_custom_game(CustomGame {
+            name: s("game"),
+            integration: Integration::Extend,
+            files: vec![
+                CustomGameFile::new("<base>/custom"),
+                CustomGameFile {
+                    path: s("<base>/shared"),
+                    tags: vec![Tag::Config],
+                    when: vec![],
+                },
+            ],
+            install_dir: vec![s("Custom Folder")],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            Game {
+                files: Some(btreemap! {
+                    s("<base>/custom") => GameFileEntry::default(),
+                    s("<base>/original") => GameFileEntry::default(),
+                    s("<base>/shared") => GameFileEntry {
+                        tags: Some(vec![Tag::Config]),
+                        when: None,
+                    },
+                }),
+                install_dir: Some(btreemap! {
+                    s("Custom Folder") => GameInstallDirEntry {},
+                    s("Original Folder") => GameInstallDirEntry {},
+                }),
+                registry: Some(btreemap! {
+                    s("HKEY_CURRENT_USER/Software/Original") => GameRegistryEntry::default(),
+                }),
+                steam: Some(SteamMetadata { id: Some(101) }),
+                gog: None,
+            },
+            manifest.0["game"],
+        );
+    }
+
+    #[test]
+    fn can_layer_secondary_manifests() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game:
+              files:
+                <base>/primary: {}
+            primary-game:
+              files:
+                <base>/file.txt: {}
+            "#,
+        )
+        .unwrap();
+        let local = SecondaryManifestConfig::Local {
+            path: StrictPath::new(format!("{}/tests/manifests/secondary.yaml", repo())),
+        };
+        let missing = SecondaryManifestConfig::Local {
+            path: StrictPath::new(format!("{}/tests/manifests/nonexistent.yaml", repo())),
+        };
+
+        let (manifest, sources) = manifest.with_secondary(&[local.clone(), missing]);
+
+        assert_eq!(
+            Some(btreemap! { s("<base>/secondary") => GameFileEntry::default() }),
+            manifest.0["game"].files,
+        );
+        assert!(manifest.0.contains_key("primary-game"));
+        assert!(manifest.0.contains_key("secondary-game"));
+        assert_eq!(
+            hashmap! {
+                s("game") => ManifestSource::Secondary(local.clone()),
+                s("primary-game") => ManifestSource::Primary,
+                s("secondary-game") => ManifestSource::Secondary(local),
+            },
+            sources,
+        );
+    }
+
+    #[test]
+    fn binary_cache_and_yaml_load_the_same_manifest() {
+        let mut yaml = String::new();
+        for i in 0..100 {
+            yaml.push_str(&format!(
+                r#"
+game-{i}:
+  files:
+    <base>/saves/{i}:
+      tags: [save]
+      when:
+        - os: windows
+          store: steam
+        - os: dos
+    <home>/.config/game-{i}: {{}}
+  installDir:
+    Game {i}: {{}}
+  registry:
+    HKEY_CURRENT_USER/Software/Game {i}:
+      tags: [config]
+      when:
+        - store: gog
+  steam:
+    id: {i}
+  gog: {{}}
+"#
+            ));
+        }
+        let stamp = || BinaryCacheStamp {
+            version: s("1.0.0"),
+            modified: chrono::DateTime::<chrono::Utc>::default(),
+            size: yaml.len() as u64,
+        };
+
+        let from_yaml = Manifest::load_from_string(&yaml).unwrap();
+
+        let binary = rmp_serde::to_vec_named(&BinaryCacheRef {
+            stamp: stamp(),
+            manifest: &from_yaml,
+        })
+        .unwrap();
+
+        let from_binary = rmp_serde::from_slice::<BinaryCache>(&binary).unwrap();
+
+        assert_eq!(stamp(), from_binary.stamp);
+        assert_eq!(
+            GameFileEntry {
+                tags: Some(vec![Tag::Save]),
+                when: Some(vec![
+                    GameFileConstraint {
+                        os: Some(Os::Windows),
+                        store: Some(Store::Steam),
+                    },
+                    GameFileConstraint {
+                        os: Some(Os::Other),
+                        store: None,
+                    },
+                ]),
+            },
+            from_binary.manifest.0["game-1"].files.as_ref().unwrap()["<base>/saves/1"],
+        );
+        assert!(from_yaml == from_binary.manifest);
+    }
+
+    #[test]
+    fn can_diff_manifests() {
+        let old = Manifest::load_from_string(
+            r#"
+            unchanged:
+              files:
+                <base>/file.txt: {}
+            changed:
+              files:
+                <base>/old: {}
+                <base>/same: {}
+              registry:
+                HKEY_CURRENT_USER/Software/Old: {}
+            removed: {}
+            old name:
+              files:
+                <base>/file.txt: {}
+              steam:
+                id: 101
+            "#,
+        )
+        .unwrap();
+        let new = Manifest::load_from_string(
+            r#"
+            unchanged:
+              files:
+                <base>/file.txt: {}
+            changed:
+              files:
+                <base>/new: {}
+                <base>/same: {}
+            added: {}
+            new name:
+              files:
+                <base>/renamed.txt: {}
+              steam:
+                id: 101
+            "#,
+        )
+        .unwrap();
+
+        let diff = ManifestDiff::new(&old, &new);
+        assert_eq!(
+            ManifestDiff {
+                added: btreeset! { s("added") },
+                removed: btreeset! { s("removed") },
+                renamed: btreemap! { s("old name") => s("new name") },
+                changed: btreemap! {
+                    s("changed") => GamePathsDiff {
+                        added_files: btreeset! { s("<base>/new") },
+                        removed_files: btreeset! { s("<base>/old") },
+                        added_registry: btreeset! {},
+                        removed_registry: btreeset! { s("HKEY_CURRENT_USER/Software/Old") },
+                    },
+                    s("new name") => GamePathsDiff {
+                        added_files: btreeset! { s("<base>/renamed.txt") },
+                        removed_files: btreeset! { s("<base>/file.txt") },
+                        ..Default::default()
+                    },
+                },
+            },
+            diff,
+        );
+        assert_eq!(
+            btreeset! { s("changed"), s("old name"), s("removed") },
+            diff.affected_games(),
+        );
+    }
 }
@@ -1,8 +1,11 @@
 use std::{path::PathBuf, sync::Mutex};
 
 use crate::{
-    config::{BackupFilter, BackupFormats, RedirectConfig, RedirectKind, RootsConfig, ToggledPaths, ToggledRegistry},
-    heroic::HeroicGames,
+    config::{
+        BackupFilter, BackupFormats, CompiledFilter, RedirectConfig, RedirectKind, RootsConfig, ToggledPaths,
+        ToggledRegistry,
+    },
+    launchers::Launchers,
     layout::{Backup, BackupLayout, GameLayout, LatestBackup},
     manifest::{Game, Manifest, Os, Store},
 };
@@ -41,6 +44,25 @@ pub enum Error {
     CliUnableToRequestConfirmation,
     CliBackupIdWithMultipleGames,
     CliInvalidBackupId,
+    CliFileNotInBackup {
+        path: StrictPath,
+    },
+    CliAmbiguousGame {
+        games: Vec<String>,
+    },
+    CliUnrecognizedSteamUser {
+        user: String,
+    },
+    CliInvalidPattern {
+        pattern: String,
+        why: String,
+    },
+    GameDidNotLaunch {
+        why: String,
+    },
+    ServerDidNotStart {
+        why: String,
+    },
     SomeEntriesFailed,
     CannotPrepareBackupTarget {
         path: StrictPath,
@@ -53,6 +75,14 @@ pub enum Error {
     UnableToBrowseFileSystem,
     UnableToOpenDir(StrictPath),
     UnableToOpenUrl(String),
+    UnableToMigrateRenamedGame {
+        old: String,
+        new: String,
+    },
+    HookFailed {
+        command: String,
+        why: String,
+    },
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Serialize)]
@@ -138,6 +168,9 @@ pub struct ScannedFile {
     /// This is the restoration target path, without redirects applied.
     pub original_path: Option<StrictPath>,
     pub ignored: bool,
+    /// Set when a game-specific rule leaves this file out of the backup.
+    /// In that case, `ignored` is always true.
+    pub excluded: Option<BackupExclusion>,
     pub change: ScanChange,
     /// An enclosing archive file, if any, depending on the `BackupFormat`.
     pub container: Option<StrictPath>,
@@ -153,6 +186,7 @@ impl ScannedFile {
             hash: hash.to_string(),
             original_path: None,
             ignored: false,
+            excluded: None,
             change: Default::default(),
             container: None,
             redirected: None,
@@ -165,6 +199,19 @@ impl ScannedFile {
         self
     }
 
+    #[cfg(test)]
+    pub fn excluded(mut self, exclusion: BackupExclusion) -> Self {
+        self.ignored = true;
+        self.excluded = Some(exclusion);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn original<T: AsRef<str> + ToString>(mut self, original_path: T) -> Self {
+        self.original_path = Some(StrictPath::new(original_path.to_string()));
+        self
+    }
+
     #[cfg(test)]
     pub fn change(mut self, change: ScanChange) -> Self {
         self.change = change;
@@ -304,6 +351,8 @@ pub struct ScanInfo {
     pub available_backups: Vec<Backup>,
     /// Only populated by a restoration scan.
     pub backup: Option<Backup>,
+    /// Only populated by a backup scan, from the game's `maxTotalSize` rule.
+    pub max_total_size: Option<u64>,
 }
 
 impl ScanInfo {
@@ -322,6 +371,11 @@ impl ScanInfo {
         successful_bytes - failed_bytes
     }
 
+    /// The game's size limit, if the files to back up are larger than it.
+    pub fn size_limit_exceeded(&self) -> Option<u64> {
+        self.max_total_size.filter(|limit| self.sum_bytes(&None) > *limit)
+    }
+
     pub fn total_possible_bytes(&self) -> u64 {
         self.found_files.iter().map(|x| x.size).sum::<u64>()
     }
@@ -344,7 +398,7 @@ impl ScanInfo {
             .iter()
             .map(|x| {
                 let mut y = x.clone();
-                y.ignored = toggled_paths.is_ignored(&self.game_name, &x.path);
+                y.ignored = x.excluded.is_some() || toggled_paths.is_ignored(&self.game_name, x.original_path());
                 y
             })
             .collect();
@@ -450,6 +504,108 @@ impl BackupInfo {
     pub fn successful(&self) -> bool {
         self.failed_files.is_empty() && self.failed_registry.is_empty()
     }
+
+    /// Every item in the scan that would have been processed.
+    pub fn all_failed(info: &ScanInfo) -> Self {
+        let mut backup_info = Self::default();
+
+        for file in &info.found_files {
+            if file.ignored {
+                continue;
+            }
+            backup_info.failed_files.insert(file.clone());
+        }
+        for reg_path in &info.found_registry_keys {
+            if reg_path.ignored {
+                continue;
+            }
+            backup_info.failed_registry.insert(reg_path.path.clone());
+        }
+
+        backup_info
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Serialize)]
+pub enum FileDiffKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileDiff {
+    pub kind: FileDiffKind,
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+}
+
+impl FileDiff {
+    pub fn size_delta(&self) -> i64 {
+        self.new_size.unwrap_or_default() as i64 - self.old_size.unwrap_or_default() as i64
+    }
+}
+
+/// Changed files between two sets of scanned files, keyed by the effective path.
+/// Files with the same content on both sides are not included.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GameDiff(pub std::collections::BTreeMap<String, FileDiff>);
+
+impl GameDiff {
+    pub fn new(old: &std::collections::HashSet<ScannedFile>, new: &std::collections::HashSet<ScannedFile>) -> Self {
+        let mut diff = std::collections::BTreeMap::new();
+
+        let old: std::collections::HashMap<_, _> = old.iter().map(|x| (x.effective().render(), x)).collect();
+        let new: std::collections::HashMap<_, _> = new.iter().map(|x| (x.effective().render(), x)).collect();
+
+        for (path, old_file) in &old {
+            match new.get(path) {
+                None => {
+                    diff.insert(
+                        path.clone(),
+                        FileDiff {
+                            kind: FileDiffKind::Removed,
+                            old_size: Some(old_file.size),
+                            new_size: None,
+                        },
+                    );
+                }
+                Some(new_file) if new_file.hash != old_file.hash => {
+                    diff.insert(
+                        path.clone(),
+                        FileDiff {
+                            kind: FileDiffKind::Modified,
+                            old_size: Some(old_file.size),
+                            new_size: Some(new_file.size),
+                        },
+                    );
+                }
+                Some(_) => {}
+            }
+        }
+        for (path, new_file) in &new {
+            if !old.contains_key(path) {
+                diff.insert(
+                    path.clone(),
+                    FileDiff {
+                        kind: FileDiffKind::Added,
+                        old_size: None,
+                        new_size: Some(new_file.size),
+                    },
+                );
+            }
+        }
+
+        Self(diff)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn size_delta(&self) -> i64 {
+        self.0.values().map(|x| x.size_delta()).sum()
+    }
 }
 
 #[derive(Clone, Debug, Default, serde::Serialize)]
@@ -639,7 +795,7 @@ pub fn parse_paths(
                 "<base>",
                 &match root.store {
                     Store::Steam => format!("{}/steamapps/common/{}", &root_interpreted, install_dir),
-                    Store::Heroic => full_install_dir
+                    Store::Bottles | Store::Heroic | Store::Itch | Store::Lutris => full_install_dir
                         .map(|x| x.interpret())
                         .unwrap_or_else(|| SKIP.to_string()),
                     _ => format!("{}/{}", &root_interpreted, install_dir),
@@ -828,6 +984,8 @@ pub fn parse_paths(
         .collect()
 }
 
+static RE_STEAM_INSTALL_DIR: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)"installdir"\s+"([^"]+)""#).unwrap());
+
 #[derive(Clone, Default)]
 pub struct InstallDirRanking(std::collections::HashMap<(RootsConfig, String), (i64, String)>);
 
@@ -851,8 +1009,8 @@ impl InstallDirRanking {
     pub fn scan(roots: &[RootsConfig], manifest: &crate::manifest::Manifest, subjects: &[String]) -> Self {
         let mut ranking = Self::default();
         for root in roots {
-            if root.store == Store::Heroic {
-                // We handle this separately in the Heroic scan.
+            if matches!(root.store, Store::Bottles | Store::Heroic | Store::Itch | Store::Lutris) {
+                // We handle these separately in the launcher scans.
                 continue;
             }
             ranking.scan_root(root, manifest, subjects);
@@ -867,6 +1025,10 @@ impl InstallDirRanking {
             Store::Steam => root.path.joined("steamapps/common"),
             _ => root.path.clone(),
         };
+        let steam_install_dirs = match root.store {
+            Store::Steam => Self::find_steam_install_dirs(root),
+            _ => Default::default(),
+        };
         let matcher = make_fuzzy_matcher();
 
         let actual_dirs: Vec<_> = std::fs::read_dir(install_parent.interpret())
@@ -884,6 +1046,12 @@ impl InstallDirRanking {
         let scores: Vec<_> = subjects
             .into_par_iter()
             .filter_map(|name| {
+                let steam_id = manifest.0[name].steam.as_ref().and_then(|x| x.id);
+                if let Some(subdir) = steam_id.and_then(|id| steam_install_dirs.get(&id)) {
+                    log::debug!("[{name}] selecting subdir from Steam appmanifest: {subdir}");
+                    return Some((i64::MAX, name, subdir));
+                }
+
                 let manifest_install_dirs: Vec<_> = manifest.0[name]
                     .install_dir
                     .as_ref()
@@ -928,6 +1096,35 @@ impl InstallDirRanking {
                 .insert((root.clone(), name.to_owned()), (score, subdir.to_owned()));
         }
     }
+
+    /// Steam records the exact install folder of each app in `steamapps/appmanifest_<id>.acf`.
+    fn find_steam_install_dirs(root: &RootsConfig) -> std::collections::HashMap<u32, String> {
+        let mut install_dirs = std::collections::HashMap::new();
+
+        let Ok(entries) = std::fs::read_dir(root.path.joined("steamapps").interpret()) else {
+            return install_dirs;
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(id) = file_name
+                .strip_prefix("appmanifest_")
+                .and_then(|x| x.strip_suffix(".acf"))
+                .and_then(|x| x.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            if let Some(captures) = RE_STEAM_INSTALL_DIR.captures(&content) {
+                log::trace!("Steam appmanifest for {id} has install dir: {}", &captures[1]);
+                install_dirs.insert(id, captures[1].to_string());
+            }
+        }
+
+        install_dirs
+    }
 }
 
 #[derive(Clone, Default)]
@@ -984,29 +1181,164 @@ pub fn filter_map_walkdir(e: Result<walkdir::DirEntry, walkdir::Error>) -> Optio
     e.ok()
 }
 
-pub fn scan_game_for_backup(
+/// Where a candidate path came from.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PathCandidateSource {
+    /// A path from the game's manifest entry.
+    Manifest(String),
+    SteamCloud,
+    SteamScreenshots,
+    WineRegistry,
+}
+
+/// A concrete location where a game's files may be found, after expanding placeholders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathCandidate {
+    /// The root that produced this candidate.
+    /// This is unset for paths that don't depend on any root.
+    pub root: Option<RootsConfig>,
+    pub source: PathCandidateSource,
+    pub path: StrictPath,
+    pub case_sensitive: Option<bool>,
+}
+
+/// Why a file would be left out of a backup.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupExclusion {
+    /// Matched by `backup.filter.ignoredPaths`.
+    IgnoredPath(StrictPath),
+    /// Belongs to a Steam user not listed in `backup.filter.steamUsers`.
+    SteamUser(String),
+    /// Deselected for this game specifically.
+    Toggled,
+    /// Matched by the game's own `ignoredPaths` in `backup.filter.games`.
+    GameIgnoredPath(StrictPath),
+    /// Larger than the game's `maxFileSize` in `backup.filter.games`.
+    MaxFileSize(u64),
+}
+
+/// What a candidate path matches on this machine.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PathCandidateCheck {
+    pub exists: bool,
+    /// Number of files that would be backed up.
+    pub files: usize,
+    /// Number of files that would be left out, by reason.
+    pub excluded: std::collections::BTreeMap<BackupExclusion, usize>,
+}
+
+impl PathCandidate {
+    pub fn check(&self, game: &str, filter: &CompiledFilter, toggled_paths: &ToggledPaths) -> PathCandidateCheck {
+        let mut check = PathCandidateCheck::default();
+        let candidate_exclusion = filter.path_exclusion(&self.path);
+
+        for matched in glob_candidate(&self.path, self.case_sensitive) {
+            check.exists = true;
+            for file in files_in_match(&matched) {
+                let exclusion = candidate_exclusion
+                    .clone()
+                    .or_else(|| filter.path_exclusion(&file))
+                    .or_else(|| filter.game_file_exclusion(game, &file, file.size()))
+                    .or_else(|| {
+                        toggled_paths
+                            .is_ignored(game, &file)
+                            .then_some(BackupExclusion::Toggled)
+                    });
+                match exclusion {
+                    Some(exclusion) => *check.excluded.entry(exclusion).or_default() += 1,
+                    None => check.files += 1,
+                }
+            }
+        }
+
+        check
+    }
+
+    /// The leading part of the path without any glob patterns,
+    /// which contains every file that the candidate could match.
+    /// This is unset if the path could not be resolved.
+    pub fn fixed_prefix(&self) -> Option<std::path::PathBuf> {
+        let interpreted = self.path.interpret();
+        if interpreted.is_empty() || interpreted.contains(SKIP) {
+            return None;
+        }
+
+        let prefix: std::path::PathBuf = std::path::Path::new(&interpreted)
+            .components()
+            .take_while(|x| !x.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+            .collect();
+        prefix
+            .components()
+            .any(|x| matches!(x, std::path::Component::Normal(_)))
+            .then_some(prefix)
+    }
+}
+
+fn glob_candidate(path: &StrictPath, case_sensitive: Option<bool>) -> Vec<StrictPath> {
+    let paths = match case_sensitive {
+        None => path.glob(),
+        Some(cs) => path.glob_case_sensitive(cs),
+    };
+    paths.into_iter().map(|x| x.rendered()).collect()
+}
+
+/// The matched path itself if it's a file, or else all of the files within it.
+fn files_in_match(path: &StrictPath) -> Vec<StrictPath> {
+    if path.is_file() {
+        vec![path.clone()]
+    } else if path.is_dir() {
+        log::trace!("looking for files in: {}", path.raw());
+        walkdir::WalkDir::new(path.as_std_path_buf())
+            .max_depth(100)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(filter_map_walkdir)
+            .filter(|x| x.file_type().is_file())
+            .map(|x| StrictPath::from(&x).rendered())
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+/// The folder name to use for `<game>` in a root.
+fn install_dir_name(
+    root: &RootsConfig,
+    name: &str,
+    ranking: &InstallDirRanking,
+    full_install_dir: Option<&StrictPath>,
+) -> Option<String> {
+    let from_launcher =
+        || full_install_dir.and_then(|x| x.as_std_path_buf().file_name().map(|x| x.to_string_lossy().to_string()));
+
+    match root.store {
+        // These launchers know the exact install folder, which is more reliable than guessing from folder names.
+        Store::Bottles | Store::Itch | Store::Lutris => from_launcher().or_else(|| ranking.get(root, name)),
+        _ => ranking.get(root, name),
+    }
+}
+
+/// Expand a game's paths into every concrete location that a backup would check.
+pub fn find_path_candidates(
     game: &Game,
     name: &str,
     roots: &[RootsConfig],
     manifest_dir: &StrictPath,
-    heroic_games: &HeroicGames,
+    launchers: &Launchers,
     steam_id: &Option<u32>,
     filter: &BackupFilter,
     wine_prefix: &Option<StrictPath>,
     ranking: &InstallDirRanking,
-    ignored_paths: &ToggledPaths,
-    #[allow(unused_variables)] ignored_registry: &ToggledRegistry,
-    previous: Option<LatestBackup>,
-    redirects: &[RedirectConfig],
     steam_shortcuts: &SteamShortcuts,
-) -> ScanInfo {
-    log::trace!("[{name}] beginning scan for backup");
-
-    let mut found_files = std::collections::HashSet::new();
-    #[allow(unused_mut)]
-    let mut found_registry_keys = std::collections::HashSet::new();
-
-    let mut paths_to_check = std::collections::HashSet::<(StrictPath, Option<bool>)>::new();
+) -> Vec<PathCandidate> {
+    let mut candidates = vec![];
+    let mut add = |candidate: PathCandidate| {
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    };
 
     // Add a dummy root for checking paths without `<root>`.
     let mut roots_to_check: Vec<RootsConfig> = vec![RootsConfig {
@@ -1024,20 +1356,20 @@ pub fn scan_game_for_backup(
         log::trace!("[{name}] adding extra Wine prefix: {}", wp.raw());
         scan_game_for_backup_add_prefix(
             &mut roots_to_check,
-            &mut paths_to_check,
+            &mut add,
             wp,
             &manifest_dir_interpreted,
             game.registry.is_some(),
         );
     }
 
-    // handle what was found for heroic
+    // handle what was found for launchers like Heroic, Lutris, and Bottles
     for root in roots {
-        if let Some(wp) = heroic_games.get_prefix(root, name) {
+        if let Some(wp) = launchers.get_prefix(root, name) {
             let with_pfx = wp.joined("pfx");
             scan_game_for_backup_add_prefix(
                 &mut roots_to_check,
-                &mut paths_to_check,
+                &mut add,
                 if with_pfx.exists() { &with_pfx } else { wp },
                 &manifest_dir_interpreted,
                 game.registry.is_some(),
@@ -1055,10 +1387,11 @@ pub fn scan_game_for_backup(
             continue;
         }
         let root_interpreted = root.path.interpret();
+        let candidate_root = (root.path.raw() != SKIP).then(|| root.clone());
 
         if let Some(files) = &game.files {
-            let install_dir = ranking.get(&root, name);
-            let full_install_dir = heroic_games.get_install_dir(&root, name);
+            let full_install_dir = launchers.get_install_dir(&root, name);
+            let install_dir = install_dir_name(&root, name, ranking, full_install_dir);
 
             for raw_path in files.keys() {
                 log::trace!("[{name}] parsing candidates from: {}", raw_path);
@@ -1080,24 +1413,33 @@ pub fn scan_game_for_backup(
                         // This covers `SKIP` and any other unmatched placeholders.
                         continue;
                     }
-                    paths_to_check.insert((candidate, case_sensitive));
+                    add(PathCandidate {
+                        root: candidate_root.clone(),
+                        source: PathCandidateSource::Manifest(raw_path.clone()),
+                        path: candidate,
+                        case_sensitive,
+                    });
                 }
             }
         }
         if root.store == Store::Steam && steam_id.is_some() {
             // Cloud saves:
-            paths_to_check.insert((
-                StrictPath::relative(
+            add(PathCandidate {
+                root: candidate_root.clone(),
+                source: PathCandidateSource::SteamCloud,
+                path: StrictPath::relative(
                     format!("{}/userdata/*/{}/remote/", root_interpreted.clone(), &steam_id.unwrap()),
                     Some(manifest_dir_interpreted.clone()),
                 ),
-                None,
-            ));
+                case_sensitive: None,
+            });
 
             // Screenshots:
             if !filter.exclude_store_screenshots {
-                paths_to_check.insert((
-                    StrictPath::relative(
+                add(PathCandidate {
+                    root: candidate_root.clone(),
+                    source: PathCandidateSource::SteamScreenshots,
+                    path: StrictPath::relative(
                         format!(
                             "{}/userdata/*/760/remote/{}/screenshots/*.*",
                             &root_interpreted,
@@ -1105,21 +1447,64 @@ pub fn scan_game_for_backup(
                         ),
                         Some(manifest_dir_interpreted.clone()),
                     ),
-                    None,
-                ));
+                    case_sensitive: None,
+                });
             }
 
             // Registry:
             if game.registry.is_some() {
                 let prefix = format!("{}/steamapps/compatdata/{}/pfx", &root_interpreted, steam_id.unwrap());
-                paths_to_check.insert((
-                    StrictPath::relative(format!("{}/*.reg", prefix), Some(manifest_dir_interpreted.clone())),
-                    None,
-                ));
+                add(PathCandidate {
+                    root: candidate_root.clone(),
+                    source: PathCandidateSource::WineRegistry,
+                    path: StrictPath::relative(format!("{}/*.reg", prefix), Some(manifest_dir_interpreted.clone())),
+                    case_sensitive: None,
+                });
             }
         }
     }
 
+    candidates
+}
+
+pub fn scan_game_for_backup(
+    game: &Game,
+    name: &str,
+    roots: &[RootsConfig],
+    manifest_dir: &StrictPath,
+    launchers: &Launchers,
+    steam_id: &Option<u32>,
+    filter: &CompiledFilter,
+    wine_prefix: &Option<StrictPath>,
+    ranking: &InstallDirRanking,
+    ignored_paths: &ToggledPaths,
+    #[allow(unused_variables)] ignored_registry: &ToggledRegistry,
+    previous: Option<LatestBackup>,
+    redirects: &[RedirectConfig],
+    steam_shortcuts: &SteamShortcuts,
+) -> ScanInfo {
+    log::trace!("[{name}] beginning scan for backup");
+
+    let mut found_files = std::collections::HashSet::new();
+    #[allow(unused_mut)]
+    let mut found_registry_keys = std::collections::HashSet::new();
+
+    let paths_to_check: std::collections::HashSet<(StrictPath, Option<bool>)> = find_path_candidates(
+        game,
+        name,
+        roots,
+        manifest_dir,
+        launchers,
+        steam_id,
+        &filter.rules,
+        wine_prefix,
+        ranking,
+        steam_shortcuts,
+    )
+    .into_iter()
+    .map(|x| (x.path, x.case_sensitive))
+    .collect();
+
     let previous_files: std::collections::HashMap<&StrictPath, &String> = previous
         .as_ref()
         .map(|previous| {
@@ -1132,70 +1517,52 @@ pub fn scan_game_for_backup(
         })
         .unwrap_or_default();
 
+    let game_filter = filter.rules.games.get(name);
+
     for (path, case_sensitive) in paths_to_check {
         log::trace!("[{name}] checking: {}", path.raw());
         if filter.is_path_ignored(&path) {
             log::debug!("[{name}] excluded: {}", path.raw());
             continue;
         }
-        let paths = match case_sensitive {
-            None => path.glob(),
-            Some(cs) => path.glob_case_sensitive(cs),
-        };
-        for p in paths {
-            let p = p.rendered();
-            if p.is_file() {
-                if filter.is_path_ignored(&p) {
-                    log::debug!("[{name}] excluded: {}", p.raw());
+        for p in glob_candidate(&path, case_sensitive) {
+            for file in files_in_match(&p) {
+                if filter.is_path_ignored(&file) {
+                    log::debug!("[{name}] excluded: {}", file.raw());
                     continue;
                 }
-                let ignored = ignored_paths.is_ignored(name, &p);
-                log::debug!("[{name}] found: {}", p.raw());
-                let hash = p.sha1();
-                let redirected = game_file_target(&p, redirects, false);
+                let size = file.size();
+                let redirected = game_file_target(&file, redirects, false);
+                if let Some(excluded) = filter.game_file_exclusion(name, &file, size) {
+                    // There's no need to hash files that will never be backed up.
+                    log::debug!("[{name}] excluded by game rule ({excluded:?}): {}", file.raw());
+                    found_files.insert(ScannedFile {
+                        change: ScanChange::Unknown,
+                        size,
+                        hash: String::new(),
+                        redirected,
+                        path: file,
+                        original_path: None,
+                        ignored: true,
+                        excluded: Some(excluded),
+                        container: None,
+                    });
+                    continue;
+                }
+                let ignored = ignored_paths.is_ignored(name, &file);
+                log::debug!("[{name}] found: {}", file.raw());
+                let hash = file.sha1();
                 found_files.insert(ScannedFile {
-                    change: ScanChange::evaluate(&hash, previous_files.get(redirected.as_ref().unwrap_or(&p))),
-                    size: p.size(),
+                    change: ScanChange::evaluate(&hash, previous_files.get(redirected.as_ref().unwrap_or(&file))),
+                    size,
                     hash,
                     redirected,
-                    path: p,
+                    path: file,
                     original_path: None,
                     ignored,
+                    excluded: None,
                     container: None,
                 });
-            } else if p.is_dir() {
-                log::trace!("[{name}] looking for files in: {}", p.raw());
-                for child in walkdir::WalkDir::new(p.as_std_path_buf())
-                    .max_depth(100)
-                    .follow_links(true)
-                    .into_iter()
-                    .filter_map(filter_map_walkdir)
-                {
-                    if child.file_type().is_file() {
-                        let child = StrictPath::from(&child).rendered();
-                        if filter.is_path_ignored(&child) {
-                            log::debug!("[{name}] excluded: {}", child.raw());
-                            continue;
-                        }
-                        let ignored = ignored_paths.is_ignored(name, &child);
-                        log::debug!("[{name}] found: {}", child.raw());
-                        let hash = child.sha1();
-                        let redirected = game_file_target(&child, redirects, false);
-                        found_files.insert(ScannedFile {
-                            change: ScanChange::evaluate(
-                                &hash,
-                                previous_files.get(redirected.as_ref().unwrap_or(&child)),
-                            ),
-                            size: child.size(),
-                            hash,
-                            redirected,
-                            path: child,
-                            original_path: None,
-                            ignored,
-                            container: None,
-                        });
-                    }
-                }
             }
         }
     }
@@ -1249,30 +1616,34 @@ pub fn scan_game_for_backup(
         game_name: name.to_string(),
         found_files,
         found_registry_keys,
+        max_total_size: game_filter.and_then(|x| x.max_total_size),
         ..Default::default()
     }
 }
 
 fn scan_game_for_backup_add_prefix(
     roots_to_check: &mut Vec<RootsConfig>,
-    paths_to_check: &mut std::collections::HashSet<(StrictPath, Option<bool>)>,
+    add: &mut impl FnMut(PathCandidate),
     wp: &StrictPath,
     manifest_dir_interpreted: &str,
     has_registry: bool,
 ) {
-    roots_to_check.push(RootsConfig {
+    let root = RootsConfig {
         path: wp.clone(),
         store: Store::OtherWine,
-    });
+    };
     if has_registry {
-        paths_to_check.insert((
-            StrictPath::relative(
+        add(PathCandidate {
+            root: Some(root.clone()),
+            source: PathCandidateSource::WineRegistry,
+            path: StrictPath::relative(
                 format!("{}/*.reg", wp.interpret()),
                 Some(manifest_dir_interpreted.to_owned()),
             ),
-            None,
-        ));
+            case_sensitive: None,
+        });
     }
+    roots_to_check.push(root);
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -1280,6 +1651,119 @@ pub enum BackupId {
     #[default]
     Latest,
     Named(String),
+    /// The newest backup made at or before this time.
+    AsOf(chrono::DateTime<chrono::Utc>),
+}
+
+/// Parse a point in time for `BackupId::AsOf`.
+/// This accepts RFC 3339 (e.g., `2023-03-01T12:00:00Z`) or a local date and time
+/// (e.g., `2023-03-01T12:00:00`, `2023-03-01 12:00`).
+/// A date without a time (e.g., `2023-03-01`) means the very end of that day,
+/// so that backups from its last second are still included.
+pub fn parse_as_of(raw: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::TimeZone;
+
+    let raw = raw.trim();
+
+    if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(raw) {
+        return Some(parsed.with_timezone(&chrono::Utc));
+    }
+
+    let naive = [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| chrono::NaiveDateTime::parse_from_str(raw, format).ok())
+    .or_else(|| {
+        chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_nano_opt(23, 59, 59, 999_999_999))
+    })?;
+
+    chrono::Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|x| x.with_timezone(&chrono::Utc))
+}
+
+/// Glob patterns to select which files of a backup get restored.
+/// Each pattern is checked against the file's original path and its parent folders.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RestoreFilter {
+    include: Vec<globetter::Pattern>,
+    exclude: Vec<globetter::Pattern>,
+}
+
+impl RestoreFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, Error> {
+        let compile = |patterns: &[String]| -> Result<Vec<_>, Error> {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    globetter::Pattern::new(pattern).map_err(|e| Error::CliInvalidPattern {
+                        pattern: pattern.clone(),
+                        why: e.to_string(),
+                    })
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn is_path_ignored(&self, path: &StrictPath) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let rendered = path.render();
+        if !self.include.is_empty() && !Self::matches_any(&self.include, &rendered) {
+            return true;
+        }
+        Self::matches_any(&self.exclude, &rendered)
+    }
+
+    fn matches_any(patterns: &[globetter::Pattern], rendered: &str) -> bool {
+        let options = globetter::MatchOptions {
+            case_sensitive: !CASE_INSENSITIVE_OS,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        let candidates: Vec<_> = rendered
+            .match_indices('/')
+            .map(|(i, _)| &rendered[..i])
+            .filter(|x| !x.is_empty())
+            .chain(std::iter::once(rendered))
+            .collect();
+
+        patterns
+            .iter()
+            .any(|pattern| candidates.iter().any(|x| pattern.matches_with(x, options)))
+    }
+
+    pub fn apply(&self, scan_info: &mut ScanInfo) {
+        if self.is_empty() {
+            return;
+        }
+        scan_info.found_files = scan_info
+            .found_files
+            .iter()
+            .map(|x| {
+                let mut y = x.clone();
+                y.ignored = y.ignored || self.is_path_ignored(x.original_path());
+                y
+            })
+            .collect();
+    }
 }
 
 pub fn scan_game_for_restoration(
@@ -1358,6 +1842,7 @@ pub fn scan_game_for_restoration(
         found_registry_keys,
         available_backups,
         backup,
+        max_total_size: None,
     }
 }
 
@@ -1394,6 +1879,7 @@ pub fn back_up_game(
     merge: bool,
     now: &chrono::DateTime<chrono::Utc>,
     format: &BackupFormats,
+    on_file: &dyn Fn(&ScannedFile, bool),
 ) -> BackupInfo {
     log::trace!("[{}] preparing for backup", &info.game_name);
 
@@ -1414,24 +1900,9 @@ pub fn back_up_game(
     };
 
     if able_to_prepare {
-        layout.back_up(info, now, format)
+        layout.back_up(info, now, format, on_file)
     } else {
-        let mut backup_info = BackupInfo::default();
-
-        for file in &info.found_files {
-            if file.ignored {
-                continue;
-            }
-            backup_info.failed_files.insert(file.clone());
-        }
-        for reg_path in &info.found_registry_keys {
-            if reg_path.ignored {
-                continue;
-            }
-            backup_info.failed_registry.insert(reg_path.path.clone());
-        }
-
-        backup_info
+        BackupInfo::all_failed(info)
     }
 }
 
@@ -1769,7 +2240,6 @@ pub fn normalize_title(title: &str) -> String {
     normalized.trim().to_string()
 }
 
-#[cfg(target_os = "windows")]
 pub fn sha1(content: String) -> String {
     use sha1::Digest;
     let mut hasher = sha1::Sha1::new();
@@ -1777,6 +2247,22 @@ pub fn sha1(content: String) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Follow a chain of renames (old name -> new name) to the game's current name.
+/// Returns `None` if the name has no alias or if the chain loops back on itself.
+pub fn resolve_game_alias(aliases: &std::collections::BTreeMap<String, String>, name: &str) -> Option<String> {
+    let mut current = aliases.get(name)?;
+    let mut visited = std::collections::HashSet::from([name]);
+
+    while let Some(next) = aliases.get(current) {
+        if !visited.insert(current) {
+            return None;
+        }
+        current = next;
+    }
+
+    (current != name).then(|| current.to_owned())
+}
+
 pub struct TitleFinder {
     all_games: std::collections::HashSet<String>,
     can_backup: std::collections::HashSet<String>,
@@ -1784,6 +2270,7 @@ pub struct TitleFinder {
     steam_ids: std::collections::HashMap<u32, String>,
     gog_ids: std::collections::HashMap<u64, String>,
     normalized: std::collections::HashMap<String, String>,
+    aliases: std::collections::HashMap<String, String>,
 }
 
 impl TitleFinder {
@@ -1805,7 +2292,23 @@ impl TitleFinder {
             steam_ids,
             gog_ids,
             normalized,
+            aliases: Default::default(),
+        }
+    }
+
+    /// Resolve games by their old names, for entries that were renamed in the manifest.
+    pub fn with_aliases(mut self, aliases: &std::collections::BTreeMap<String, String>) -> Self {
+        for old in aliases.keys() {
+            if let Some(new) = resolve_game_alias(aliases, old) {
+                if self.all_games.contains(&new) {
+                    self.normalized
+                        .entry(normalize_title(old))
+                        .or_insert_with(|| new.clone());
+                    self.aliases.insert(old.clone(), new);
+                }
+            }
         }
+        self
     }
 
     fn eligible(&self, game: &str, backup: bool, restore: bool) -> bool {
@@ -1872,6 +2375,15 @@ impl TitleFinder {
             }
         }
 
+        for name in names {
+            if let Some(found) = self.aliases.get(name) {
+                if self.eligible(found, backup, restore) {
+                    output.insert(found.to_owned());
+                    return output;
+                }
+            }
+        }
+
         if normalized {
             for name in names {
                 if let Some(found) = self.normalized.get(&normalize_title(name)) {
@@ -1912,7 +2424,7 @@ pub fn compare_games_by_size(
 #[cfg(test)]
 mod tests {
     use crate::{
-        config::ToggledRegistryEntry,
+        config::{GameFilter, ToggledRegistryEntry},
         serialization::ResourceFile,
         testing::{repo, s},
     };
@@ -2066,10 +2578,122 @@ mod tests {
     }
 
     #[test]
-    fn can_scan_game_for_backup_with_file_matches() {
-        assert_eq!(
-            ScanInfo {
-                game_name: s("game1"),
+    fn can_check_path_candidates() {
+        let ignored = StrictPath::new(format!("{}/tests/root1/game1/subdir/file2.txt", repo()));
+        let filter = BackupFilter {
+            ignored_paths: vec![ignored.clone()],
+            ..Default::default()
+        };
+        let candidates = find_path_candidates(
+            &manifest().0["game1"],
+            "game1",
+            &config().roots,
+            &StrictPath::new(repo()),
+            &Launchers::default(),
+            &None,
+            &filter,
+            &None,
+            &InstallDirRanking::scan(&config().roots, &manifest(), &["game1".to_string()]),
+            &Default::default(),
+        );
+        let filter = filter.compile();
+        let checked: std::collections::HashMap<_, _> = candidates
+            .iter()
+            .map(|x| (x.path.render(), x.check("game1", &filter, &ToggledPaths::default())))
+            .collect();
+
+        let path = |x: &str| StrictPath::new(format!("{}/tests/{}", repo(), x)).render();
+        assert_eq!(
+            hashmap! {
+                path("root1/game1/file1.txt") => PathCandidateCheck::default(),
+                path("root1/game1/subdir") => PathCandidateCheck {
+                    exists: true,
+                    files: 0,
+                    excluded: btreemap! { BackupExclusion::IgnoredPath(ignored) => 1 },
+                },
+                path("root2/game1/file1.txt") => PathCandidateCheck {
+                    exists: true,
+                    files: 1,
+                    excluded: btreemap! {},
+                },
+                path("root2/game1/subdir") => PathCandidateCheck::default(),
+            },
+            checked,
+        );
+    }
+
+    #[test]
+    fn can_find_fixed_prefix_of_path_candidates() {
+        let candidate = |path: &str| PathCandidate {
+            root: None,
+            source: PathCandidateSource::SteamCloud,
+            path: StrictPath::new(path.to_string()),
+            case_sensitive: None,
+        };
+        let path = |x: &str| Some(std::path::PathBuf::from(StrictPath::new(x.to_string()).interpret()));
+
+        assert_eq!(path("/games/foo/saves"), candidate("/games/foo/saves").fixed_prefix());
+        assert_eq!(path("/games/foo"), candidate("/games/foo/*.sav").fixed_prefix());
+        assert_eq!(path("/games"), candidate("/games/f?o/[ab]/saves").fixed_prefix());
+        assert_eq!(None, candidate("/*/saves").fixed_prefix());
+        assert_eq!(None, candidate("/games/<skip>/saves").fixed_prefix());
+    }
+
+    #[test]
+    fn prefers_launcher_install_folder_for_lutris_roots() {
+        let root = RootsConfig {
+            path: StrictPath::new(s("/lutris")),
+            store: Store::Lutris,
+        };
+        let ranking = InstallDirRanking(hashmap! {
+            (root.clone(), s("game1")) => (i64::MAX, s("Guessed")),
+        });
+        let install_dir = StrictPath::new(s("/games/Actual"));
+
+        assert_eq!(
+            Some(s("Actual")),
+            install_dir_name(&root, "game1", &ranking, Some(&install_dir))
+        );
+        assert_eq!(Some(s("Guessed")), install_dir_name(&root, "game1", &ranking, None));
+    }
+
+    #[test]
+    fn uses_launcher_install_folder_for_itch_and_bottles_roots() {
+        let install_dir = StrictPath::new(s("/games/Actual"));
+
+        for store in [Store::Bottles, Store::Itch] {
+            let root = RootsConfig {
+                path: StrictPath::new(s("/launcher")),
+                store,
+            };
+            assert_eq!(
+                Some(s("Actual")),
+                install_dir_name(&root, "game1", &InstallDirRanking::default(), Some(&install_dir))
+            );
+        }
+    }
+
+    #[test]
+    fn does_not_use_launcher_install_folder_for_other_roots() {
+        let install_dir = StrictPath::new(s("/games/Actual"));
+
+        for store in [Store::Heroic, Store::Steam, Store::Other] {
+            let root = RootsConfig {
+                path: StrictPath::new(s("/launcher")),
+                store,
+            };
+            assert_eq!(
+                None,
+                install_dir_name(&root, "game1", &InstallDirRanking::default(), Some(&install_dir))
+            );
+        }
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_file_matches() {
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
                 found_files: hashset! {
                     ScannedFile::new(format!("{}/tests/root1/game1/subdir/file2.txt", repo()), 2, "9d891e731f75deae56884d79e9816736b7488080").change_new(),
                     ScannedFile::new(format!("{}/tests/root2/game1/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
@@ -2082,9 +2706,9 @@ mod tests {
                 "game1",
                 &config().roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(&config().roots, &manifest(), &["game1".to_string()]),
                 &ToggledPaths::default(),
@@ -2109,9 +2733,9 @@ mod tests {
                 "game 2",
                 &config().roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(&config().roots, &manifest(), &["game 2".to_string()]),
                 &ToggledPaths::default(),
@@ -2143,9 +2767,9 @@ mod tests {
                 "game5",
                 roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(roots, &manifest(), &["game5".to_string()]),
                 &ToggledPaths::default(),
@@ -2177,9 +2801,9 @@ mod tests {
                 "game 2",
                 roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(roots, &manifest(), &["game 2".to_string()]),
                 &ToggledPaths::default(),
@@ -2191,6 +2815,145 @@ mod tests {
         );
     }
 
+    fn steam_manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            steam-game:
+              files:
+                <base>/file1.txt: {}
+              steam:
+                id: 101
+            steam-game-without-appmanifest:
+              files:
+                <base>/file1.txt: {}
+              installDir:
+                steam-game: {}
+              steam:
+                id: 103
+            steam-cloud-game:
+              steam:
+                id: 104
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn can_rank_steam_install_dir_from_appmanifest() {
+        let roots = &[RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/steam", repo())),
+            store: Store::Steam,
+        }];
+        let ranking = InstallDirRanking::scan(roots, &steam_manifest(), &["steam-game".to_string()]);
+        assert_eq!(Some(s("Steam Game Folder")), ranking.get(&roots[0], "steam-game"));
+    }
+
+    #[test]
+    fn can_rank_steam_install_dir_by_fuzzy_matching_without_appmanifest() {
+        let roots = &[RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/steam", repo())),
+            store: Store::Steam,
+        }];
+        let ranking = InstallDirRanking::scan(
+            roots,
+            &steam_manifest(),
+            &["steam-game-without-appmanifest".to_string()],
+        );
+        assert_eq!(
+            Some(s("steam-game")),
+            ranking.get(&roots[0], "steam-game-without-appmanifest")
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_install_dir_from_steam_appmanifest() {
+        let roots = &[RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/steam", repo())),
+            store: Store::Steam,
+        }];
+        let manifest = steam_manifest();
+        assert_eq!(
+            ScanInfo {
+                game_name: s("steam-game"),
+                found_files: hashset! {
+                    ScannedFile::new(format!("{}/tests/launchers/steam/steamapps/common/Steam Game Folder/file1.txt", repo()), 2, "3f786850e387550fdab836ed7e6dc881de23001b").change_new(),
+                },
+                found_registry_keys: hashset! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest.0["steam-game"],
+                "steam-game",
+                roots,
+                &StrictPath::new(repo()),
+                &Launchers::default(),
+                &Some(101),
+                &CompiledFilter::default(),
+                &None,
+                &InstallDirRanking::scan(roots, &manifest, &["steam-game".to_string()]),
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            ),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_steam_cloud_saves_for_selected_users() {
+        let roots = &[RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/steam", repo())),
+            store: Store::Steam,
+        }];
+        let manifest = steam_manifest();
+        let cases = [
+            (
+                BackupFilter::default(),
+                hashset! {
+                    ScannedFile::new(format!("{}/tests/launchers/steam/userdata/12345/104/remote/save.dat", repo()), 1, "86f7e437faa5a7fce15d1ddcb9eaeaea377667b8").change_new(),
+                    ScannedFile::new(format!("{}/tests/launchers/steam/userdata/67890/104/remote/save.dat", repo()), 2, "9a900f538965a426994e1e90600920aff0b4e8d2").change_new(),
+                },
+            ),
+            (
+                BackupFilter {
+                    steam_users: vec![s("67890")],
+                    ..Default::default()
+                },
+                hashset! {
+                    ScannedFile::new(format!("{}/tests/launchers/steam/userdata/67890/104/remote/save.dat", repo()), 2, "9a900f538965a426994e1e90600920aff0b4e8d2").change_new(),
+                },
+            ),
+        ];
+
+        for (filter, found_files) in cases {
+            assert_eq!(
+                ScanInfo {
+                    game_name: s("steam-cloud-game"),
+                    found_files,
+                    found_registry_keys: hashset! {},
+                    ..Default::default()
+                },
+                scan_game_for_backup(
+                    &manifest.0["steam-cloud-game"],
+                    "steam-cloud-game",
+                    roots,
+                    &StrictPath::new(repo()),
+                    &Launchers::default(),
+                    &Some(104),
+                    &filter.compile(),
+                    &None,
+                    &InstallDirRanking::default(),
+                    &ToggledPaths::default(),
+                    &ToggledRegistry::default(),
+                    None,
+                    &[],
+                    &Default::default(),
+                ),
+            );
+        }
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn can_scan_game_for_backup_with_file_matches_in_custom_home_folder() {
@@ -2215,9 +2978,9 @@ mod tests {
                 "game4",
                 roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(roots, &manifest(), &["game4".to_string()]),
                 &ToggledPaths::default(),
@@ -2252,9 +3015,9 @@ mod tests {
                 "game4",
                 roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(roots, &manifest(), &["game4".to_string()]),
                 &ToggledPaths::default(),
@@ -2282,9 +3045,9 @@ mod tests {
                 "game4",
                 &config().roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &Some(StrictPath::new(format!("{}/tests/wine-prefix", repo()))),
                 &InstallDirRanking::scan(&config().roots, &manifest(), &["game4".to_string()]),
                 &ToggledPaths::default(),
@@ -2312,9 +3075,9 @@ mod tests {
                 "fake-registry",
                 &config().roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &Some(StrictPath::new(format!("{}/tests/wine-prefix", repo()))),
                 &InstallDirRanking::scan(&config().roots, &manifest(), &["fake-registry".to_string()]),
                 &ToggledPaths::default(),
@@ -2378,9 +3141,9 @@ mod tests {
                     "game1",
                     &config().roots,
                     &StrictPath::new(repo()),
-                    &HeroicGames::default(),
+                    &Launchers::default(),
                     &None,
-                    &filter,
+                    &filter.compile(),
                     &None,
                     &InstallDirRanking::scan(&config().roots, &manifest(), &["game1".to_string()]),
                     &ignored,
@@ -2393,6 +3156,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn can_scan_game_for_backup_with_game_filter() {
+        let file1 = format!("{}/tests/root2/game1/file1.txt", repo());
+        let file2 = format!("{}/tests/root1/game1/subdir/file2.txt", repo());
+        let cases = [
+            (
+                GameFilter {
+                    ignored_paths: vec![StrictPath::new(s("file2.*"))],
+                    max_total_size: Some(1),
+                    ..Default::default()
+                },
+                hashset! {
+                    ScannedFile::new(&file1, 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                    ScannedFile::new(&file2, 2, "").excluded(BackupExclusion::GameIgnoredPath(StrictPath::new(s("file2.*")))),
+                },
+                None,
+            ),
+            (
+                GameFilter {
+                    max_file_size: Some(1),
+                    max_total_size: Some(0),
+                    ..Default::default()
+                },
+                hashset! {
+                    ScannedFile::new(&file1, 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                    ScannedFile::new(&file2, 2, "").excluded(BackupExclusion::MaxFileSize(1)),
+                },
+                Some(0),
+            ),
+        ];
+
+        for (game_filter, found, exceeded) in cases {
+            let filter = BackupFilter {
+                games: btreemap! { s("game1") => game_filter.clone() },
+                ..Default::default()
+            };
+            let scan = scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::default(),
+                &None,
+                &filter.compile(),
+                &None,
+                &InstallDirRanking::scan(&config().roots, &manifest(), &["game1".to_string()]),
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            );
+            assert_eq!(
+                ScanInfo {
+                    game_name: s("game1"),
+                    found_files: found,
+                    found_registry_keys: hashset! {},
+                    max_total_size: game_filter.max_total_size,
+                    ..Default::default()
+                },
+                scan,
+            );
+            assert_eq!(exceeded, scan.size_limit_exceeded());
+        }
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn can_scan_game_for_backup_with_registry_matches_on_leaf_key_with_values() {
@@ -2416,9 +3245,9 @@ mod tests {
                 "game3",
                 &config().roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(&config().roots, &manifest(), &["game3".to_string()]),
                 &ToggledPaths::default(),
@@ -2455,9 +3284,9 @@ mod tests {
                 "game3-outer",
                 &config().roots,
                 &StrictPath::new(repo()),
-                &HeroicGames::default(),
+                &Launchers::default(),
                 &None,
-                &BackupFilter::default(),
+                &CompiledFilter::default(),
                 &None,
                 &InstallDirRanking::scan(&config().roots, &manifest(), &["game3-outer".to_string()]),
                 &ToggledPaths::default(),
@@ -2549,7 +3378,7 @@ mod tests {
                     "game3-outer",
                     &config().roots,
                     &StrictPath::new(repo()),
-                    &HeroicGames::default(),
+                    &Launchers::default(),
                     &None,
                     &filter,
                     &None,
@@ -2589,8 +3418,8 @@ mod tests {
                     name: ".".into(),
                     when: now(),
                     files: btreemap! {
-                        mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                        mapping_file_key("/file2.txt") => IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                        mapping_file_key("/file1.txt") => IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                        mapping_file_key("/file2.txt") => IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                     },
                     ..Default::default()
                 }]),
@@ -2607,10 +3436,12 @@ mod tests {
                 mapping_file_key("/file1.txt") => IndividualMappingFile {
                     hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
                     size: 1,
+                    ..Default::default()
                 },
                 mapping_file_key("/file2.txt") => IndividualMappingFile {
                     hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
                     size: 2,
+                    ..Default::default()
                 },
             },
             ..Default::default()
@@ -2626,6 +3457,7 @@ mod tests {
                         hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
                         original_path: Some(make_original_path("/file1.txt")),
                         ignored: false,
+                        excluded: None,
                         change: ScanChange::New,
                         container: None,
                         redirected: None,
@@ -2636,6 +3468,7 @@ mod tests {
                         hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
                         original_path: Some(make_original_path("/file2.txt")),
                         ignored: false,
+                        excluded: None,
                         change: ScanChange::New,
                         container: None,
                         redirected: None,
@@ -2769,6 +3602,7 @@ mod tests {
                 hash: "1".to_string(),
                 original_path: Some(StrictPath::new(s("file1.txt"))),
                 ignored: false,
+                excluded: None,
                 change: Default::default(),
                 container: None,
                 redirected: None,
@@ -2779,6 +3613,7 @@ mod tests {
                 hash: "1b".to_string(),
                 original_path: Some(StrictPath::new(s("file1.txt"))),
                 ignored: false,
+                excluded: None,
                 change: Default::default(),
                 container: None,
                 redirected: None,
@@ -2803,6 +3638,7 @@ mod tests {
                 hash: "1a".to_string(),
                 original_path: None,
                 ignored: false,
+                excluded: None,
                 change: Default::default(),
                 container: None,
                 redirected: None,
@@ -2816,10 +3652,222 @@ mod tests {
                 hash: "1b".to_string(),
                 original_path: None,
                 ignored: false,
+                excluded: None,
                 change: Default::default(),
                 container: None,
                 redirected: None,
             }));
         }
     }
+
+    mod restore_filter {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn allows_everything_when_empty() {
+            let filter = RestoreFilter::default();
+            assert!(!filter.is_path_ignored(&StrictPath::new(s("/saves/slot1/data.sav"))));
+        }
+
+        #[test]
+        fn can_include_by_file_or_parent_folder() {
+            let filter = RestoreFilter::new(&[s("/saves/slot1"), s("/saves/*.cfg")], &[]).unwrap();
+            assert!(!filter.is_path_ignored(&StrictPath::new(s("/saves/slot1/data.sav"))));
+            assert!(!filter.is_path_ignored(&StrictPath::new(s("/saves/game.cfg"))));
+            assert!(filter.is_path_ignored(&StrictPath::new(s("/saves/slot2/data.sav"))));
+            assert!(filter.is_path_ignored(&StrictPath::new(s("/saves/slot10/data.sav"))));
+        }
+
+        #[test]
+        fn exclusions_take_precedence() {
+            let filter = RestoreFilter::new(&[s("/saves")], &[s("/saves/*/*.bak")]).unwrap();
+            assert!(!filter.is_path_ignored(&StrictPath::new(s("/saves/slot1/data.sav"))));
+            assert!(filter.is_path_ignored(&StrictPath::new(s("/saves/slot1/data.bak"))));
+        }
+
+        #[test]
+        fn rejects_invalid_patterns() {
+            assert!(matches!(
+                RestoreFilter::new(&[s("/saves/[abc")], &[]),
+                Err(Error::CliInvalidPattern { pattern, .. }) if pattern == "/saves/[abc",
+            ));
+            assert!(matches!(
+                RestoreFilter::new(&[], &[s("/saves/[abc")]),
+                Err(Error::CliInvalidPattern { pattern, .. }) if pattern == "/saves/[abc",
+            ));
+        }
+
+        #[test]
+        fn can_apply_to_scan_info() {
+            let mut scan_info = ScanInfo {
+                game_name: s("game1"),
+                found_files: hashset! {
+                    ScannedFile::new("/backup/file1.txt", 1, "1").original("/saves/file1.txt"),
+                    ScannedFile::new("/backup/file2.txt", 2, "2").original("/saves/file2.txt"),
+                },
+                ..Default::default()
+            };
+            RestoreFilter::new(&[], &[s("/saves/file2.txt")])
+                .unwrap()
+                .apply(&mut scan_info);
+
+            assert_eq!(
+                hashset! {
+                    ScannedFile::new("/backup/file1.txt", 1, "1").original("/saves/file1.txt"),
+                    ScannedFile::new("/backup/file2.txt", 2, "2").original("/saves/file2.txt").ignored(),
+                },
+                scan_info.found_files,
+            );
+        }
+
+        #[test]
+        fn toggles_redirected_files_by_original_path() {
+            let redirected = |file: ScannedFile, target: &str| ScannedFile {
+                redirected: Some(StrictPath::new(s(target))),
+                ..file
+            };
+            let mut scan_info = ScanInfo {
+                game_name: s("game1"),
+                found_files: hashset! {
+                    redirected(ScannedFile::new("/backup/file1.txt", 1, "1").original("/saves/file1.txt"), "/new/file1.txt"),
+                    redirected(ScannedFile::new("/backup/file2.txt", 2, "2").original("/saves/file2.txt"), "/new/file2.txt"),
+                },
+                ..Default::default()
+            };
+            scan_info.update_ignored(
+                &ToggledPaths::new(btreemap! {
+                    s("game1") => btreemap! {
+                        StrictPath::new(s("/saves/file1.txt")) => false,
+                        StrictPath::new(s("/new/file2.txt")) => false,
+                    }
+                }),
+                &ToggledRegistry::default(),
+            );
+
+            assert_eq!(
+                hashset! {
+                    redirected(ScannedFile::new("/backup/file1.txt", 1, "1").original("/saves/file1.txt").ignored(), "/new/file1.txt"),
+                    redirected(ScannedFile::new("/backup/file2.txt", 2, "2").original("/saves/file2.txt"), "/new/file2.txt"),
+                },
+                scan_info.found_files,
+            );
+        }
+    }
+
+    mod as_of {
+        use super::*;
+        use chrono::TimeZone;
+        use pretty_assertions::assert_eq;
+
+        fn local(
+            year: i32,
+            month: u32,
+            day: u32,
+            hour: u32,
+            minute: u32,
+            second: u32,
+        ) -> chrono::DateTime<chrono::Utc> {
+            chrono::Local
+                .with_ymd_and_hms(year, month, day, hour, minute, second)
+                .unwrap()
+                .with_timezone(&chrono::Utc)
+        }
+
+        #[test]
+        fn can_parse_rfc3339() {
+            assert_eq!(
+                Some(chrono::Utc.with_ymd_and_hms(2023, 3, 1, 10, 0, 0).unwrap()),
+                parse_as_of("2023-03-01T12:00:00+02:00"),
+            );
+        }
+
+        #[test]
+        fn can_parse_local_date_and_time() {
+            assert_eq!(Some(local(2023, 3, 1, 12, 30, 15)), parse_as_of("2023-03-01T12:30:15"));
+            assert_eq!(Some(local(2023, 3, 1, 12, 30, 0)), parse_as_of("2023-03-01 12:30"));
+        }
+
+        #[test]
+        fn treats_plain_date_as_end_of_day() {
+            let end = local(2023, 3, 1, 23, 59, 59) + chrono::Duration::nanoseconds(999_999_999);
+            assert_eq!(Some(end), parse_as_of("2023-03-01"));
+
+            let last_second = local(2023, 3, 1, 23, 59, 59) + chrono::Duration::milliseconds(500);
+            assert!(last_second <= parse_as_of("2023-03-01").unwrap());
+            assert!(local(2023, 3, 2, 0, 0, 0) > parse_as_of("2023-03-01").unwrap());
+        }
+
+        #[test]
+        fn rejects_invalid_input() {
+            assert_eq!(None, parse_as_of(""));
+            assert_eq!(None, parse_as_of("yesterday"));
+            assert_eq!(None, parse_as_of("2023-02-30"));
+        }
+    }
+
+    mod title_finder {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        fn aliases() -> std::collections::BTreeMap<String, String> {
+            btreemap! {
+                s("game1-old") => s("game2"),
+                s("game3") => s("game3-middle"),
+                s("game3-middle") => s("game3-new"),
+            }
+        }
+
+        fn title_finder() -> TitleFinder {
+            let manifest = Manifest::load_from_string(
+                r#"
+                game2: {}
+                game3-new: {}
+                "#,
+            )
+            .unwrap();
+            let layout = BackupLayout::new(
+                StrictPath::new(format!("{}/tests/backup", repo())),
+                Retention::default(),
+            );
+            TitleFinder::new(&manifest, &layout).with_aliases(&aliases())
+        }
+
+        #[test]
+        fn can_resolve_alias_chains() {
+            assert_eq!(Some(s("game2")), resolve_game_alias(&aliases(), "game1-old"));
+            assert_eq!(Some(s("game3-new")), resolve_game_alias(&aliases(), "game3"));
+            assert_eq!(None, resolve_game_alias(&aliases(), "game2"));
+            assert_eq!(
+                None,
+                resolve_game_alias(&btreemap! { s("a") => s("b"), s("b") => s("a") }, "a")
+            );
+        }
+
+        #[test]
+        fn can_find_renamed_game_by_old_name() {
+            let finder = title_finder();
+            assert_eq!(
+                btreeset! { s("game2") },
+                finder.find(&[s("game1-old")], &None, &None, false, true, false)
+            );
+            assert_eq!(
+                btreeset! { s("game2") },
+                finder.find(&[s("Game1 Old")], &None, &None, true, true, false)
+            );
+            assert_eq!(
+                btreeset! { s("game3-new") },
+                finder.find(&[s("game3")], &None, &None, false, true, false)
+            );
+        }
+
+        #[test]
+        fn prefers_existing_backups_under_old_name_when_restoring() {
+            let finder = title_finder();
+            assert_eq!(
+                btreeset! { s("game3") },
+                finder.find(&[s("game3")], &None, &None, false, false, true)
+            );
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::{
-    config::{BackupFilter, ToggledRegistry},
+    config::{CompiledFilter, ToggledRegistry},
     prelude::{
         Error, RegistryItem, ScanChange, ScannedRegistry, ScannedRegistryValue, ScannedRegistryValues, StrictPath,
     },
@@ -42,7 +42,7 @@ pub struct Entry {
 pub fn scan_registry(
     game: &str,
     path: &str,
-    filter: &BackupFilter,
+    filter: &CompiledFilter,
     toggled: &ToggledRegistry,
     previous: &Option<Hives>,
 ) -> Result<Vec<ScannedRegistry>, Error> {
@@ -59,7 +59,7 @@ fn scan_registry_key(
     hive: winreg::HKEY,
     hive_name: &str,
     key: &str,
-    filter: &BackupFilter,
+    filter: &CompiledFilter,
     toggled: &ToggledRegistry,
     previous: &Option<Hives>,
 ) -> Result<Vec<ScannedRegistry>, Error> {
@@ -25,6 +25,10 @@ pub fn is_false(v: &bool) -> bool {
     !v
 }
 
+pub fn is_default<T: Default + PartialEq>(v: &T) -> bool {
+    *v == T::default()
+}
+
 pub fn is_empty_set<T>(v: &std::collections::HashSet<T>) -> bool {
     v.is_empty()
 }
@@ -0,0 +1,205 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{config::RootsConfig, manifest::Store, prelude::StrictPath};
+
+/// Offset between a 64-bit Steam ID and the 32-bit account ID used for `userdata` folder names.
+const STEAM_ID_64_BASE: u64 = 76561197960265728;
+
+/// Matches `userdata/<user>/<app>/`, where saves and screenshots are kept for each Steam user.
+static RE_USERDATA: Lazy<Regex> = Lazy::new(|| Regex::new(r#"[/\\]userdata[/\\](\d+)[/\\]\d+[/\\]"#).unwrap());
+static RE_LOGIN_USER: Lazy<Regex> = Lazy::new(|| Regex::new(r#""(\d{17})"\s*\{([^}]*)\}"#).unwrap());
+static RE_ACCOUNT_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)"AccountName"\s+"([^"]*)""#).unwrap());
+static RE_PERSONA_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)"PersonaName"\s+"([^"]*)""#).unwrap());
+
+/// Get the Steam user that a file belongs to, if it is inside of Steam's `userdata` folder.
+pub fn user_id_in_path(path: &StrictPath) -> Option<String> {
+    RE_USERDATA
+        .captures(&path.render())
+        .and_then(|captures| captures.get(1))
+        .map(|x| x.as_str().to_string())
+}
+
+/// Move a file from one Steam user's `userdata` folder to another's.
+/// Paths belonging to other users (or no user) are returned as-is.
+pub fn retarget_user(path: &StrictPath, from: &str, to: &str) -> StrictPath {
+    let rendered = path.render();
+    match RE_USERDATA.captures(&rendered).and_then(|captures| captures.get(1)) {
+        Some(user) if user.as_str() == from => StrictPath::new(format!(
+            "{}{}{}",
+            &rendered[..user.start()],
+            to,
+            &rendered[user.end()..]
+        )),
+        _ => path.clone(),
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SteamUser {
+    /// Account ID, as used for the user's `userdata` folder.
+    pub id: String,
+    pub account_name: Option<String>,
+    pub persona_name: Option<String>,
+}
+
+//
+/// Steam users who have logged in on this computer
+//
+#[derive(Clone, Debug, Default)]
+pub struct SteamUsers(Vec<SteamUser>);
+
+impl SteamUsers {
+    pub fn scan(roots: &[RootsConfig]) -> Self {
+        let mut users = std::collections::BTreeMap::<String, SteamUser>::new();
+
+        for root in roots.iter().filter(|x| x.store == Store::Steam) {
+            for user in Self::read_login_users(root) {
+                users.insert(user.id.clone(), user);
+            }
+
+            // Users who logged in before may no longer be listed in `loginusers.vdf`.
+            let Ok(entries) = std::fs::read_dir(root.path.joined("userdata").interpret()) else {
+                continue;
+            };
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let id = entry.file_name().to_string_lossy().to_string();
+                if id == "0" || id.parse::<u32>().is_err() || !entry.path().is_dir() {
+                    continue;
+                }
+                users.entry(id.clone()).or_insert_with(|| SteamUser {
+                    id,
+                    ..Default::default()
+                });
+            }
+        }
+
+        log::trace!("found Steam users: {:?}", &users);
+        Self(users.into_values().collect())
+    }
+
+    fn read_login_users(root: &RootsConfig) -> Vec<SteamUser> {
+        let file = root.path.joined("config").joined("loginusers.vdf");
+        let Some(content) = file.read() else {
+            return vec![];
+        };
+
+        RE_LOGIN_USER
+            .captures_iter(&content)
+            .filter_map(|captures| {
+                let id64 = captures.get(1)?.as_str().parse::<u64>().ok()?;
+                let body = captures.get(2)?.as_str();
+                Some(SteamUser {
+                    id: id64.checked_sub(STEAM_ID_64_BASE)?.to_string(),
+                    account_name: RE_ACCOUNT_NAME.captures(body).map(|x| x[1].to_string()),
+                    persona_name: RE_PERSONA_NAME.captures(body).map(|x| x[1].to_string()),
+                })
+            })
+            .collect()
+    }
+
+    /// Find a user's account ID from their account ID, 64-bit Steam ID, account name, or persona name.
+    /// Numeric IDs are accepted even if that user has never logged in on this computer.
+    pub fn resolve(&self, query: &str) -> Option<String> {
+        if let Ok(id) = query.parse::<u64>() {
+            return match id.checked_sub(STEAM_ID_64_BASE) {
+                Some(id) => Some(id.to_string()),
+                None => u32::try_from(id).ok().map(|id| id.to_string()),
+            };
+        }
+
+        let by_name = |name: &Option<String>| name.as_ref().map(|x| x.eq_ignore_ascii_case(query)).unwrap_or(false);
+        self.0
+            .iter()
+            .find(|x| by_name(&x.account_name))
+            .or_else(|| self.0.iter().find(|x| by_name(&x.persona_name)))
+            .map(|x| x.id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{repo, s};
+    use pretty_assertions::assert_eq;
+
+    fn roots() -> Vec<RootsConfig> {
+        vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/launchers/steam", repo())),
+            store: Store::Steam,
+        }]
+    }
+
+    #[test]
+    fn can_find_user_id_in_path() {
+        assert_eq!(
+            Some(s("12345")),
+            user_id_in_path(&StrictPath::new(s("/steam/userdata/12345/104/remote/save.dat")))
+        );
+        assert_eq!(
+            Some(s("12345")),
+            user_id_in_path(&StrictPath::new(s(
+                "/steam/userdata/12345/760/remote/104/screenshots/1.jpg"
+            )))
+        );
+        assert_eq!(
+            None,
+            user_id_in_path(&StrictPath::new(s("/steam/userdata/12345/config/x.vdf")))
+        );
+        assert_eq!(None, user_id_in_path(&StrictPath::new(s("/saves/104/save.dat"))));
+    }
+
+    #[test]
+    fn can_retarget_user() {
+        let path = StrictPath::new(s("/steam/userdata/12345/104/remote/save.dat"));
+        assert_eq!(
+            StrictPath::new(s("/steam/userdata/67890/104/remote/save.dat")),
+            retarget_user(&path, "12345", "67890")
+        );
+        assert_eq!(path, retarget_user(&path, "24680", "67890"));
+    }
+
+    #[test]
+    fn scan_finds_nothing_when_folder_does_not_exist() {
+        let roots = vec![RootsConfig {
+            path: StrictPath::new(format!("{}/tests/nonexistent", repo())),
+            store: Store::Steam,
+        }];
+        assert_eq!(Vec::<SteamUser>::new(), SteamUsers::scan(&roots).0);
+    }
+
+    #[test]
+    fn scan_finds_logged_in_users_and_userdata_folders() {
+        assert_eq!(
+            vec![
+                SteamUser {
+                    id: s("12345"),
+                    account_name: Some(s("first")),
+                    persona_name: Some(s("First User")),
+                },
+                SteamUser {
+                    id: s("24680"),
+                    account_name: None,
+                    persona_name: None,
+                },
+                SteamUser {
+                    id: s("67890"),
+                    account_name: Some(s("second")),
+                    persona_name: Some(s("Second User")),
+                },
+            ],
+            SteamUsers::scan(&roots()).0,
+        );
+    }
+
+    #[test]
+    fn can_resolve_user() {
+        let users = SteamUsers::scan(&roots());
+        assert_eq!(Some(s("12345")), users.resolve("12345"));
+        assert_eq!(Some(s("12345")), users.resolve("76561197960278073"));
+        assert_eq!(Some(s("67890")), users.resolve("second"));
+        assert_eq!(Some(s("67890")), users.resolve("second user"));
+        assert_eq!(Some(s("99999")), users.resolve("99999"));
+        assert_eq!(None, users.resolve("nobody"));
+    }
+}